@@ -30,6 +30,11 @@ async fn main() -> Result<()> {
     println!("Launching browser...");
     let browser = Browser::launch_with_config(config).await?;
 
+    // Local checks first: no network needed
+    println!("\n--- Local self-test ---\n");
+    let report = browser.run_detection_suite().await?;
+    print!("{}", report);
+
     // Test 1: bot.sannysoft.com
     println!("\n--- Test 1: bot.sannysoft.com ---\n");
     let page = browser.new_page("https://bot.sannysoft.com").await?;
@@ -2,7 +2,8 @@
 //!
 //! Handles Chrome discovery, launching with stealth flags, and binary patching.
 
-use std::path::PathBuf;
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
@@ -11,14 +12,160 @@ static BROWSER_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 use crate::cdp::transport::launch_chrome;
 use crate::cdp::{Connection, Transport};
+use crate::detection::{DetectionCheck, DetectionReport, DETECTION_JS, DETECTION_PAGE};
 use crate::error::{Error, Result};
 use crate::page::Page;
-use crate::stealth::{build_evasion_script, find_chrome, random_user_agent, ChromePatcher};
+use crate::stealth::evasions::accept_language;
+use crate::stealth::patcher::describe_offsets;
+use crate::stealth::{build_evasion_script_for, find_chrome, ChromePatcher, Fingerprint};
 use crate::StealthConfig;
 
-/// Stealth browser arguments (pre-built for zero allocation)
-fn stealth_args(config: &StealthConfig) -> Vec<String> {
-    let mut args = vec![
+/// Oldest Chrome major the CDP usage here is written against. Older builds
+/// only get a warning; newer ones are assumed to keep working.
+const MIN_EXPECTED_CHROME_VERSION: u32 = 110;
+
+/// Major version from a `Browser.getVersion` product string such as
+/// `HeadlessChrome/120.0.6099.109` or `Chrome/120`
+fn parse_chrome_major(product: &str) -> Option<u32> {
+    product
+        .split(|c: char| c == '/' || c.is_whitespace())
+        .filter_map(|token| {
+            let major = token.split('.').next()?;
+            if major.is_empty() || !major.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            major.parse().ok()
+        })
+        .next()
+}
+
+/// Warn about Chrome older than expected, fail below `min_chrome_version`
+fn check_chrome_version(product: &str, config: &StealthConfig) -> Result<()> {
+    let Some(major) = parse_chrome_major(product) else {
+        tracing::warn!("Could not parse Chrome version from '{}'", product);
+        return Ok(());
+    };
+
+    if let Some(min) = config.min_chrome_version {
+        if major < min {
+            return Err(Error::Launch(format!(
+                "Chrome {} is older than the required minimum {} ({})",
+                major, min, product
+            )));
+        }
+    }
+
+    if major < MIN_EXPECTED_CHROME_VERSION {
+        tracing::warn!(
+            "Chrome {} is older than {}; CDP behavior may differ",
+            major,
+            MIN_EXPECTED_CHROME_VERSION
+        );
+    }
+    Ok(())
+}
+
+/// The Chrome binary `config` launches: the configured or discovered one,
+/// patched (and verified) first when `patch_binary` is set
+pub(crate) fn chrome_binary(config: &StealthConfig) -> Result<PathBuf> {
+    let chrome_path = match &config.chrome_path {
+        Some(p) => PathBuf::from(p),
+        None => find_chrome()?,
+    };
+    if !config.patch_binary {
+        return Ok(chrome_path);
+    }
+
+    let mut patcher = ChromePatcher::new(&chrome_path)?;
+    if let Some(dir) = &config.patched_dir {
+        patcher = patcher.with_output_dir(dir);
+    }
+    let patched = patcher.get_patched_path()?;
+    if config.verify_patch_full {
+        let residual = patcher.residual_offsets()?;
+        if !residual.is_empty() {
+            return Err(Error::patching(
+                "verify_full",
+                format!(
+                    "{} unpatched markers remain at offsets {}",
+                    residual.len(),
+                    describe_offsets(&residual)
+                ),
+            ));
+        }
+    }
+    Ok(patched)
+}
+
+/// Stealth browser arguments (pre-built for zero allocation). `identity` is
+/// `config.fingerprint`, or the random one picked for this launch.
+fn stealth_args(config: &StealthConfig, identity: &Fingerprint) -> Vec<String> {
+    let mut args = if config.disable_default_args {
+        Vec::new()
+    } else {
+        default_args()
+    };
+
+    // A fixed identity goes first, so launch flags and the evasion script
+    // describe the same browser. Explicit config fields below override it:
+    // the viewport always sets the window size, and `languages` replaces the
+    // fingerprint's locale flags unless left at the default.
+    if let Some(fingerprint) = &config.fingerprint {
+        merge_args(&mut args, &fingerprint.to_chrome_args());
+    }
+
+    // Window size
+    merge_args(
+        &mut args,
+        &[format!(
+            "--window-size={},{}",
+            config.viewport_width, config.viewport_height
+        )],
+    );
+
+    // User agent: explicit, else the identity's
+    merge_args(
+        &mut args,
+        &[format!(
+            "--user-agent={}",
+            effective_user_agent(config, identity)
+        )],
+    );
+
+    // Locale: UI language and Accept-Language, matching navigator.languages
+    let languages = config.effective_languages();
+    if let Some(primary) = languages.first() {
+        merge_args(
+            &mut args,
+            &[
+                format!("--lang={}", primary),
+                format!("--accept-lang={}", languages.join(",")),
+            ],
+        );
+    }
+
+    // Headless mode
+    if config.headless {
+        args.push("--headless=new".into());
+    }
+
+    // Proxy
+    if let Some(ref proxy) = config.proxy {
+        args.push(format!("--proxy-server={}", proxy));
+    }
+
+    merge_args(&mut args, &config.extra_args);
+    args
+}
+
+/// User agent `config` presents: `user_agent` if set, else `identity`'s
+fn effective_user_agent<'a>(config: &'a StealthConfig, identity: &'a Fingerprint) -> &'a str {
+    config.user_agent.as_deref().unwrap_or(&identity.user_agent)
+}
+
+/// Built-in flags that hide automation and make the browser look natural
+fn default_args() -> Vec<String> {
+    vec![
         // Core automation hiding
         "--disable-blink-features=AutomationControlled".into(),
         "--disable-automation".into(),
@@ -50,28 +197,63 @@ fn stealth_args(config: &StealthConfig) -> Vec<String> {
         "--password-store=basic".into(),
         "--use-mock-keychain".into(),
         "--excludeSwitches=enable-automation".into(),
-        // Window size
-        format!(
-            "--window-size={},{}",
-            config.viewport_width, config.viewport_height
-        ),
-    ];
+    ]
+}
 
-    // User agent
-    let user_agent = config.user_agent.clone().unwrap_or_else(random_user_agent);
-    args.push(format!("--user-agent={}", user_agent));
+/// Flag name without its value (`--window-size=800,600` -> `--window-size`)
+fn flag_name(arg: &str) -> &str {
+    arg.split_once('=').map_or(arg, |(name, _)| name)
+}
 
-    // Headless mode
-    if config.headless {
-        args.push("--headless=new".into());
+/// Flags taking a comma-separated list. Chrome only reads one occurrence,
+/// so replacing them would silently drop the stealth defaults.
+const LIST_FLAGS: &[&str] = &[
+    "--disable-features",
+    "--enable-features",
+    "--disable-blink-features",
+    "--enable-blink-features",
+];
+
+/// Merge user flags into `args`: a flag that is already present is replaced
+/// in place (or, for [`LIST_FLAGS`], gets the new entries added to its
+/// list), anything else is appended.
+fn merge_args(args: &mut Vec<String>, extra: &[String]) {
+    for arg in extra {
+        let name = flag_name(arg);
+        let Some(i) = args.iter().position(|a| flag_name(a) == name) else {
+            args.push(arg.clone());
+            continue;
+        };
+        match (
+            LIST_FLAGS.contains(&name),
+            args[i].split_once('='),
+            arg.split_once('='),
+        ) {
+            (true, Some((_, current)), Some((_, added))) => {
+                let mut entries: Vec<&str> = current.split(',').filter(|e| !e.is_empty()).collect();
+                for entry in added.split(',').filter(|e| !e.is_empty()) {
+                    if !entries.contains(&entry) {
+                        entries.push(entry);
+                    }
+                }
+                args[i] = format!("{}={}", name, entries.join(","));
+            }
+            _ => args[i] = arg.clone(),
+        }
     }
+}
 
-    // Proxy
-    if let Some(ref proxy) = config.proxy {
-        args.push(format!("--proxy-server={}", proxy));
+/// `scheme://host[:port]` of an http(s) URL; `None` for about:, data:, etc.
+fn url_origin(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    if scheme != "http" && scheme != "https" {
+        return None;
     }
-
-    args
+    let host = rest
+        .split(['/', '?', '#'])
+        .next()
+        .filter(|h| !h.is_empty())?;
+    Some(format!("{}://{}", scheme, host))
 }
 
 /// Info about an open tab
@@ -80,16 +262,101 @@ pub struct TabInfo {
     pub id: String,
     pub title: String,
     pub url: String,
+    /// ID of the tab that opened this one (popups, `target=_blank` links)
+    pub opener_id: Option<String>,
+}
+
+/// Lock file marking a persistent profile as in use by an eoka browser
+const PROFILE_LOCK_FILE: &str = "eoka.lock";
+
+/// Chrome profile directory, either a throwaway temp dir or a persistent one
+enum UserDataDir {
+    /// Unique temp directory, deleted on close
+    Temp(PathBuf),
+    /// User-supplied profile, kept on close (only the lock file is removed)
+    Persistent(PathBuf),
+}
+
+impl UserDataDir {
+    /// Create a fresh temp profile directory
+    fn temp() -> Result<Self> {
+        let instance_id = BROWSER_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!(
+            "eoka-browser-{}-{}",
+            std::process::id(),
+            instance_id
+        ));
+
+        // Clean up any stale data
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path)?;
+        Ok(Self::Temp(path))
+    }
+
+    /// Claim a persistent profile directory, failing if another browser holds it
+    fn persistent(path: PathBuf) -> Result<Self> {
+        std::fs::create_dir_all(&path)?;
+        let lock = path.join(PROFILE_LOCK_FILE);
+        match std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&lock)
+        {
+            Ok(mut file) => {
+                use std::io::Write;
+                let _ = write!(file, "{}", std::process::id());
+                Ok(Self::Persistent(path))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Err(Error::Launch(format!(
+                "user data dir {:?} is already in use (remove {:?} if no browser is running)",
+                path, lock
+            ))),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn path(&self) -> &Path {
+        match self {
+            Self::Temp(p) | Self::Persistent(p) => p,
+        }
+    }
+
+    /// Best-effort cleanup: delete temp profiles, release persistent ones
+    fn cleanup(&self) {
+        match self {
+            Self::Temp(p) => {
+                let _ = std::fs::remove_dir_all(p);
+            }
+            Self::Persistent(p) => {
+                let _ = std::fs::remove_file(p.join(PROFILE_LOCK_FILE));
+            }
+        }
+    }
+}
+
+impl Drop for UserDataDir {
+    fn drop(&mut self) {
+        // Best-effort cleanup if close() wasn't called or launch failed midway.
+        // The Transport's Drop impl handles killing the Chrome process.
+        self.cleanup();
+    }
 }
 
 /// The main stealth browser
 pub struct Browser {
     connection: Connection,
     config: Arc<StealthConfig>,
-    /// User data directory (cleaned up on close)
-    user_data_dir: PathBuf,
+    /// User data directory (temp dirs are cleaned up on close)
+    user_data_dir: UserDataDir,
+    /// Identity for pages without their own fingerprint: `config.fingerprint`
+    /// or one picked at launch, shared by the launch flags and every page
+    fingerprint: Fingerprint,
     /// Evasion script (cached)
     evasion_script: String,
+    /// Page targets already wrapped or known at launch
+    claimed_targets: std::sync::Mutex<HashSet<String>>,
+    /// Discovered page targets not yet checked by `wait_for_new_page`
+    discovered_targets: std::sync::Mutex<VecDeque<String>>,
 }
 
 impl Browser {
@@ -102,90 +369,192 @@ impl Browser {
     pub async fn launch_with_config(config: StealthConfig) -> Result<Self> {
         let config = Arc::new(config);
 
-        // Create unique user data directory
-        let instance_id = BROWSER_COUNTER.fetch_add(1, Ordering::Relaxed);
-        let user_data_dir = std::env::temp_dir().join(format!(
-            "eoka-browser-{}-{}",
-            std::process::id(),
-            instance_id
-        ));
-
-        // Clean up any stale data
-        let _ = std::fs::remove_dir_all(&user_data_dir);
-        std::fs::create_dir_all(&user_data_dir)?;
-
-        // Find Chrome path
-        let chrome_path = match &config.chrome_path {
-            Some(p) => PathBuf::from(p),
-            None => find_chrome()?,
+        // Use the configured profile, or a unique temp one
+        let user_data_dir = match &config.user_data_dir {
+            Some(dir) => UserDataDir::persistent(dir.clone())?,
+            None => UserDataDir::temp()?,
         };
 
-        // Optionally patch the binary
-        let chrome_path = if config.patch_binary {
-            let patcher = ChromePatcher::new(&chrome_path)?;
-            patcher.get_patched_path()?
-        } else {
-            chrome_path
-        };
+        let chrome_path = chrome_binary(&config)?;
+
+        // One identity for the whole browser, so every page agrees with the
+        // launch flags and with each other
+        let fingerprint = config
+            .fingerprint
+            .clone()
+            .unwrap_or_else(Fingerprint::random);
 
         // Build args
-        let mut args = stealth_args(&config);
-        args.push(format!("--user-data-dir={}", user_data_dir.display()));
+        let mut args = stealth_args(&config, &fingerprint);
+        args.push(format!(
+            "--user-data-dir={}",
+            user_data_dir.path().display()
+        ));
 
         // Launch Chrome
         tracing::info!("Launching Chrome from {:?}", chrome_path);
         let (child, ws_url) = launch_chrome(&chrome_path, &args)?;
 
         // Create transport and connection
-        let transport = Transport::new(child, &ws_url)?;
+        let transport =
+            Transport::connect_with_log(Some(child), &ws_url, config.cdp_log.as_deref()).await?;
         let connection = Connection::new(transport);
 
         // Get browser version
         let version = connection.version().await?;
         tracing::info!("Connected to Chrome: {}", version.product);
+        check_chrome_version(&version.product, &config)?;
+
+        // Track targets so pages opened by the site (popups) can be picked up.
+        // Everything that exists right now is ours, not a popup.
+        connection.set_discover_targets(true).await?;
+        let claimed_targets = connection
+            .get_targets()
+            .await?
+            .into_iter()
+            .map(|t| t.target_id)
+            .collect();
 
         // Build evasion script
-        let evasion_script = build_evasion_script(&config);
+        let evasion_script = build_evasion_script_for(&config, &fingerprint);
 
         Ok(Self {
             connection,
             config,
             user_data_dir,
+            fingerprint,
             evasion_script,
+            claimed_targets: std::sync::Mutex::new(claimed_targets),
+            discovered_targets: std::sync::Mutex::new(VecDeque::new()),
         })
     }
 
+    /// Attach to a target, enable page events and inject the evasion scripts
+    async fn attach_page(&self, target_id: &str) -> Result<Page> {
+        self.attach_page_with(target_id, &self.config, &self.evasion_script)
+            .await
+    }
+
+    /// [`Browser::attach_page`] for a page-specific config and evasion script
+    async fn attach_page_with(
+        &self,
+        target_id: &str,
+        config: &Arc<StealthConfig>,
+        evasion_script: &str,
+    ) -> Result<Page> {
+        self.claimed_targets
+            .lock()
+            .unwrap()
+            .insert(target_id.to_string());
+
+        let session = self.connection.attach_to_target(target_id).await?;
+
+        // Enable page events
+        session.page_enable().await?;
+
+        // Inject evasion scripts BEFORE navigation. Awaiting the response
+        // means the script is registered before any later Page.navigate, so
+        // it runs in the first document that navigation commits.
+        if let Some(timezone) = &config.timezone_override {
+            session.set_timezone_override(timezone).await?;
+        }
+
+        let evasion_script_id = session
+            .add_script_to_evaluate_on_new_document(evasion_script)
+            .await?;
+
+        Ok(Page::new(session, Arc::clone(config), evasion_script_id))
+    }
+
     /// Create a new page and navigate to URL
+    ///
+    /// The target starts at `about:blank` and is only navigated once the
+    /// evasions are registered, so they cover the very first document.
     pub async fn new_page(&self, url: &str) -> Result<Page> {
         // Create a new target (window size is set via --window-size Chrome arg)
         let target_id = self
             .connection
             .create_target("about:blank", None, None)
             .await?;
+        let page = self.attach_page(&target_id).await?;
+        Self::navigate_new_page(page, url).await
+    }
 
-        // Attach to the target
-        let session = self.connection.attach_to_target(&target_id).await?;
+    /// Create a new page with its own identity and navigate to URL, for
+    /// several identities in one browser.
+    ///
+    /// The page gets an evasion script built from `config` (its fingerprint,
+    /// languages and which evasions run), plus its user agent (with matching
+    /// Client Hints), `Accept-Language`, viewport and device scale factor
+    /// when they differ from the browser's. Launch-only settings
+    /// (`headless`, `proxy`, `chrome_path`, args, ...) are ignored; start
+    /// from [`Browser::config`] to change only a few fields.
+    pub async fn new_page_with_config(&self, url: &str, config: StealthConfig) -> Result<Page> {
+        let config = Arc::new(config);
+        // Without its own fingerprint the page keeps the browser's identity
+        let identity = config.fingerprint.as_ref().unwrap_or(&self.fingerprint);
+        let evasion_script = build_evasion_script_for(&config, identity);
 
-        // Enable page events
-        session.page_enable().await?;
+        let target_id = self
+            .connection
+            .create_target("about:blank", None, None)
+            .await?;
+        let page = self
+            .attach_page_with(&target_id, &config, &evasion_script)
+            .await?;
 
-        // Inject evasion scripts BEFORE navigation
-        session
-            .add_script_to_evaluate_on_new_document(&self.evasion_script)
+        // One override carries the UA, its Client Hints and Accept-Language,
+        // so it's needed when either the UA or the languages change
+        let user_agent = effective_user_agent(&config, identity);
+        if user_agent != effective_user_agent(&self.config, &self.fingerprint)
+            || config.effective_languages() != self.config.effective_languages()
+        {
+            let languages = config.effective_languages();
+            let accept_language = (!languages.is_empty()).then(|| accept_language(languages));
+            page.session()
+                .set_user_agent_override(user_agent, accept_language.as_deref())
+                .await?;
+        }
+        // A fixed fingerprint's scale is forced at launch; the page's identity
+        // may need another one even at the same viewport size
+        let scale = config
+            .fingerprint
+            .as_ref()
+            .or(self.config.fingerprint.as_ref())
+            .map(Fingerprint::device_scale_factor);
+        let browser_scale = self
+            .config
+            .fingerprint
+            .as_ref()
+            .map(Fingerprint::device_scale_factor);
+        if (config.viewport_width, config.viewport_height)
+            != (self.config.viewport_width, self.config.viewport_height)
+            || scale != browser_scale
+        {
+            page.set_viewport(
+                config.viewport_width,
+                config.viewport_height,
+                scale.unwrap_or(1.0),
+            )
             .await?;
+        }
+
+        Self::navigate_new_page(page, url).await
+    }
 
-        // Navigate to URL
-        let nav_result = session.navigate(url).await?;
+    /// Navigate a freshly attached page and wait for it to become interactive
+    async fn navigate_new_page(page: Page, url: &str) -> Result<Page> {
+        let nav_result = page.session().navigate(url).await?;
         if let Some(error) = nav_result.error_text {
             return Err(Error::Navigation(error));
         }
 
-        // Brief settle time for the initial page load to start.
-        // For reliable waiting, callers should use page.wait_for_navigation() or
-        // page.wait_for(selector, timeout) after this returns.
-        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        // Wait (bounded) for the new document to become interactive. For
+        // specific content, callers should still use page.wait_for(selector, timeout).
+        page.wait_for_document_ready(nav_result.loader_id.as_deref())
+            .await;
 
-        Ok(Page::new(session, Arc::clone(&self.config)))
+        Ok(page)
     }
 
     /// Create a new page without navigation (at about:blank)
@@ -194,14 +563,57 @@ impl Browser {
             .connection
             .create_target("about:blank", None, None)
             .await?;
+        self.attach_page(&target_id).await
+    }
 
-        let session = self.connection.attach_to_target(&target_id).await?;
-        session.page_enable().await?;
-        session
-            .add_script_to_evaluate_on_new_document(&self.evasion_script)
-            .await?;
+    /// Wait for a page opened by the site (`target=_blank` link, `window.open`)
+    /// and attach to it.
+    ///
+    /// Pages opened since the last call count too, so it's fine to click first
+    /// and wait afterwards. Use [`TabInfo::opener_id`] from [`Browser::tabs`] to
+    /// find which page opened it. Evasions apply from the popup's next document
+    /// on; its first document may already be loading when we attach.
+    pub async fn wait_for_new_page(&self, timeout_ms: u64) -> Result<Page> {
+        let start = std::time::Instant::now();
+        let timeout = std::time::Duration::from_millis(timeout_ms);
+
+        loop {
+            if let Some(target_id) = self.next_unclaimed_page() {
+                let page = self.attach_page(&target_id).await?;
+                page.wait_for_document_ready(None).await;
+                return Ok(page);
+            }
+
+            if start.elapsed() > timeout {
+                return Err(Error::Timeout(format!(
+                    "No new page opened within {}ms",
+                    timeout_ms
+                )));
+            }
+
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+    }
 
-        Ok(Page::new(session, Arc::clone(&self.config)))
+    /// Pop the oldest discovered page target that nobody has wrapped yet
+    fn next_unclaimed_page(&self) -> Option<String> {
+        let mut discovered = self.discovered_targets.lock().unwrap();
+        discovered.extend(
+            self.connection
+                .transport()
+                .take_created_targets()
+                .into_iter()
+                .filter(|t| t.r#type == "page")
+                .map(|t| t.target_id),
+        );
+
+        let claimed = self.claimed_targets.lock().unwrap();
+        while let Some(target_id) = discovered.pop_front() {
+            if !claimed.contains(&target_id) {
+                return Some(target_id);
+            }
+        }
+        None
     }
 
     /// Get the browser version
@@ -220,6 +632,7 @@ impl Browser {
                 id: t.target_id,
                 title: t.title,
                 url: t.url,
+                opener_id: t.opener_id,
             })
             .collect())
     }
@@ -235,21 +648,297 @@ impl Browser {
         Ok(())
     }
 
-    /// Close the browser
+    /// Run common bot-detection checks (`navigator.webdriver`, automation
+    /// markers, plugins, `chrome.runtime`, headless UA tokens, WebGL renderer,
+    /// languages) on a local `data:` page in a new tab.
+    ///
+    /// No network access needed; the tab is closed afterwards.
+    pub async fn run_detection_suite(&self) -> Result<DetectionReport> {
+        let page = self.new_page(DETECTION_PAGE).await?;
+        let checks = page.evaluate::<Vec<DetectionCheck>>(DETECTION_JS).await;
+        self.close_tab(page.target_id()).await?;
+        Ok(DetectionReport { checks: checks? })
+    }
+
+    /// Reset cookies, HTTP cache and site storage without relaunching
+    ///
+    /// Cookies and the cache are cleared browser-wide. Storage (localStorage,
+    /// IndexedDB, service workers, ...) is cleared for the origins of the
+    /// currently open tabs; other origins keep theirs.
+    pub async fn clear_browsing_data(&self) -> Result<()> {
+        self.connection.clear_cookies().await?;
+
+        let tabs = self.tabs().await?;
+        let mut origins: Vec<String> = tabs.iter().filter_map(|t| url_origin(&t.url)).collect();
+        origins.sort();
+        origins.dedup();
+        for origin in &origins {
+            self.connection.clear_data_for_origin(origin, "all").await?;
+        }
+
+        // The HTTP cache is profile-wide, but only reachable from a page session
+        if let Some(tab) = tabs.first() {
+            let session = self.connection.attach_to_target(&tab.id).await?;
+            let cleared = session.clear_browser_cache().await;
+            self.connection
+                .detach_from_target(session.session_id())
+                .await?;
+            cleared?;
+        }
+        Ok(())
+    }
+
+    /// Whether Chrome is still running and connected.
+    ///
+    /// Once this is false (crash, kill, dropped socket) every command on this
+    /// browser and its pages fails with `Error::SessionClosed`.
+    pub fn is_alive(&self) -> bool {
+        self.connection.is_alive()
+    }
+
+    /// The config this browser was launched with
+    pub fn config(&self) -> &StealthConfig {
+        &self.config
+    }
+
+    /// The identity pages present unless given their own: `config.fingerprint`,
+    /// or the random one picked at launch
+    pub fn fingerprint(&self) -> &Fingerprint {
+        &self.fingerprint
+    }
+
+    /// Close the browser.
+    ///
+    /// Asks Chrome to shut down and waits up to `close_timeout_ms` for it to
+    /// exit. If it has to be killed, the profile is still cleaned up but
+    /// `Error::Timeout` is returned.
     pub async fn close(self) -> Result<()> {
-        self.connection.close().await?;
+        let result = self
+            .connection
+            .close(std::time::Duration::from_millis(
+                self.config.close_timeout_ms,
+            ))
+            .await;
 
         // Clean up user data directory
-        let _ = std::fs::remove_dir_all(&self.user_data_dir);
+        self.user_data_dir.cleanup();
 
-        Ok(())
+        result
     }
 }
 
-impl Drop for Browser {
-    fn drop(&mut self) {
-        // Best-effort cleanup of user data directory if close() wasn't called.
-        // The Transport's Drop impl handles killing the Chrome process.
-        let _ = std::fs::remove_dir_all(&self.user_data_dir);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extra_args_appended() {
+        let config = StealthConfig {
+            extra_args: vec!["--lang=de-DE".into()],
+            ..Default::default()
+        };
+        let args = stealth_args(&config, &Fingerprint::random());
+        assert!(args.contains(&"--lang=de-DE".to_string()));
+    }
+
+    #[test]
+    fn test_extra_args_override_generated() {
+        let config = StealthConfig {
+            user_agent: Some("generated".into()),
+            extra_args: vec!["--user-agent=custom".into(), "--window-size=800,600".into()],
+            ..Default::default()
+        };
+        let args = stealth_args(&config, &Fingerprint::random());
+        let ua: Vec<_> = args
+            .iter()
+            .filter(|a| a.starts_with("--user-agent="))
+            .collect();
+        assert_eq!(ua, ["--user-agent=custom"]);
+        let size: Vec<_> = args
+            .iter()
+            .filter(|a| a.starts_with("--window-size="))
+            .collect();
+        assert_eq!(size, ["--window-size=800,600"]);
+    }
+
+    #[test]
+    fn test_extra_feature_lists_merged() {
+        let config = StealthConfig {
+            extra_args: vec![
+                "--disable-features=Translate,AutomationControlled".into(),
+                "--enable-features=WebGPU".into(),
+            ],
+            ..Default::default()
+        };
+        let args = stealth_args(&config, &Fingerprint::random());
+        let disabled: Vec<_> = args
+            .iter()
+            .filter(|a| a.starts_with("--disable-features="))
+            .collect();
+        assert_eq!(disabled.len(), 1);
+        assert_eq!(
+            disabled[0],
+            "--disable-features=IsolateOrigins,site-per-process,AutomationControlled,EnableAutomation,Translate"
+        );
+        assert!(args.contains(
+            &"--enable-features=NetworkService,NetworkServiceInProcess,WebGPU".to_string()
+        ));
+    }
+
+    #[test]
+    fn test_fingerprint_args_merged() {
+        let fingerprint = Fingerprint::random();
+        let config = StealthConfig {
+            fingerprint: Some(fingerprint.clone()),
+            languages: vec!["fr-FR".into()],
+            ..Default::default()
+        };
+        let args = stealth_args(&config, &fingerprint);
+        let ua: Vec<_> = args
+            .iter()
+            .filter(|a| a.starts_with("--user-agent="))
+            .collect();
+        assert_eq!(ua, [&format!("--user-agent={}", fingerprint.user_agent)]);
+        assert_eq!(
+            args.iter()
+                .filter(|a| a.starts_with("--window-size="))
+                .count(),
+            1
+        );
+        assert!(args
+            .iter()
+            .any(|a| a.starts_with("--force-device-scale-factor=")));
+        let lang: Vec<_> = args.iter().filter(|a| a.starts_with("--lang=")).collect();
+        assert_eq!(lang, ["--lang=fr-FR"]);
+    }
+
+    #[test]
+    fn test_fingerprint_languages_when_default() {
+        let mut fingerprint = Fingerprint::random();
+        fingerprint.languages = vec!["ja-JP".into(), "ja".into()];
+        let config = StealthConfig {
+            fingerprint: Some(fingerprint.clone()),
+            ..Default::default()
+        };
+        let args = stealth_args(&config, &fingerprint);
+        let lang: Vec<_> = args.iter().filter(|a| a.starts_with("--lang=")).collect();
+        assert_eq!(lang, ["--lang=ja-JP"]);
+        assert!(args.contains(&"--accept-lang=ja-JP,ja".to_string()));
+    }
+
+    #[test]
+    fn test_explicit_config_wins_over_fingerprint() {
+        let config = StealthConfig {
+            fingerprint: Some(Fingerprint::random()),
+            user_agent: Some("explicit".into()),
+            viewport_width: 1280,
+            viewport_height: 720,
+            ..Default::default()
+        };
+        let args = stealth_args(&config, &Fingerprint::random());
+        let ua: Vec<_> = args
+            .iter()
+            .filter(|a| a.starts_with("--user-agent="))
+            .collect();
+        assert_eq!(ua, ["--user-agent=explicit"]);
+        let size: Vec<_> = args
+            .iter()
+            .filter(|a| a.starts_with("--window-size="))
+            .collect();
+        assert_eq!(size, ["--window-size=1280,720"]);
+    }
+
+    #[test]
+    fn test_random_identity_user_agent_used() {
+        let identity = Fingerprint::random();
+        let args = stealth_args(&StealthConfig::default(), &identity);
+        assert!(args.contains(&format!("--user-agent={}", identity.user_agent)));
+        // A random identity only supplies the UA, not its screen or scale
+        assert!(!args
+            .iter()
+            .any(|a| a.starts_with("--force-device-scale-factor=")));
+    }
+
+    #[test]
+    fn test_disable_default_args() {
+        let config = StealthConfig {
+            disable_default_args: true,
+            ..Default::default()
+        };
+        let args = stealth_args(&config, &Fingerprint::random());
+        assert!(!args.iter().any(|a| a == "--no-sandbox"));
+        assert!(args.iter().any(|a| a.starts_with("--window-size=")));
+    }
+
+    #[test]
+    fn test_parse_chrome_major() {
+        assert_eq!(parse_chrome_major("Chrome/120.0.6099.109"), Some(120));
+        assert_eq!(
+            parse_chrome_major("HeadlessChrome/131.0.6778.85"),
+            Some(131)
+        );
+        assert_eq!(parse_chrome_major("Chromium/99.0.4844.51"), Some(99));
+        assert_eq!(parse_chrome_major("Chrome/120"), Some(120));
+        assert_eq!(
+            parse_chrome_major("Microsoft Edge 121.0.2277.83"),
+            Some(121)
+        );
+        assert_eq!(parse_chrome_major("Chrome/unknown"), None);
+        assert_eq!(parse_chrome_major(""), None);
+    }
+
+    #[test]
+    fn test_min_chrome_version_enforced() {
+        let config = StealthConfig {
+            min_chrome_version: Some(120),
+            ..Default::default()
+        };
+        assert!(check_chrome_version("Chrome/121.0.0.0", &config).is_ok());
+        assert!(matches!(
+            check_chrome_version("HeadlessChrome/119.0.0.0", &config),
+            Err(Error::Launch(_))
+        ));
+        // Unparseable versions are let through
+        assert!(check_chrome_version("Chrome/dev", &config).is_ok());
+
+        // Without a minimum, old and future versions only warn
+        let config = StealthConfig::default();
+        assert!(check_chrome_version("Chrome/90.0.0.0", &config).is_ok());
+        assert!(check_chrome_version("Chrome/250.0.0.0", &config).is_ok());
+    }
+
+    #[test]
+    fn test_persistent_profile_lock() {
+        let dir = std::env::temp_dir().join(format!("eoka-profile-lock-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let first = UserDataDir::persistent(dir.clone()).expect("first claim");
+        assert!(matches!(
+            UserDataDir::persistent(dir.clone()),
+            Err(Error::Launch(_))
+        ));
+
+        // Releasing keeps the directory but frees the lock
+        drop(first);
+        assert!(dir.exists());
+        let second = UserDataDir::persistent(dir.clone()).expect("second claim");
+        drop(second);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_url_origin() {
+        assert_eq!(
+            url_origin("https://example.com/a/b?q=1").as_deref(),
+            Some("https://example.com")
+        );
+        assert_eq!(
+            url_origin("http://127.0.0.1:8080#top").as_deref(),
+            Some("http://127.0.0.1:8080")
+        );
+        assert_eq!(url_origin("about:blank"), None);
+        assert_eq!(url_origin("data:text/html,<p>x</p>"), None);
+        assert_eq!(url_origin("file:///tmp/a.html"), None);
     }
 }
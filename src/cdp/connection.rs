@@ -3,6 +3,7 @@
 //! Manages browser and page sessions over the CDP transport.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use super::transport::Transport;
 use super::types::*;
@@ -74,6 +75,41 @@ impl Connection {
         })
     }
 
+    /// Detach a session created by [`Connection::attach_to_target`]
+    pub async fn detach_from_target(&self, session_id: &str) -> Result<()> {
+        self.transport
+            .send::<_, serde_json::Value>(
+                "Target.detachFromTarget",
+                &TargetDetachFromTarget {
+                    session_id: session_id.to_string(),
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Clear every cookie in the browser (all origins)
+    pub async fn clear_cookies(&self) -> Result<()> {
+        self.transport
+            .send::<_, serde_json::Value>("Storage.clearCookies", &StorageClearCookies {})
+            .await?;
+        Ok(())
+    }
+
+    /// Clear site data (`storage_types`, comma-separated, or `all`) for one origin
+    pub async fn clear_data_for_origin(&self, origin: &str, storage_types: &str) -> Result<()> {
+        self.transport
+            .send::<_, serde_json::Value>(
+                "Storage.clearDataForOrigin",
+                &StorageClearDataForOrigin {
+                    origin: origin.to_string(),
+                    storage_types: storage_types.to_string(),
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
     /// Close a target
     pub async fn close_target(&self, target_id: &str) -> Result<bool> {
         let result: TargetCloseTargetResult = self
@@ -110,17 +146,39 @@ impl Connection {
         Ok(())
     }
 
-    /// Close the browser
-    pub async fn close(&self) -> Result<()> {
-        let _ = self
+    /// Enable or disable `Target.targetCreated`/`targetDestroyed` events
+    pub async fn set_discover_targets(&self, discover: bool) -> Result<()> {
+        self.transport
+            .send::<_, serde_json::Value>(
+                "Target.setDiscoverTargets",
+                &TargetSetDiscoverTargets { discover },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Whether the browser is still reachable
+    pub fn is_alive(&self) -> bool {
+        !self.transport.is_closed()
+    }
+
+    /// Close the browser, waiting up to `timeout` for the process to exit
+    pub async fn close(&self, timeout: Duration) -> Result<()> {
+        // Chrome may drop the socket before replying; the exit wait below
+        // is what tells us whether shutdown worked
+        if let Err(e) = self
             .transport
             .send::<_, serde_json::Value>("Browser.close", &BrowserClose {})
-            .await;
-        self.transport.close().await
+            .await
+        {
+            tracing::debug!("Browser.close failed: {}", e);
+        }
+        self.transport.close(timeout).await
     }
 }
 
 /// A CDP session attached to a specific target
+#[derive(Clone)]
 pub struct Session {
     transport: Arc<Transport>,
     session_id: String,
@@ -138,6 +196,27 @@ impl Session {
         &self.target_id
     }
 
+    /// Subscribe to an event method on this session (e.g. `"Page.frameNavigated"`),
+    /// receiving each event's params
+    pub fn on(&self, method: &str) -> tokio::sync::broadcast::Receiver<serde_json::Value> {
+        self.transport.subscribe(Some(&self.session_id), method)
+    }
+
+    /// Like [`Session::on`], but lossless: events queue until read instead
+    /// of lagging
+    pub fn on_unbounded(
+        &self,
+        method: &str,
+    ) -> tokio::sync::mpsc::UnboundedReceiver<serde_json::Value> {
+        self.transport
+            .subscribe_unbounded(Some(&self.session_id), method)
+    }
+
+    /// Current document epoch for this session (see [`Transport::document_epoch`])
+    pub fn document_epoch(&self) -> u64 {
+        self.transport.document_epoch(&self.session_id)
+    }
+
     /// Send a command to this session
     pub async fn send<C, R>(&self, method: &str, params: &C) -> Result<R>
     where
@@ -180,37 +259,39 @@ impl Session {
         Ok(())
     }
 
-    /// Go back in history
-    pub async fn go_back(&self) -> Result<()> {
+    /// Go back in history. Returns `false` if already at the first entry.
+    pub async fn go_back(&self) -> Result<bool> {
         let history: PageGetNavigationHistoryResult = self
             .send("Page.getNavigationHistory", &PageGetNavigationHistory {})
             .await?;
-        if history.current_index > 0 {
-            let entry_id = history.entries[history.current_index as usize - 1].id;
-            self.send::<_, serde_json::Value>(
-                "Page.navigateToHistoryEntry",
-                &PageNavigateToHistoryEntry { entry_id },
-            )
-            .await?;
+        if history.current_index <= 0 {
+            return Ok(false);
         }
-        Ok(())
+        let entry_id = history.entries[history.current_index as usize - 1].id;
+        self.send::<_, serde_json::Value>(
+            "Page.navigateToHistoryEntry",
+            &PageNavigateToHistoryEntry { entry_id },
+        )
+        .await?;
+        Ok(true)
     }
 
-    /// Go forward in history
-    pub async fn go_forward(&self) -> Result<()> {
+    /// Go forward in history. Returns `false` if already at the last entry.
+    pub async fn go_forward(&self) -> Result<bool> {
         let history: PageGetNavigationHistoryResult = self
             .send("Page.getNavigationHistory", &PageGetNavigationHistory {})
             .await?;
         let next = history.current_index as usize + 1;
-        if next < history.entries.len() {
-            let entry_id = history.entries[next].id;
-            self.send::<_, serde_json::Value>(
-                "Page.navigateToHistoryEntry",
-                &PageNavigateToHistoryEntry { entry_id },
-            )
-            .await?;
+        if next >= history.entries.len() {
+            return Ok(false);
         }
-        Ok(())
+        let entry_id = history.entries[next].id;
+        self.send::<_, serde_json::Value>(
+            "Page.navigateToHistoryEntry",
+            &PageNavigateToHistoryEntry { entry_id },
+        )
+        .await?;
+        Ok(true)
     }
 
     /// Add a script to evaluate on every new document
@@ -228,6 +309,18 @@ impl Session {
         Ok(result.identifier)
     }
 
+    /// Remove a script added with `add_script_to_evaluate_on_new_document`
+    pub async fn remove_script_to_evaluate_on_new_document(&self, identifier: &str) -> Result<()> {
+        self.send::<_, serde_json::Value>(
+            "Page.removeScriptToEvaluateOnNewDocument",
+            &PageRemoveScriptToEvaluateOnNewDocument {
+                identifier: identifier.to_string(),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Capture a screenshot
     pub async fn capture_screenshot(
         &self,
@@ -240,15 +333,33 @@ impl Session {
                 &PageCaptureScreenshot {
                     format: format.map(String::from),
                     quality,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        decode_base64(&result.data)
+    }
+
+    /// Capture a PNG of one region of the document, even outside the viewport
+    pub async fn capture_screenshot_clip(&self, clip: ScreenshotClip) -> Result<Vec<u8>> {
+        let result: PageCaptureScreenshotResult = self
+            .send(
+                "Page.captureScreenshot",
+                &PageCaptureScreenshot {
+                    format: Some("png".into()),
+                    clip: Some(clip),
+                    capture_beyond_viewport: Some(true),
+                    ..Default::default()
                 },
             )
             .await?;
+        decode_base64(&result.data)
+    }
 
-        use base64::Engine;
-        let bytes = base64::engine::general_purpose::STANDARD
-            .decode(&result.data)
-            .map_err(|e| crate::error::Error::Decode(e.to_string()))?;
-        Ok(bytes)
+    /// Print the page to PDF
+    pub async fn print_to_pdf(&self, params: PagePrintToPdf) -> Result<Vec<u8>> {
+        let result: PagePrintToPdfResult = self.send("Page.printToPDF", &params).await?;
+        decode_base64(&result.data)
     }
 
     /// Get the frame tree
@@ -434,10 +545,45 @@ impl Session {
         object_id: &str,
         function_declaration: &str,
     ) -> Result<RuntimeEvaluateResult> {
-        self.call_function_on_impl(object_id, function_declaration, true)
+        self.call_function_on_impl(object_id, function_declaration, None, true)
             .await
     }
 
+    /// Call a function on a remote object with JSON arguments, returning by value.
+    /// Arguments arrive as the function's parameters, so they never need
+    /// escaping into the source.
+    pub async fn call_function_on_with_args(
+        &self,
+        object_id: &str,
+        function_declaration: &str,
+        args: &[serde_json::Value],
+    ) -> Result<RuntimeEvaluateResult> {
+        self.call_function_on_impl(
+            object_id,
+            function_declaration,
+            Some(call_arguments(args)),
+            true,
+        )
+        .await
+    }
+
+    /// Like [`Session::call_function_on_with_args`], but returns a remote
+    /// object reference (e.g. to a DOM node) instead of a value
+    pub async fn call_function_on_for_remote_object(
+        &self,
+        object_id: &str,
+        function_declaration: &str,
+        args: &[serde_json::Value],
+    ) -> Result<RuntimeEvaluateResult> {
+        self.call_function_on_impl(
+            object_id,
+            function_declaration,
+            Some(call_arguments(args)),
+            false,
+        )
+        .await
+    }
+
     /// Focus an element
     pub async fn focus(&self, node_id: i32) -> Result<()> {
         self.send::<_, serde_json::Value>(
@@ -483,6 +629,32 @@ impl Session {
         Ok(result.success)
     }
 
+    /// Set a cookie with every `Network.setCookie` field available
+    pub async fn set_cookie_full(&self, cookie: NetworkSetCookie) -> Result<bool> {
+        let result: NetworkSetCookieResult = self.send("Network.setCookie", &cookie).await?;
+        Ok(result.success)
+    }
+
+    /// Clear every cookie in the browser (not just this target's)
+    pub async fn clear_browser_cookies(&self) -> Result<()> {
+        self.send::<_, serde_json::Value>(
+            "Network.clearBrowserCookies",
+            &NetworkClearBrowserCookies {},
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Clear the browser's HTTP cache
+    pub async fn clear_browser_cache(&self) -> Result<()> {
+        self.send::<_, serde_json::Value>(
+            "Network.clearBrowserCache",
+            &NetworkClearBrowserCache {},
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Delete cookies
     pub async fn delete_cookies(
         &self,
@@ -523,6 +695,68 @@ impl Session {
         Ok(())
     }
 
+    /// Add headers to every request from this target (needs `Network.enable`)
+    pub async fn set_extra_http_headers(
+        &self,
+        headers: std::collections::HashMap<String, String>,
+    ) -> Result<()> {
+        self.send::<_, serde_json::Value>(
+            "Network.setExtraHTTPHeaders",
+            &NetworkSetExtraHTTPHeaders { headers },
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Pause every request before it is sent (`Fetch.requestPaused`)
+    pub async fn fetch_enable(&self) -> Result<()> {
+        self.send::<_, serde_json::Value>(
+            "Fetch.enable",
+            &FetchEnable {
+                patterns: Some(vec![FetchRequestPattern {
+                    url_pattern: Some("*".into()),
+                    request_stage: Some("Request".into()),
+                }]),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Stop pausing requests
+    pub async fn fetch_disable(&self) -> Result<()> {
+        self.send::<_, serde_json::Value>("Fetch.disable", &FetchDisable {})
+            .await?;
+        Ok(())
+    }
+
+    /// Resume a paused request, optionally modified
+    pub async fn fetch_continue_request(&self, params: FetchContinueRequest) -> Result<()> {
+        self.send::<_, serde_json::Value>("Fetch.continueRequest", &params)
+            .await?;
+        Ok(())
+    }
+
+    /// Fail a paused request with a network error reason (e.g. `BlockedByClient`)
+    pub async fn fetch_fail_request(&self, request_id: &str, error_reason: &str) -> Result<()> {
+        self.send::<_, serde_json::Value>(
+            "Fetch.failRequest",
+            &FetchFailRequest {
+                request_id: request_id.to_string(),
+                error_reason: error_reason.to_string(),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Answer a paused request with a synthetic response
+    pub async fn fetch_fulfill_request(&self, params: FetchFulfillRequest) -> Result<()> {
+        self.send::<_, serde_json::Value>("Fetch.fulfillRequest", &params)
+            .await?;
+        Ok(())
+    }
+
     /// Get response body for a request
     pub async fn get_response_body(&self, request_id: &str) -> Result<(String, bool)> {
         let result: NetworkGetResponseBodyResult = self
@@ -536,12 +770,26 @@ impl Session {
         Ok((result.body, result.base64_encoded))
     }
 
+    /// Stream the body of an in-flight request through `Network.dataReceived`
+    /// events; returns the base64 data already buffered
+    pub async fn stream_resource_content(&self, request_id: &str) -> Result<String> {
+        let result: NetworkStreamResourceContentResult = self
+            .send(
+                "Network.streamResourceContent",
+                &NetworkStreamResourceContent {
+                    request_id: request_id.to_string(),
+                },
+            )
+            .await?;
+        Ok(result.buffered_data)
+    }
+
     /// Evaluate JavaScript and return a remote object reference (not by value).
     pub async fn evaluate_for_remote_object(
         &self,
         expression: &str,
     ) -> Result<RuntimeEvaluateResult> {
-        self.evaluate_impl(expression, false, Some("eoka"), true)
+        self.evaluate_impl(expression, false, Some("eoka"), true, None)
             .await
     }
 
@@ -575,10 +823,23 @@ impl Session {
         Ok(result.result)
     }
 
+    /// Release a remote object so the page can garbage-collect it
+    pub async fn release_object(&self, object_id: &str) -> Result<()> {
+        self.send::<_, serde_json::Value>(
+            "Runtime.releaseObject",
+            &crate::cdp::types::RuntimeReleaseObject {
+                object_id: object_id.to_string(),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
     async fn call_function_on_impl(
         &self,
         object_id: &str,
         function_declaration: &str,
+        arguments: Option<Vec<CallArgument>>,
         return_by_value: bool,
     ) -> Result<RuntimeEvaluateResult> {
         let result: RuntimeCallFunctionOnResult = self
@@ -587,7 +848,7 @@ impl Session {
                 &RuntimeCallFunctionOn {
                     function_declaration: function_declaration.to_string(),
                     object_id: Some(object_id.to_string()),
-                    arguments: None,
+                    arguments,
                     silent: None,
                     return_by_value: Some(return_by_value),
                     await_promise: Some(true),
@@ -602,13 +863,14 @@ impl Session {
 
     /// Evaluate JavaScript expression and return the result by value
     pub async fn evaluate(&self, expression: &str) -> Result<RuntimeEvaluateResult> {
-        self.evaluate_impl(expression, true, None, true).await
+        self.evaluate_impl(expression, true, None, true, None).await
     }
 
     /// Evaluate JavaScript synchronously (don't await promises).
     /// Use this when the page may have unresolved promises that would block.
     pub async fn evaluate_sync(&self, expression: &str) -> Result<RuntimeEvaluateResult> {
-        self.evaluate_impl(expression, true, None, false).await
+        self.evaluate_impl(expression, true, None, false, None)
+            .await
     }
 
     async fn evaluate_impl(
@@ -617,6 +879,7 @@ impl Session {
         return_by_value: bool,
         object_group: Option<&str>,
         await_promise: bool,
+        context_id: Option<i64>,
     ) -> Result<RuntimeEvaluateResult> {
         self.send(
             "Runtime.evaluate",
@@ -625,11 +888,182 @@ impl Session {
                 object_group: object_group.map(String::from),
                 return_by_value: Some(return_by_value),
                 await_promise: Some(await_promise),
+                context_id,
             },
         )
         .await
     }
 
+    /// Evaluate JavaScript in a specific execution context (e.g. an isolated
+    /// world created with `create_isolated_world`). `return_by_value = false`
+    /// returns a remote object reference instead of the value.
+    pub async fn evaluate_in_context(
+        &self,
+        expression: &str,
+        context_id: i64,
+        return_by_value: bool,
+    ) -> Result<RuntimeEvaluateResult> {
+        let object_group = if return_by_value { None } else { Some("eoka") };
+        self.evaluate_impl(
+            expression,
+            return_by_value,
+            object_group,
+            true,
+            Some(context_id),
+        )
+        .await
+    }
+
+    /// Create an isolated JS world in a frame, returning its execution context id.
+    /// Shares the frame's DOM but not its page-defined globals.
+    pub async fn create_isolated_world(&self, frame_id: &str) -> Result<i64> {
+        let result: PageCreateIsolatedWorldResult = self
+            .send(
+                "Page.createIsolatedWorld",
+                &PageCreateIsolatedWorld {
+                    frame_id: frame_id.to_string(),
+                    world_name: None,
+                },
+            )
+            .await?;
+        Ok(result.execution_context_id)
+    }
+
+    /// Describe a DOM node (e.g. to get an `<iframe>`'s frame id)
+    pub async fn describe_node(&self, node_id: i32) -> Result<DOMNode> {
+        let result: DOMDescribeNodeResult = self
+            .send("DOM.describeNode", &DOMDescribeNode { node_id })
+            .await?;
+        Ok(result.node)
+    }
+
+    /// Grant browser permissions (e.g. `clipboardReadWrite`) for an origin.
+    /// Browser-level command, sent without a session.
+    pub async fn grant_permissions(&self, origin: &str, permissions: &[&str]) -> Result<()> {
+        self.transport
+            .send::<_, serde_json::Value>(
+                "Browser.grantPermissions",
+                &BrowserGrantPermissions {
+                    permissions: permissions.iter().map(|p| p.to_string()).collect(),
+                    origin: Some(origin.to_string()),
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Bring this page to the front (gives its document focus)
+    pub async fn bring_to_front(&self) -> Result<()> {
+        self.send::<_, serde_json::Value>("Page.bringToFront", &PageBringToFront {})
+            .await?;
+        Ok(())
+    }
+
+    /// List all targets (browser-level; includes out-of-process iframes)
+    pub async fn get_targets(&self) -> Result<Vec<TargetInfo>> {
+        let result: TargetGetTargetsResult = self
+            .transport
+            .send("Target.getTargets", &TargetGetTargets {})
+            .await?;
+        Ok(result.target_infos)
+    }
+
+    /// Close this session's own target (browser-level)
+    pub async fn close_target(&self) -> Result<()> {
+        self.transport
+            .send::<_, TargetCloseTargetResult>(
+                "Target.closeTarget",
+                &TargetCloseTarget {
+                    target_id: self.target_id.clone(),
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Attach to another target (e.g. an out-of-process iframe) on the same connection
+    pub async fn attach_to_target(&self, target_id: &str) -> Result<Session> {
+        let result: TargetAttachToTargetResult = self
+            .transport
+            .send(
+                "Target.attachToTarget",
+                &TargetAttachToTarget {
+                    target_id: target_id.to_string(),
+                    flatten: Some(true),
+                },
+            )
+            .await?;
+
+        Ok(Session {
+            transport: Arc::clone(&self.transport),
+            session_id: result.session_id,
+            target_id: target_id.to_string(),
+        })
+    }
+
+    /// Detach this session from its target (browser-level). The target keeps
+    /// running; only this connection to it goes away.
+    pub async fn detach(&self) -> Result<()> {
+        self.transport
+            .send::<_, serde_json::Value>(
+                "Target.detachFromTarget",
+                &TargetDetachFromTarget {
+                    session_id: self.session_id.clone(),
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Browser context this session's target lives in, `None` for the
+    /// default context (which CDP addresses by omitting the id)
+    pub async fn browser_context_id(&self) -> Result<Option<String>> {
+        let info: TargetGetTargetInfoResult = self
+            .transport
+            .send(
+                "Target.getTargetInfo",
+                &TargetGetTargetInfo {
+                    target_id: self.target_id.clone(),
+                },
+            )
+            .await?;
+        let Some(context_id) = info.target_info.browser_context_id else {
+            return Ok(None);
+        };
+
+        let created: TargetGetBrowserContextsResult = self
+            .transport
+            .send("Target.getBrowserContexts", &TargetGetBrowserContexts {})
+            .await?;
+        Ok(created
+            .browser_context_ids
+            .contains(&context_id)
+            .then_some(context_id))
+    }
+
+    /// Control where downloads go and whether `Browser.download*` events
+    /// are sent to this session. Applies to this session's browser context
+    /// only, not to pages in other contexts.
+    pub async fn set_download_behavior(
+        &self,
+        behavior: &str,
+        download_path: Option<&str>,
+        events_enabled: bool,
+    ) -> Result<()> {
+        let browser_context_id = self.browser_context_id().await?;
+        self.send::<_, serde_json::Value>(
+            "Browser.setDownloadBehavior",
+            &BrowserSetDownloadBehavior {
+                behavior: behavior.to_string(),
+                download_path: download_path.map(String::from),
+                browser_context_id,
+                events_enabled: Some(events_enabled),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Set files for a file input element
     pub async fn set_file_input_files(&self, node_id: i32, files: Vec<String>) -> Result<()> {
         self.send::<_, serde_json::Value>(
@@ -651,4 +1085,112 @@ impl Session {
             .await?;
         Ok(())
     }
+
+    /// Override the viewport size and device scale factor
+    /// NOTE: Emulation overrides may be detectable by advanced anti-bot
+    pub async fn set_device_metrics_override(
+        &self,
+        params: EmulationSetDeviceMetricsOverride,
+    ) -> Result<()> {
+        self.send::<_, serde_json::Value>("Emulation.setDeviceMetricsOverride", &params)
+            .await?;
+        Ok(())
+    }
+
+    /// Remove a viewport override set with `set_device_metrics_override`
+    pub async fn clear_device_metrics_override(&self) -> Result<()> {
+        self.send::<_, serde_json::Value>(
+            "Emulation.clearDeviceMetricsOverride",
+            &EmulationClearDeviceMetricsOverride {},
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Pinch-zoom the page (`1.0` is unzoomed)
+    pub async fn set_page_scale_factor(&self, page_scale_factor: f64) -> Result<()> {
+        self.send::<_, serde_json::Value>(
+            "Emulation.setPageScaleFactor",
+            &EmulationSetPageScaleFactor { page_scale_factor },
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Override the timezone Chrome reports (`""` restores the host's)
+    pub async fn set_timezone_override(&self, timezone_id: &str) -> Result<()> {
+        self.send::<_, serde_json::Value>(
+            "Emulation.setTimezoneOverride",
+            &EmulationSetTimezoneOverride {
+                timezone_id: timezone_id.to_string(),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Override the user agent (and optionally `Accept-Language`) for this target.
+    /// `navigator.platform` and Client Hints are derived from `user_agent`
+    /// so they agree with it.
+    pub async fn set_user_agent_override(
+        &self,
+        user_agent: &str,
+        accept_language: Option<&str>,
+    ) -> Result<()> {
+        self.send::<_, serde_json::Value>(
+            "Emulation.setUserAgentOverride",
+            &EmulationSetUserAgentOverride {
+                user_agent: user_agent.to_string(),
+                accept_language: accept_language.map(str::to_string),
+                platform: crate::stealth::fingerprint::navigator_platform_for(user_agent)
+                    .map(String::from),
+                user_agent_metadata: crate::stealth::fingerprint::user_agent_metadata(user_agent),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Make the page behave as focused even when its window isn't
+    pub async fn set_focus_emulation_enabled(&self, enabled: bool) -> Result<()> {
+        self.send::<_, serde_json::Value>(
+            "Emulation.setFocusEmulationEnabled",
+            &EmulationSetFocusEmulationEnabled { enabled },
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Enable the Performance domain (needed before `get_performance_metrics`)
+    pub async fn performance_enable(&self) -> Result<()> {
+        self.send::<_, serde_json::Value>("Performance.enable", &PerformanceEnable {})
+            .await?;
+        Ok(())
+    }
+
+    /// Current run-time metrics of the target (DOM nodes, JS heap, layouts, ...)
+    pub async fn get_performance_metrics(&self) -> Result<Vec<PerformanceMetric>> {
+        let result: PerformanceGetMetricsResult = self
+            .send("Performance.getMetrics", &PerformanceGetMetrics {})
+            .await?;
+        Ok(result.metrics)
+    }
+}
+
+/// JSON values as `Runtime.callFunctionOn` arguments
+fn call_arguments(args: &[serde_json::Value]) -> Vec<CallArgument> {
+    args.iter()
+        .map(|value| CallArgument {
+            value: Some(value.clone()),
+            object_id: None,
+        })
+        .collect()
+}
+
+/// Decode a base64 payload (screenshots, PDFs)
+fn decode_base64(data: &str) -> Result<Vec<u8>> {
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| crate::error::Error::Decode(e.to_string()))
 }
@@ -3,21 +3,56 @@
 //! Handles communication with Chrome via WebSocket.
 //! Includes built-in filtering to block detectable CDP commands.
 
-use std::collections::HashMap;
-use std::io::{BufRead, BufReader};
-use std::net::TcpStream;
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{BufRead, LineWriter, Write};
+use std::path::Path;
 use std::process::{Child, Stdio};
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::{json, Value};
-use tokio::sync::{mpsc, oneshot, Mutex};
-
-/// Pending requests map - uses std::sync::Mutex because it's accessed from both
-/// a std thread (reader loop) and async contexts, and the lock is held very briefly.
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::TcpStream;
+use tokio::sync::{broadcast, mpsc, oneshot, Mutex};
+use tokio::task::JoinHandle;
+
+/// Pending requests map - uses std::sync::Mutex because the lock is held very
+/// briefly and never across an await point.
 type PendingMap = std::sync::Mutex<HashMap<u64, PendingRequest>>;
+/// Per-session counter of document replacements (main-frame navigations)
+type DocumentEpochs = std::sync::Mutex<HashMap<String, u64>>;
+/// Targets announced by `Target.targetCreated`, oldest first
+type CreatedTargets = std::sync::Mutex<VecDeque<TargetInfo>>;
+
+/// Event subscribers keyed by (session id, method)
+type Subscribers = std::sync::Mutex<HashMap<(Option<String>, String), EventSinks>>;
+
+/// Senders for one subscription key
+#[derive(Default)]
+struct EventSinks {
+    /// Shared bounded channel behind [`Transport::subscribe`]
+    broadcast: Option<broadcast::Sender<Value>>,
+    /// Lossless channels behind [`Transport::subscribe_unbounded`]
+    unbounded: Vec<mpsc::UnboundedSender<Value>>,
+}
+
+/// Per-subscription buffer; slow subscribers see `RecvError::Lagged`
+const SUBSCRIBER_CAPACITY: usize = 64;
+
+/// Cap on remembered created targets (oldest are dropped first)
+const MAX_CREATED_TARGETS: usize = 64;
+
+/// How often `close` checks whether Chrome has exited
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
+/// How often the supervisor checks whether Chrome is still running
+const SUPERVISOR_INTERVAL: Duration = Duration::from_millis(250);
+
+use super::types::{TargetInfo, TargetTargetCreatedEvent};
 use crate::error::{Error, Result};
 
 /// Commands that are blocked (highly detectable by anti-bot)
@@ -52,9 +87,45 @@ fn is_risky(method: &str) -> bool {
     RISKY_COMMANDS.contains(&method)
 }
 
+/// Check if an event means the session's root document node was replaced
+fn replaces_document(method: &str, params: &Value) -> bool {
+    match method {
+        "DOM.documentUpdated" => true,
+        "Page.frameNavigated" => params
+            .get("frame")
+            .is_some_and(|f| f.get("parentId").is_none()),
+        _ => false,
+    }
+}
+
+/// JSON-lines record of raw CDP traffic (see `StealthConfig::cdp_log`)
+struct CdpLog(std::sync::Mutex<LineWriter<File>>);
+
+impl CdpLog {
+    fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path)
+            .map_err(|e| Error::transport_io(format!("Failed to create CDP log {:?}", path), e))?;
+        Ok(Self(std::sync::Mutex::new(LineWriter::new(file))))
+    }
+
+    /// Append one message; `dir` is `"send"` or `"recv"`
+    fn record(&self, dir: &str, message: &Value) {
+        let ts = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0.0, |d| d.as_secs_f64());
+        let line = json!({ "ts": ts, "dir": dir, "message": message });
+        if let Err(e) = writeln!(self.0.lock().unwrap(), "{}", line) {
+            tracing::debug!("CDP log write failed: {}", e);
+        }
+    }
+}
+
 /// A pending request waiting for a response
 type PendingRequest = oneshot::Sender<Result<Value>>;
 
+/// Read buffer for the WebSocket stream
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
 /// WebSocket message types
 mod ws {
     pub const OPCODE_TEXT: u8 = 0x1;
@@ -63,50 +134,61 @@ mod ws {
     pub const OPCODE_PONG: u8 = 0xA;
 }
 
-/// Simple WebSocket frame writer
-fn write_ws_frame(stream: &mut TcpStream, data: &[u8]) -> std::io::Result<()> {
-    use std::io::Write;
-
+/// Encode a single-fragment WebSocket frame.
+///
+/// Clients must mask their frames (RFC 6455); `mask` is `None` only when
+/// acting as the server side in tests.
+fn encode_ws_frame(opcode: u8, data: &[u8], mask: Option<[u8; 4]>) -> Vec<u8> {
     let len = data.len();
     let mut frame = Vec::with_capacity(14 + len);
 
-    // FIN + text opcode
-    frame.push(0x80 | ws::OPCODE_TEXT);
+    // FIN + opcode
+    frame.push(0x80 | opcode);
 
-    // Mask bit set (client must mask), then length
+    // Mask bit, then length
+    let mask_bit = if mask.is_some() { 0x80 } else { 0 };
     if len < 126 {
-        frame.push(0x80 | len as u8);
+        frame.push(mask_bit | len as u8);
     } else if len < 65536 {
-        frame.push(0x80 | 126);
+        frame.push(mask_bit | 126);
         frame.push((len >> 8) as u8);
         frame.push(len as u8);
     } else {
-        frame.push(0x80 | 127);
+        frame.push(mask_bit | 127);
         for i in (0..8).rev() {
             frame.push((len >> (i * 8)) as u8);
         }
     }
 
-    // Random masking key per frame (RFC 6455 compliance)
-    let mask: [u8; 4] = rand::random();
-    frame.extend_from_slice(&mask);
-
-    // Masked payload
-    for (i, byte) in data.iter().enumerate() {
-        frame.push(byte ^ mask[i % 4]);
+    match mask {
+        Some(mask) => {
+            frame.extend_from_slice(&mask);
+            for (i, byte) in data.iter().enumerate() {
+                frame.push(byte ^ mask[i % 4]);
+            }
+        }
+        None => frame.extend_from_slice(data),
     }
 
-    stream.write_all(&frame)?;
-    stream.flush()?;
-    Ok(())
+    frame
 }
 
-/// Read a WebSocket frame, returns (opcode, payload)
-fn read_ws_frame(stream: &mut TcpStream) -> std::io::Result<(u8, Vec<u8>)> {
-    use std::io::Read;
+/// Write a masked WebSocket frame
+async fn write_ws_frame<W: AsyncWrite + Unpin>(
+    stream: &mut W,
+    opcode: u8,
+    data: &[u8],
+) -> std::io::Result<()> {
+    // Random masking key per frame (RFC 6455 compliance)
+    let frame = encode_ws_frame(opcode, data, Some(rand::random()));
+    stream.write_all(&frame).await?;
+    stream.flush().await
+}
 
+/// Read a WebSocket frame, returns (opcode, payload)
+async fn read_ws_frame<R: AsyncRead + Unpin>(stream: &mut R) -> std::io::Result<(u8, Vec<u8>)> {
     let mut header = [0u8; 2];
-    stream.read_exact(&mut header)?;
+    stream.read_exact(&mut header).await?;
 
     let opcode = header[0] & 0x0F;
     let masked = (header[1] & 0x80) != 0;
@@ -114,11 +196,11 @@ fn read_ws_frame(stream: &mut TcpStream) -> std::io::Result<(u8, Vec<u8>)> {
 
     if len == 126 {
         let mut ext = [0u8; 2];
-        stream.read_exact(&mut ext)?;
+        stream.read_exact(&mut ext).await?;
         len = ((ext[0] as usize) << 8) | (ext[1] as usize);
     } else if len == 127 {
         let mut ext = [0u8; 8];
-        stream.read_exact(&mut ext)?;
+        stream.read_exact(&mut ext).await?;
         len = 0;
         for byte in ext.iter() {
             len = (len << 8) | (*byte as usize);
@@ -127,14 +209,14 @@ fn read_ws_frame(stream: &mut TcpStream) -> std::io::Result<(u8, Vec<u8>)> {
 
     let mask = if masked {
         let mut m = [0u8; 4];
-        stream.read_exact(&mut m)?;
+        stream.read_exact(&mut m).await?;
         Some(m)
     } else {
         None
     };
 
     let mut payload = vec![0u8; len];
-    stream.read_exact(&mut payload)?;
+    stream.read_exact(&mut payload).await?;
 
     if let Some(mask) = mask {
         for (i, byte) in payload.iter_mut().enumerate() {
@@ -147,16 +229,42 @@ fn read_ws_frame(stream: &mut TcpStream) -> std::io::Result<(u8, Vec<u8>)> {
 
 /// CDP Transport - handles sending commands and receiving responses via WebSocket
 pub struct Transport {
-    /// The Chrome child process
-    child: Mutex<Child>,
-    /// WebSocket stream for writing
-    writer: Mutex<TcpStream>,
+    /// The Chrome child process (None when attached to an external endpoint).
+    /// Shared with the supervisor task, locked only briefly.
+    child: Arc<std::sync::Mutex<Option<Child>>>,
+    /// Set once Chrome exits or the socket drops; commands then fail fast
+    closed: Arc<AtomicBool>,
+    /// WebSocket write half (shared with the reader task for pongs)
+    writer: Arc<Mutex<OwnedWriteHalf>>,
     /// Next message ID
     next_id: AtomicU64,
     /// Pending requests waiting for responses
     pending: Arc<PendingMap>,
     /// Channel to receive parsed messages from the reader task
     event_rx: Mutex<mpsc::Receiver<CdpMessage>>,
+    /// Document epochs per session, bumped by the reader task
+    document_epochs: Arc<DocumentEpochs>,
+    /// Targets created since discovery was enabled, recorded by the reader task
+    created_targets: Arc<CreatedTargets>,
+    /// Per-session, per-method event fan-out
+    subscribers: Arc<Subscribers>,
+    /// Reader task, aborted on drop
+    reader: JoinHandle<()>,
+    /// Process supervisor task (only with an owned child), aborted on drop
+    supervisor: Option<JoinHandle<()>>,
+    /// Traffic log, shared with the reader task
+    log: Option<Arc<CdpLog>>,
+}
+
+/// Transport state the reader task writes into
+struct ReaderContext {
+    writer: Arc<Mutex<OwnedWriteHalf>>,
+    pending: Arc<PendingMap>,
+    document_epochs: Arc<DocumentEpochs>,
+    created_targets: Arc<CreatedTargets>,
+    subscribers: Arc<Subscribers>,
+    event_tx: mpsc::Sender<CdpMessage>,
+    log: Option<Arc<CdpLog>>,
 }
 
 /// A parsed CDP message (response or event)
@@ -175,14 +283,33 @@ pub enum CdpMessage {
 
 impl Transport {
     /// Create a new transport connecting to Chrome via WebSocket
-    pub fn new(child: Child, ws_url: &str) -> Result<Self> {
+    pub async fn new(child: Child, ws_url: &str) -> Result<Self> {
+        Self::connect(Some(child), ws_url).await
+    }
+
+    /// Connect to a WebSocket debugger URL, optionally owning the browser process
+    pub(crate) async fn connect(child: Option<Child>, ws_url: &str) -> Result<Self> {
+        Self::connect_with_log(child, ws_url, None).await
+    }
+
+    /// Like [`Transport::connect`], additionally writing every command,
+    /// response and event as a JSON line to `cdp_log`
+    pub(crate) async fn connect_with_log(
+        child: Option<Child>,
+        ws_url: &str,
+        cdp_log: Option<&Path>,
+    ) -> Result<Self> {
+        let log = cdp_log.map(CdpLog::create).transpose()?.map(Arc::new);
+
         // Parse WebSocket URL
         let url = ws_url.trim_start_matches("ws://");
         let (host_port, _path) = url.split_once('/').unwrap_or((url, ""));
 
         // Connect TCP
         let mut stream = TcpStream::connect(host_port)
+            .await
             .map_err(|e| Error::transport_io("Failed to connect to Chrome", e))?;
+        let _ = stream.set_nodelay(true);
 
         // WebSocket handshake
         let path = format!("/{}", url.split_once('/').map(|(_, p)| p).unwrap_or(""));
@@ -202,27 +329,25 @@ impl Transport {
             path, host_port, key
         );
 
-        use std::io::{BufRead, Write};
         stream
             .write_all(handshake.as_bytes())
+            .await
             .map_err(|e| Error::transport_io("Handshake write failed", e))?;
 
         // Read handshake response - loop until we see the end of HTTP headers.
-        // Use BufReader to avoid a syscall per byte.
-        let mut reader = std::io::BufReader::new(&stream);
         let mut response_buf = Vec::with_capacity(1024);
+        let mut chunk = [0u8; 1024];
         loop {
-            let available = reader
-                .fill_buf()
+            let n = stream
+                .read(&mut chunk)
+                .await
                 .map_err(|e| Error::transport_io("Handshake read failed", e))?;
-            if available.is_empty() {
+            if n == 0 {
                 return Err(Error::transport(
                     "Connection closed during WebSocket handshake",
                 ));
             }
-            let len = available.len();
-            response_buf.extend_from_slice(available);
-            reader.consume(len);
+            response_buf.extend_from_slice(&chunk[..n]);
             if response_buf.len() >= 4 && response_buf[response_buf.len() - 4..] == *b"\r\n\r\n" {
                 break;
             }
@@ -241,37 +366,114 @@ impl Transport {
 
         tracing::debug!("WebSocket connected to {}", ws_url);
 
-        // Clone stream for reader
-        let reader_stream = stream
-            .try_clone()
-            .map_err(|e| Error::transport_io("Failed to clone stream", e))?;
+        let (read_half, write_half) = stream.into_split();
+        let writer = Arc::new(Mutex::new(write_half));
 
         let pending: Arc<PendingMap> = Arc::new(std::sync::Mutex::new(HashMap::new()));
         let (event_tx, event_rx) = mpsc::channel(256);
+        let document_epochs: Arc<DocumentEpochs> = Arc::new(std::sync::Mutex::new(HashMap::new()));
+        let created_targets: Arc<CreatedTargets> = Arc::new(std::sync::Mutex::new(VecDeque::new()));
+        let subscribers: Arc<Subscribers> = Arc::new(std::sync::Mutex::new(HashMap::new()));
+        let closed = Arc::new(AtomicBool::new(false));
+
+        // Spawn reader task. Frames are parsed from a buffer so a burst of
+        // small events costs one read syscall instead of several per frame.
+        let reader = tokio::spawn({
+            let pending = Arc::clone(&pending);
+            let closed = Arc::clone(&closed);
+            let reader_loop = Self::reader_loop(
+                BufReader::with_capacity(READ_BUFFER_SIZE, read_half),
+                ReaderContext {
+                    writer: Arc::clone(&writer),
+                    pending: Arc::clone(&pending),
+                    document_epochs: Arc::clone(&document_epochs),
+                    created_targets: Arc::clone(&created_targets),
+                    subscribers: Arc::clone(&subscribers),
+                    event_tx,
+                    log: log.clone(),
+                },
+            );
+            async move {
+                reader_loop.await;
+                // Fail any in-flight commands instead of leaving them waiting forever
+                Self::mark_closed(&closed, &pending);
+            }
+        });
 
-        // Spawn reader task
-        let pending_clone = Arc::clone(&pending);
-        std::thread::spawn(move || {
-            Self::reader_loop(reader_stream, pending_clone, event_tx);
+        let has_child = child.is_some();
+        let child = Arc::new(std::sync::Mutex::new(child));
+        let supervisor = has_child.then(|| {
+            tokio::spawn(Self::supervise(
+                Arc::clone(&child),
+                Arc::clone(&pending),
+                Arc::clone(&closed),
+            ))
         });
 
         Ok(Self {
-            child: Mutex::new(child),
-            writer: Mutex::new(stream),
+            child,
+            closed,
+            writer,
             next_id: AtomicU64::new(1),
             pending,
             event_rx: Mutex::new(event_rx),
+            document_epochs,
+            created_targets,
+            subscribers,
+            reader,
+            supervisor,
+            log,
         })
     }
 
-    /// Reader loop - runs in a separate thread to read from WebSocket
-    fn reader_loop(
-        mut stream: TcpStream,
+    /// Watch the Chrome process and mark the transport closed once it exits.
+    ///
+    /// A crashed browser normally also drops the socket, but this catches the
+    /// cases where the read side doesn't notice promptly.
+    async fn supervise(
+        child: Arc<std::sync::Mutex<Option<Child>>>,
         pending: Arc<PendingMap>,
-        event_tx: mpsc::Sender<CdpMessage>,
+        closed: Arc<AtomicBool>,
     ) {
         loop {
-            let (opcode, payload) = match read_ws_frame(&mut stream) {
+            tokio::time::sleep(SUPERVISOR_INTERVAL).await;
+            let exited = match child.lock().unwrap().as_mut() {
+                Some(child) => !matches!(child.try_wait(), Ok(None)),
+                None => true,
+            };
+            if exited {
+                tracing::warn!("Chrome process exited");
+                Self::mark_closed(&closed, &pending);
+                return;
+            }
+        }
+    }
+
+    /// Flip into the closed state and fail every in-flight command
+    fn mark_closed(closed: &AtomicBool, pending: &PendingMap) {
+        closed.store(true, Ordering::SeqCst);
+        // Dropping the senders wakes waiters with `SessionClosed`
+        pending.lock().unwrap().clear();
+    }
+
+    /// Whether Chrome is gone (process exited or connection dropped)
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    /// Reader loop - runs as a Tokio task reading from the WebSocket
+    async fn reader_loop(mut stream: BufReader<OwnedReadHalf>, ctx: ReaderContext) {
+        let ReaderContext {
+            writer,
+            pending,
+            document_epochs,
+            created_targets,
+            subscribers,
+            event_tx,
+            log,
+        } = ctx;
+        loop {
+            let (opcode, payload) = match read_ws_frame(&mut stream).await {
                 Ok(frame) => frame,
                 Err(e) => {
                     tracing::debug!("WebSocket read error: {}", e);
@@ -294,6 +496,10 @@ impl Transport {
                         }
                     };
 
+                    if let Some(log) = &log {
+                        log.record("recv", &msg);
+                    }
+
                     // Check if response or event
                     if let Some(id) = msg.get("id").and_then(|v| v.as_u64()) {
                         let result = if let Some(error) = msg.get("error") {
@@ -311,11 +517,12 @@ impl Transport {
                             Ok(msg.get("result").cloned().unwrap_or(json!({})))
                         };
 
-                        let mut pending_guard = pending.lock().unwrap();
-                        if let Some(sender) = pending_guard.remove(&id) {
-                            let _ = sender.send(result);
-                        } else {
-                            tracing::trace!("Response for unknown id: {}", id);
+                        let sender = pending.lock().unwrap().remove(&id);
+                        match sender {
+                            Some(sender) => {
+                                let _ = sender.send(result);
+                            }
+                            None => tracing::trace!("Response for unknown id: {}", id),
                         }
                     } else if let Some(method) = msg.get("method").and_then(|m| m.as_str()) {
                         let params = msg.get("params").cloned().unwrap_or(json!({}));
@@ -324,17 +531,49 @@ impl Transport {
                             .and_then(|s| s.as_str())
                             .map(String::from);
 
-                        let _ = event_tx.blocking_send(CdpMessage::Event {
-                            method: method.to_string(),
-                            params,
-                            session_id,
-                        });
+                        // Track document replacement here rather than in a consumer,
+                        // so node id caches stay correct even if nobody drains events.
+                        if let Some(sid) = &session_id {
+                            if replaces_document(method, &params) {
+                                *document_epochs
+                                    .lock()
+                                    .unwrap()
+                                    .entry(sid.clone())
+                                    .or_insert(0) += 1;
+                            }
+                        }
+
+                        if method == "Target.targetCreated" {
+                            if let Ok(event) =
+                                serde_json::from_value::<TargetTargetCreatedEvent>(params.clone())
+                            {
+                                let mut targets = created_targets.lock().unwrap();
+                                if targets.len() == MAX_CREATED_TARGETS {
+                                    targets.pop_front();
+                                }
+                                targets.push_back(event.target_info);
+                            }
+                        }
+
+                        Self::publish(&subscribers, &session_id, method, &params);
+
+                        // Never wait on a slow (or absent) event consumer: that
+                        // would stall command responses behind it.
+                        if let Err(mpsc::error::TrySendError::Full(_)) =
+                            event_tx.try_send(CdpMessage::Event {
+                                method: method.to_string(),
+                                params,
+                                session_id,
+                            })
+                        {
+                            tracing::trace!("Event queue full, dropping {}", method);
+                        }
                     }
                 }
                 ws::OPCODE_PING => {
                     // Respond with pong
-                    let frame = vec![0x80 | ws::OPCODE_PONG, 0x80, 0, 0, 0, 0];
-                    let _ = std::io::Write::write_all(&mut stream, &frame);
+                    let mut writer = writer.lock().await;
+                    let _ = write_ws_frame(&mut *writer, ws::OPCODE_PONG, &payload).await;
                 }
                 ws::OPCODE_CLOSE => {
                     tracing::debug!("WebSocket closed by server");
@@ -347,6 +586,28 @@ impl Transport {
         tracing::debug!("CDP reader loop ended");
     }
 
+    /// Deliver an event to its subscribers, dropping senders nobody listens to
+    fn publish(
+        subscribers: &Subscribers,
+        session_id: &Option<String>,
+        method: &str,
+        params: &Value,
+    ) {
+        let mut subscribers = subscribers.lock().unwrap();
+        let key = (session_id.clone(), method.to_string());
+        if let Some(sinks) = subscribers.get_mut(&key) {
+            if let Some(sender) = &sinks.broadcast {
+                if sender.send(params.clone()).is_err() {
+                    sinks.broadcast = None;
+                }
+            }
+            sinks.unbounded.retain(|tx| tx.send(params.clone()).is_ok());
+            if sinks.broadcast.is_none() && sinks.unbounded.is_empty() {
+                subscribers.remove(&key);
+            }
+        }
+    }
+
     /// Internal: send a CDP command with optional session ID
     async fn send_impl<C, R>(&self, session_id: Option<&str>, method: &str, params: &C) -> Result<R>
     where
@@ -373,6 +634,13 @@ impl Transport {
             pending.insert(id, tx);
         }
 
+        // Checked after registering, so a concurrent close either sees our
+        // entry and drops it, or we see the flag here
+        if self.is_closed() {
+            self.pending.lock().unwrap().remove(&id);
+            return Err(Error::SessionClosed);
+        }
+
         // Build message
         let mut msg = json!({
             "id": id,
@@ -387,16 +655,30 @@ impl Transport {
 
         {
             let mut writer = self.writer.lock().await;
-            write_ws_frame(&mut writer, data.as_bytes())
-                .map_err(|e| Error::transport_io("WebSocket write failed", e))?;
+            // Logged under the writer lock and before writing, so the entry
+            // always precedes the response's and sends stay in wire order
+            if let Some(log) = &self.log {
+                log.record("send", &msg);
+            }
+            if let Err(e) = write_ws_frame(&mut *writer, ws::OPCODE_TEXT, data.as_bytes()).await {
+                self.pending.lock().unwrap().remove(&id);
+                return Err(Error::transport_io("WebSocket write failed", e));
+            }
         }
 
         tracing::trace!("Sent CDP command: {} (id={})", method, id);
 
-        // Wait for response
+        // Wait for response. The reader doesn't know which method an id belongs
+        // to, so attach it here while mapping known failures to typed errors.
         let result = rx
             .await
-            .map_err(|_| Error::transport("Response channel closed"))??;
+            .map_err(|_| Error::SessionClosed)?
+            .map_err(|e| match e {
+                Error::Cdp { code, message, .. } => {
+                    Error::from_cdp_response(method, code, &message)
+                }
+                e => e,
+            })?;
 
         let response: R = serde_json::from_value(result)?;
         Ok(response)
@@ -425,6 +707,59 @@ impl Transport {
         self.send_impl(Some(session_id), method, params).await
     }
 
+    /// Number of times the session's main document has been replaced
+    /// (main-frame navigation or `DOM.documentUpdated`). Node ids obtained
+    /// under an older epoch should be considered stale.
+    pub fn document_epoch(&self, session_id: &str) -> u64 {
+        self.document_epochs
+            .lock()
+            .unwrap()
+            .get(session_id)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Take all targets announced via `Target.targetCreated` since the last call.
+    /// Requires target discovery (`Target.setDiscoverTargets`) to be enabled.
+    pub fn take_created_targets(&self) -> Vec<TargetInfo> {
+        self.created_targets.lock().unwrap().drain(..).collect()
+    }
+
+    /// Subscribe to an event method (e.g. `"Page.loadEventFired"`) for one session,
+    /// or for browser-level events when `session_id` is `None`.
+    ///
+    /// Each receiver gets its own copy of the event params, independent of
+    /// [`Transport::recv_event`] and of other subscribers. The domain must be
+    /// enabled (e.g. `Page.enable`) for Chrome to emit its events.
+    pub fn subscribe(&self, session_id: Option<&str>, method: &str) -> broadcast::Receiver<Value> {
+        self.subscribers
+            .lock()
+            .unwrap()
+            .entry((session_id.map(String::from), method.to_string()))
+            .or_default()
+            .broadcast
+            .get_or_insert_with(|| broadcast::channel(SUBSCRIBER_CAPACITY).0)
+            .subscribe()
+    }
+
+    /// Like [`Transport::subscribe`], but the receiver never lags: events
+    /// queue until read. For consumers that must see every event.
+    pub fn subscribe_unbounded(
+        &self,
+        session_id: Option<&str>,
+        method: &str,
+    ) -> mpsc::UnboundedReceiver<Value> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.subscribers
+            .lock()
+            .unwrap()
+            .entry((session_id.map(String::from), method.to_string()))
+            .or_default()
+            .unbounded
+            .push(tx);
+        rx
+    }
+
     /// Receive the next event from Chrome
     pub async fn recv_event(&self) -> Option<CdpMessage> {
         let mut rx = self.event_rx.lock().await;
@@ -437,27 +772,56 @@ impl Transport {
         rx.try_recv().ok()
     }
 
-    /// Close the transport and kill Chrome
-    pub async fn close(&self) -> Result<()> {
+    /// Close the transport and wait up to `timeout` for Chrome to exit.
+    ///
+    /// Chrome is killed if it is still running after the timeout, and
+    /// `Error::Timeout` is returned so callers know shutdown wasn't clean.
+    pub async fn close(&self, timeout: Duration) -> Result<()> {
         // Send WebSocket close frame
         {
             let mut writer = self.writer.lock().await;
-            let close_frame = vec![0x80 | ws::OPCODE_CLOSE, 0x80, 0, 0, 0, 0];
-            let _ = std::io::Write::write_all(&mut *writer, &close_frame);
+            let _ = write_ws_frame(&mut *writer, ws::OPCODE_CLOSE, &[]).await;
         }
 
-        let mut child = self.child.lock().await;
-        let _ = child.kill();
-        let _ = child.wait();
-        Ok(())
+        let start = Instant::now();
+        loop {
+            let status = self.child.lock().unwrap().as_mut().map(Child::try_wait);
+            match status {
+                None => return Ok(()),
+                Some(Ok(Some(status))) => {
+                    tracing::debug!("Chrome exited with {}", status);
+                    return Ok(());
+                }
+                Some(Ok(None)) if start.elapsed() < timeout => {
+                    tokio::time::sleep(EXIT_POLL_INTERVAL).await;
+                }
+                _ => break,
+            }
+        }
+
+        tracing::warn!("Chrome still running after {:?}, killing it", timeout);
+        if let Some(child) = self.child.lock().unwrap().as_mut() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        Err(Error::Timeout(format!(
+            "Chrome did not exit within {}ms and was force-killed",
+            timeout.as_millis()
+        )))
     }
 }
 
 impl Drop for Transport {
     fn drop(&mut self) {
+        self.reader.abort();
+        if let Some(supervisor) = &self.supervisor {
+            supervisor.abort();
+        }
         // Try to kill Chrome process on drop
-        if let Ok(mut child) = self.child.try_lock() {
-            let _ = child.kill();
+        if let Ok(mut child) = self.child.lock() {
+            if let Some(child) = child.as_mut() {
+                let _ = child.kill();
+            }
         }
     }
 }
@@ -483,7 +847,7 @@ pub fn launch_chrome(path: &std::path::Path, args: &[String]) -> Result<(Child,
         .take()
         .ok_or(Error::Launch("No stderr from Chrome".into()))?;
 
-    let reader = BufReader::new(stderr);
+    let reader = std::io::BufReader::new(stderr);
     let mut ws_url = None;
 
     // Chrome prints: DevTools listening on ws://127.0.0.1:PORT/devtools/browser/GUID
@@ -511,3 +875,265 @@ pub fn launch_chrome(path: &std::path::Path, args: &[String]) -> Result<(Child,
 
     Ok((child, ws_url))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    /// Minimal CDP server: completes the handshake, then for each command emits
+    /// a `Test.commandSeen` event on the command's session and echoes the id
+    /// back as a response with the method name in the result.
+    async fn fake_cdp_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = Vec::new();
+            let mut chunk = [0u8; 1024];
+            while !buf.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut chunk).await.unwrap();
+                buf.extend_from_slice(&chunk[..n]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 101 Switching Protocols\r\n\r\n")
+                .await
+                .unwrap();
+
+            let (mut reader, mut writer) = stream.into_split();
+            while let Ok((opcode, payload)) = read_ws_frame(&mut reader).await {
+                if opcode != ws::OPCODE_TEXT {
+                    break;
+                }
+                let msg: Value = serde_json::from_slice(&payload).unwrap();
+                let mut event =
+                    json!({ "method": "Test.commandSeen", "params": { "method": msg["method"] } });
+                if let Some(sid) = msg.get("sessionId") {
+                    event["sessionId"] = sid.clone();
+                }
+                let frame = encode_ws_frame(ws::OPCODE_TEXT, event.to_string().as_bytes(), None);
+                writer.write_all(&frame).await.unwrap();
+                let reply = json!({ "id": msg["id"], "result": { "method": msg["method"] } });
+                let frame = encode_ws_frame(ws::OPCODE_TEXT, reply.to_string().as_bytes(), None);
+                writer.write_all(&frame).await.unwrap();
+            }
+        });
+
+        format!("ws://{}/devtools/browser/test", addr)
+    }
+
+    #[test]
+    fn test_frame_roundtrip_extended_lengths() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        for len in [0, 125, 126, 65535, 65536] {
+            let data = vec![b'x'; len];
+            let frame = encode_ws_frame(ws::OPCODE_TEXT, &data, Some([1, 2, 3, 4]));
+            let (opcode, payload) = rt.block_on(read_ws_frame(&mut &frame[..])).unwrap();
+            assert_eq!(opcode, ws::OPCODE_TEXT);
+            assert_eq!(payload, data);
+        }
+    }
+
+    #[test]
+    fn test_replaces_document_only_for_main_frame() {
+        let main = json!({ "frame": { "id": "A", "url": "about:blank" } });
+        let child = json!({ "frame": { "id": "B", "parentId": "A", "url": "about:blank" } });
+        assert!(replaces_document("Page.frameNavigated", &main));
+        assert!(!replaces_document("Page.frameNavigated", &child));
+        assert!(replaces_document("DOM.documentUpdated", &json!({})));
+        assert!(!replaces_document("Page.loadEventFired", &json!({})));
+    }
+
+    #[tokio::test]
+    async fn test_buffered_reader_parses_concatenated_frames() {
+        let payloads: Vec<Vec<u8>> = vec![
+            b"{}".to_vec(),
+            vec![b'a'; 300],   // 16-bit extended length
+            vec![b'b'; 70000], // 64-bit extended length
+            b"tail".to_vec(),
+        ];
+        let mut stream = Vec::new();
+        for p in &payloads {
+            stream.extend(encode_ws_frame(ws::OPCODE_TEXT, p, None));
+        }
+
+        // Tiny buffer so headers and length fields straddle refills
+        let mut reader = BufReader::with_capacity(7, &stream[..]);
+        for expected in &payloads {
+            let (opcode, payload) = read_ws_frame(&mut reader).await.unwrap();
+            assert_eq!(opcode, ws::OPCODE_TEXT);
+            assert_eq!(&payload, expected);
+        }
+        assert!(read_ws_frame(&mut reader).await.is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_commands_all_get_responses() {
+        let url = fake_cdp_server().await;
+        let transport = Arc::new(Transport::connect(None, &url).await.unwrap());
+
+        let tasks: Vec<_> = (0..64)
+            .map(|i| {
+                let transport = Arc::clone(&transport);
+                tokio::spawn(async move {
+                    let method = format!("Test.method{}", i);
+                    let result: Value = transport.send(&method, &json!({})).await.unwrap();
+                    assert_eq!(result["method"], method);
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert!(transport.pending.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_only_their_session_events() {
+        let url = fake_cdp_server().await;
+        let transport = Transport::connect(None, &url).await.unwrap();
+
+        let mut a1 = transport.subscribe(Some("A"), "Test.commandSeen");
+        let mut a2 = transport.subscribe(Some("A"), "Test.commandSeen");
+        let mut b = transport.subscribe(Some("B"), "Test.commandSeen");
+
+        let _: Value = transport
+            .send_to_session("A", "Page.navigate", &json!({}))
+            .await
+            .unwrap();
+
+        // The event is written before the response, so it's already published
+        assert_eq!(a1.try_recv().unwrap()["method"], "Page.navigate");
+        assert_eq!(a2.try_recv().unwrap()["method"], "Page.navigate");
+        assert!(b.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unbounded_subscriber_never_lags() {
+        let url = fake_cdp_server().await;
+        let transport = Transport::connect(None, &url).await.unwrap();
+
+        let mut bounded = transport.subscribe(Some("A"), "Test.commandSeen");
+        let mut unbounded = transport.subscribe_unbounded(Some("A"), "Test.commandSeen");
+
+        let count = SUBSCRIBER_CAPACITY + 10;
+        for _ in 0..count {
+            let _: Value = transport
+                .send_to_session("A", "Page.navigate", &json!({}))
+                .await
+                .unwrap();
+        }
+
+        assert!(matches!(
+            bounded.try_recv(),
+            Err(broadcast::error::TryRecvError::Lagged(_))
+        ));
+        for _ in 0..count {
+            assert_eq!(unbounded.try_recv().unwrap()["method"], "Page.navigate");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cdp_log_records_traffic() {
+        let url = fake_cdp_server().await;
+        let path = std::env::temp_dir().join(format!("eoka-cdp-log-{}.jsonl", std::process::id()));
+        let transport = Transport::connect_with_log(None, &url, Some(&path))
+            .await
+            .unwrap();
+
+        let _: Value = transport
+            .send_to_session("A", "Page.navigate", &json!({}))
+            .await
+            .unwrap();
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let lines: Vec<Value> = log
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        // Command, then the echoed event, then the response
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["dir"], "send");
+        assert_eq!(lines[0]["message"]["method"], "Page.navigate");
+        assert_eq!(lines[1]["dir"], "recv");
+        assert_eq!(lines[1]["message"]["method"], "Test.commandSeen");
+        assert_eq!(lines[2]["message"]["id"], lines[0]["message"]["id"]);
+        assert!(lines[0]["ts"].as_f64().unwrap() > 0.0);
+    }
+
+    /// Stand-in for Chrome that ignores the shutdown request
+    #[cfg(unix)]
+    fn spawn_stuck_process() -> Child {
+        std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap()
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_close_force_kills_after_timeout() {
+        let url = fake_cdp_server().await;
+        let transport = Transport::connect(Some(spawn_stuck_process()), &url)
+            .await
+            .unwrap();
+
+        let start = Instant::now();
+        let result = transport.close(Duration::from_millis(200)).await;
+        assert!(matches!(result, Err(Error::Timeout(_))));
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        // The process was reaped, not left running
+        let mut child = transport.child.lock().unwrap();
+        assert!(child.as_mut().unwrap().try_wait().unwrap().is_some());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_close_ok_when_process_exits() {
+        let url = fake_cdp_server().await;
+        let child = std::process::Command::new("true").spawn().unwrap();
+        let transport = Transport::connect(Some(child), &url).await.unwrap();
+
+        assert!(transport.close(Duration::from_secs(5)).await.is_ok());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_commands_fail_fast_after_process_dies() {
+        let url = fake_cdp_server().await;
+        let transport = Transport::connect(Some(spawn_stuck_process()), &url)
+            .await
+            .unwrap();
+        let _: Value = transport.send("Test.before", &json!({})).await.unwrap();
+        assert!(!transport.is_closed());
+
+        transport
+            .child
+            .lock()
+            .unwrap()
+            .as_mut()
+            .unwrap()
+            .kill()
+            .unwrap();
+
+        // The supervisor notices within a poll interval or two
+        let start = Instant::now();
+        while !transport.is_closed() {
+            assert!(
+                start.elapsed() < Duration::from_secs(2),
+                "crash not detected"
+            );
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        // The fake server is still up, so this only fails because of the flag
+        let result: Result<Value> = transport.send("Test.after", &json!({})).await;
+        assert!(matches!(result, Err(Error::SessionClosed)));
+    }
+}
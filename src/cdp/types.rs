@@ -50,6 +50,12 @@ pub struct TargetAttachToTargetResult {
     pub session_id: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetDetachFromTarget {
+    pub session_id: String,
+}
+
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct TargetGetTargets {}
 
@@ -60,6 +66,29 @@ pub struct TargetGetTargetsResult {
     pub target_infos: Vec<TargetInfo>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetGetTargetInfo {
+    pub target_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetGetTargetInfoResult {
+    pub target_info: TargetInfo,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TargetGetBrowserContexts {}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetGetBrowserContextsResult {
+    /// Contexts created with `Target.createBrowserContext`; never the default one
+    #[serde(default)]
+    pub browser_context_ids: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TargetActivateTarget {
@@ -75,6 +104,11 @@ pub struct TargetInfo {
     pub url: String,
     #[serde(default)]
     pub attached: bool,
+    /// Target that opened this one (popup / `target=_blank`)
+    #[serde(default)]
+    pub opener_id: Option<String>,
+    #[serde(default)]
+    pub browser_context_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -147,6 +181,11 @@ pub struct PageAddScriptToEvaluateOnNewDocumentResult {
     pub identifier: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct PageRemoveScriptToEvaluateOnNewDocument {
+    pub identifier: String,
+}
+
 #[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PageCaptureScreenshot {
@@ -154,6 +193,20 @@ pub struct PageCaptureScreenshot {
     pub format: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub quality: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clip: Option<ScreenshotClip>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capture_beyond_viewport: Option<bool>,
+}
+
+/// Region to capture, in CSS pixels relative to the document
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ScreenshotClip {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub scale: f64,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -162,6 +215,34 @@ pub struct PageCaptureScreenshotResult {
     pub data: String,
 }
 
+/// Page.printToPDF (paper sizes and margins in inches)
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PagePrintToPdf {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub print_background: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paper_width: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paper_height: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub margin_top: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub margin_bottom: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub margin_left: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub margin_right: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_ranges: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PagePrintToPdfResult {
+    #[serde(default)]
+    pub data: String,
+}
+
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct PageGetFrameTree {}
 
@@ -190,6 +271,9 @@ pub struct Frame {
     pub name: Option<String>,
     #[serde(default)]
     pub url: String,
+    /// Identifies the document load; changes on every cross-document navigation
+    #[serde(default)]
+    pub loader_id: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -267,7 +351,7 @@ pub struct NetworkGetCookiesResult {
     pub cookies: Vec<Cookie>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Cookie {
     pub name: String,
@@ -322,6 +406,12 @@ pub struct NetworkDeleteCookies {
     pub path: Option<String>,
 }
 
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NetworkClearBrowserCookies {}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NetworkClearBrowserCache {}
+
 #[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NetworkEnable {
@@ -332,6 +422,11 @@ pub struct NetworkEnable {
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct NetworkDisable {}
 
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NetworkSetExtraHTTPHeaders {
+    pub headers: HashMap<String, String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NetworkGetResponseBody {
@@ -347,6 +442,30 @@ pub struct NetworkGetResponseBodyResult {
     pub base64_encoded: bool,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkStreamResourceContent {
+    pub request_id: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkStreamResourceContentResult {
+    /// Base64 data received before streaming was enabled
+    #[serde(default)]
+    pub buffered_data: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkDataReceivedEvent {
+    pub request_id: String,
+    pub data_length: i64,
+    /// Base64 chunk, only sent for requests being streamed
+    #[serde(default)]
+    pub data: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NetworkRequest {
@@ -424,6 +543,21 @@ pub struct DOMGetDocumentResult {
 pub struct DOMNode {
     #[serde(default)]
     pub node_id: i32,
+    /// Frame ID, for frame owner elements (`<iframe>`)
+    #[serde(default)]
+    pub frame_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DOMDescribeNode {
+    pub node_id: i32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DOMDescribeNodeResult {
+    pub node: DOMNode,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -472,6 +606,9 @@ pub struct DOMGetBoxModelResult {
 pub struct BoxModel {
     #[serde(default)]
     pub content: Vec<f64>,
+    /// Border quad: the content box plus padding and border
+    #[serde(default)]
+    pub border: Vec<f64>,
 }
 
 impl BoxModel {
@@ -521,6 +658,12 @@ pub struct RuntimeGetProperties {
     pub own_properties: Option<bool>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeReleaseObject {
+    pub object_id: String,
+}
+
 #[derive(Debug, Clone, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RuntimeGetPropertiesResult {
@@ -554,6 +697,8 @@ pub struct RuntimeEvaluate {
     pub return_by_value: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub await_promise: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_id: Option<i64>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -588,6 +733,9 @@ pub struct ExceptionDetails {
     pub text: String,
     pub line_number: i32,
     pub column_number: i32,
+    /// The thrown value (its `description` carries e.g. `NotAllowedError: ...`)
+    #[serde(default)]
+    pub exception: Option<RemoteObject>,
 }
 
 #[derive(Debug, Clone, Default, Serialize)]
@@ -699,6 +847,69 @@ pub mod modifiers {
     pub const SHIFT: i32 = 8;
 }
 
+// === Frames ===
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageCreateIsolatedWorld {
+    pub frame_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub world_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageCreateIsolatedWorldResult {
+    pub execution_context_id: i64,
+}
+
+// === Permissions ===
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrowserGrantPermissions {
+    pub permissions: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PageBringToFront {}
+
+// === Downloads ===
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrowserSetDownloadBehavior {
+    /// `deny`, `allow`, `allowAndName` (file named by guid) or `default`
+    pub behavior: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_path: Option<String>,
+    /// Context to apply to; the default browser context when omitted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub browser_context_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events_enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrowserDownloadWillBeginEvent {
+    pub guid: String,
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub suggested_filename: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrowserDownloadProgressEvent {
+    pub guid: String,
+    /// `inProgress`, `completed` or `canceled`
+    pub state: String,
+}
+
 // === Target Discovery (for multi-tab) ===
 
 #[derive(Debug, Clone, Serialize)]
@@ -718,3 +929,173 @@ pub struct TargetTargetCreatedEvent {
 pub struct TargetTargetDestroyedEvent {
     pub target_id: String,
 }
+
+// === Storage ===
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StorageClearCookies {}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageClearDataForOrigin {
+    pub origin: String,
+    /// Comma-separated storage types (`all`, `local_storage`, `indexeddb`, ...)
+    pub storage_types: String,
+}
+
+// === Emulation ===
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmulationSetDeviceMetricsOverride {
+    pub width: u32,
+    pub height: u32,
+    pub device_scale_factor: f64,
+    pub mobile: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub screen_width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub screen_height: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EmulationClearDeviceMetricsOverride {}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmulationSetPageScaleFactor {
+    pub page_scale_factor: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmulationSetTimezoneOverride {
+    /// IANA id; empty restores the host timezone
+    pub timezone_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmulationSetUserAgentOverride {
+    pub user_agent: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_language: Option<String>,
+    /// `navigator.platform`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+    /// Client Hints (`navigator.userAgentData`, `Sec-CH-UA-*` headers)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent_metadata: Option<UserAgentMetadata>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserAgentMetadata {
+    pub brands: Vec<UserAgentBrandVersion>,
+    pub full_version_list: Vec<UserAgentBrandVersion>,
+    pub platform: String,
+    pub platform_version: String,
+    pub architecture: String,
+    pub model: String,
+    pub mobile: bool,
+    pub bitness: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UserAgentBrandVersion {
+    pub brand: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EmulationSetFocusEmulationEnabled {
+    pub enabled: bool,
+}
+
+// === Performance ===
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PerformanceEnable {}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PerformanceGetMetrics {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PerformanceGetMetricsResult {
+    pub metrics: Vec<PerformanceMetric>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PerformanceMetric {
+    /// `Nodes`, `JSHeapUsedSize`, `Documents`, ...
+    pub name: String,
+    pub value: f64,
+}
+
+// === Fetch (request interception) ===
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchRequestPattern {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url_pattern: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_stage: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchEnable {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patterns: Option<Vec<FetchRequestPattern>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FetchDisable {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeaderEntry {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchRequestPausedEvent {
+    pub request_id: String,
+    pub request: NetworkRequest,
+    #[serde(default)]
+    pub resource_type: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchContinueRequest {
+    pub request_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Base64-encoded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Vec<HeaderEntry>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchFailRequest {
+    pub request_id: String,
+    pub error_reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchFulfillRequest {
+    pub request_id: String,
+    pub response_code: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_headers: Option<Vec<HeaderEntry>>,
+    /// Base64-encoded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
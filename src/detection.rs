@@ -0,0 +1,155 @@
+//! Local Detection Self-Test
+//!
+//! A handful of common bot checks run as plain JavaScript on a `data:` page,
+//! so stealth can be verified without any external site. See
+//! [`crate::Browser::run_detection_suite`].
+
+use std::fmt;
+
+use serde::Deserialize;
+
+/// Result of one check in a [`DetectionReport`]
+#[derive(Debug, Clone, Deserialize)]
+pub struct DetectionCheck {
+    /// Short identifier (`webdriver`, `plugins`, ...)
+    pub name: String,
+    pub passed: bool,
+    /// What the page saw
+    pub detail: String,
+}
+
+/// Outcome of [`crate::Browser::run_detection_suite`]
+#[derive(Debug, Clone)]
+pub struct DetectionReport {
+    pub checks: Vec<DetectionCheck>,
+}
+
+impl DetectionReport {
+    /// Whether every check passed
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|c| c.passed)
+    }
+
+    /// The checks that failed
+    pub fn failures(&self) -> impl Iterator<Item = &DetectionCheck> {
+        self.checks.iter().filter(|c| !c.passed)
+    }
+
+    /// Look up a check by name
+    pub fn check(&self, name: &str) -> Option<&DetectionCheck> {
+        self.checks.iter().find(|c| c.name == name)
+    }
+}
+
+impl fmt::Display for DetectionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            let status = if check.passed { "PASS" } else { "FAIL" };
+            writeln!(f, "[{}] {}: {}", status, check.name, check.detail)?;
+        }
+        Ok(())
+    }
+}
+
+/// Page the suite runs on; evasions apply to it like to any first document
+pub(crate) const DETECTION_PAGE: &str = "data:text/html,<title>eoka self-test</title>";
+
+/// Evaluates to an array of `{ name, passed, detail }`
+pub(crate) const DETECTION_JS: &str = r#"
+(() => {
+    const checks = [];
+    const check = (name, passed, detail) => checks.push({ name, passed: !!passed, detail: String(detail) });
+
+    check('webdriver', navigator.webdriver === false || navigator.webdriver === undefined,
+        'navigator.webdriver = ' + navigator.webdriver);
+
+    const markerPattern = /cdc_|webdriver|selenium|__driver|domAutomation|callPhantom|_phantom|__nightmare/i;
+    const markers = Object.getOwnPropertyNames(window)
+        .concat(Object.getOwnPropertyNames(document))
+        .filter(key => markerPattern.test(key));
+    check('automation_markers', markers.length === 0,
+        markers.length ? markers.join(', ') : 'none');
+
+    check('plugins', navigator.plugins.length > 0,
+        'navigator.plugins.length = ' + navigator.plugins.length);
+
+    check('chrome_runtime', !!(window.chrome && window.chrome.runtime),
+        'window.chrome.runtime ' + (window.chrome && window.chrome.runtime ? 'present' : 'missing'));
+
+    const brands = navigator.userAgentData ? navigator.userAgentData.brands.map(b => b.brand) : [];
+    const headlessTokens = [navigator.userAgent, navigator.appVersion].concat(brands)
+        .filter(s => /headless/i.test(s));
+    check('headless_user_agent', headlessTokens.length === 0,
+        headlessTokens.length ? headlessTokens.join(' | ') : navigator.userAgent);
+
+    // OS the user agent claims; navigator.platform and the GPU must agree
+    const ua = navigator.userAgent;
+    const os = /Windows NT/.test(ua) ? 'windows' : /Macintosh/.test(ua) ? 'mac'
+        : /Linux|X11|CrOS/.test(ua) ? 'linux' : 'unknown';
+    const platformPattern = { windows: /^Win/, mac: /^Mac/, linux: /Linux/ }[os];
+    check('platform', !!platformPattern && platformPattern.test(navigator.platform),
+        'navigator.platform = ' + navigator.platform + ', user agent OS = ' + os);
+
+    let webgl = 'no WebGL context';
+    let webglOk = false;
+    let renderer = '';
+    try {
+        const gl = document.createElement('canvas').getContext('webgl');
+        if (gl) {
+            const info = gl.getExtension('WEBGL_debug_renderer_info');
+            const vendor = info ? gl.getParameter(info.UNMASKED_VENDOR_WEBGL) : gl.getParameter(gl.VENDOR);
+            renderer = String(info ? gl.getParameter(info.UNMASKED_RENDERER_WEBGL) : gl.getParameter(gl.RENDERER));
+            webgl = vendor + ' / ' + renderer;
+            webglOk = !!vendor && !!renderer && !/swiftshader|llvmpipe|software/i.test(renderer);
+        }
+    } catch (e) {
+        webgl = 'WebGL error: ' + e.message;
+    }
+    check('webgl', webglOk, webgl);
+
+    // ANGLE names its backend: Direct3D only exists on Windows, Apple GPUs
+    // and Metal only on macOS
+    const rendererOk = {
+        windows: r => /Direct3D|D3D|Vulkan/i.test(r) && !/Apple|Metal/.test(r),
+        mac: r => !/Direct3D|D3D|Vulkan/i.test(r) && /Apple|Intel|AMD|Metal|OpenGL/.test(r),
+        linux: r => !/Direct3D|D3D|Apple|Metal/i.test(r),
+    }[os];
+    check('webgl_platform', !!renderer && !!rendererOk && rendererOk(renderer),
+        (renderer || 'no renderer') + ' on ' + os);
+
+    check('languages', navigator.languages && navigator.languages.length > 0,
+        'navigator.languages = ' + JSON.stringify(navigator.languages));
+
+    return checks;
+})()
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(name: &str, passed: bool) -> DetectionCheck {
+        DetectionCheck {
+            name: name.into(),
+            passed,
+            detail: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_report_summary() {
+        let report = DetectionReport {
+            checks: vec![check("webdriver", true), check("plugins", false)],
+        };
+        assert!(!report.passed());
+        assert_eq!(
+            report
+                .failures()
+                .map(|c| c.name.as_str())
+                .collect::<Vec<_>>(),
+            ["plugins"]
+        );
+        assert!(report.check("webdriver").unwrap().passed);
+        assert!(report.to_string().contains("[FAIL] plugins"));
+    }
+}
@@ -32,6 +32,22 @@ pub enum Error {
     #[error("CDP error: {0}")]
     CdpSimple(String),
 
+    /// Node exists but has no layout box (hidden, display:none, detached)
+    #[error("Node is not visible: no box model could be computed")]
+    NodeNotVisible,
+
+    /// JavaScript evaluation threw an exception
+    #[error("JavaScript error: {text} at {line}:{column}")]
+    JavaScriptException {
+        text: String,
+        line: i32,
+        column: i32,
+    },
+
+    /// The CDP session (tab) is gone, e.g. the target was closed or crashed
+    #[error("CDP session closed")]
+    SessionClosed,
+
     /// Navigation error
     #[error("Navigation error: {0}")]
     Navigation(String),
@@ -44,6 +60,23 @@ pub enum Error {
     #[error("Element not visible: '{selector}' exists in DOM but is not rendered (hidden, display:none, or off-screen)")]
     ElementNotVisible { selector: String },
 
+    /// The browser refused a permission-gated API (clipboard, etc.)
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
+    /// Selector string could not be parsed
+    #[error("Invalid selector: {0}")]
+    InvalidSelector(String),
+
+    /// Element is in a state the requested action can't handle
+    /// (e.g. unchecking a radio button)
+    #[error("Invalid element state: {0}")]
+    InvalidState(String),
+
+    /// A file to upload is missing, not a regular file, or unreadable
+    #[error("Cannot upload '{path}': {reason}")]
+    FileNotReadable { path: String, reason: String },
+
     /// Timeout
     #[error("Timeout: {0}")]
     Timeout(String),
@@ -99,6 +132,20 @@ impl Error {
         }
     }
 
+    /// Map a CDP error response to a typed error where the failure is well known,
+    /// falling back to [`Error::Cdp`]
+    pub fn from_cdp_response(method: impl Into<String>, code: i64, message: &str) -> Self {
+        if message.contains("Could not compute box model") {
+            Self::NodeNotVisible
+        } else if message.contains("Session with given id not found")
+            || message.contains("No target with given id")
+        {
+            Self::SessionClosed
+        } else {
+            Self::cdp(method, code, message)
+        }
+    }
+
     /// Create a patching error
     pub fn patching(operation: impl Into<String>, message: impl Into<String>) -> Self {
         Self::Patching {
@@ -107,3 +154,42 @@ impl Error {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_box_model_error_maps_to_node_not_visible() {
+        let err =
+            Error::from_cdp_response("DOM.getBoxModel", -32000, "Could not compute box model.");
+        assert!(matches!(err, Error::NodeNotVisible));
+    }
+
+    #[test]
+    fn test_missing_session_maps_to_session_closed() {
+        let err = Error::from_cdp_response(
+            "Runtime.evaluate",
+            -32001,
+            "Session with given id not found.",
+        );
+        assert!(matches!(err, Error::SessionClosed));
+    }
+
+    #[test]
+    fn test_unknown_error_keeps_cdp_context() {
+        let err = Error::from_cdp_response("DOM.querySelector", -32000, "DOM Error while querying");
+        match err {
+            Error::Cdp {
+                method,
+                code,
+                message,
+            } => {
+                assert_eq!(method, "DOM.querySelector");
+                assert_eq!(code, -32000);
+                assert_eq!(message, "DOM Error while querying");
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Form Extraction
+//!
+//! A structured view of the forms on a page (fields, labels, current values)
+//! for planning fills. See [`crate::Page::forms`].
+
+use serde::Deserialize;
+
+/// A `<form>` and its fillable fields
+#[derive(Debug, Clone, Deserialize)]
+pub struct FormInfo {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    /// Absolute URL the form submits to
+    pub action: String,
+    /// `GET` or `POST` (uppercase)
+    pub method: String,
+    /// CSS selector for the form itself
+    pub selector: String,
+    pub fields: Vec<FormField>,
+}
+
+impl FormInfo {
+    /// Look up a field by `name`, falling back to `id`
+    pub fn field(&self, name: &str) -> Option<&FormField> {
+        self.fields
+            .iter()
+            .find(|f| f.name.as_deref() == Some(name))
+            .or_else(|| self.fields.iter().find(|f| f.id.as_deref() == Some(name)))
+    }
+}
+
+/// One `<input>`, `<select>` or `<textarea>` of a [`FormInfo`]; buttons are
+/// left out
+#[derive(Debug, Clone, Deserialize)]
+pub struct FormField {
+    pub name: Option<String>,
+    pub id: Option<String>,
+    /// Input type (`email`, `password`, `checkbox`, ...), or `select-one`,
+    /// `select-multiple` and `textarea`
+    #[serde(rename = "type")]
+    pub field_type: String,
+    /// From `<label>`, `aria-label`, `aria-labelledby` or the placeholder
+    pub label: Option<String>,
+    pub required: bool,
+    /// Current value
+    pub value: String,
+    /// For checkboxes and radios
+    pub checked: bool,
+    /// Choices of a `<select>`, empty otherwise
+    pub options: Vec<FormOption>,
+    /// CSS selector to fill or click this field with
+    pub selector: String,
+}
+
+/// An `<option>` of a select [`FormField`]
+#[derive(Debug, Clone, Deserialize)]
+pub struct FormOption {
+    pub value: String,
+    pub text: String,
+    pub selected: bool,
+}
+
+/// Evaluates to an array of [`FormInfo`]
+pub(crate) const FORMS_JS: &str = r#"
+(() => {
+    const text = s => (s || '').replace(/\s+/g, ' ').trim();
+
+    // Unique selector: the nearest id, then nth-of-type steps down to `el`
+    const selectorFor = el => {
+        const parts = [];
+        for (; el && el.nodeType === 1; el = el.parentElement) {
+            if (el.id) {
+                parts.unshift('#' + CSS.escape(el.id));
+                break;
+            }
+            const tag = el.tagName.toLowerCase();
+            if (!el.parentElement) {
+                parts.unshift(tag);
+                break;
+            }
+            const same = Array.from(el.parentElement.children).filter(c => c.tagName === el.tagName);
+            parts.unshift(same.length > 1 ? tag + ':nth-of-type(' + (same.indexOf(el) + 1) + ')' : tag);
+        }
+        return parts.join(' > ');
+    };
+
+    const labelFor = el => {
+        const fromLabels = text(Array.from(el.labels || []).map(l => l.textContent).join(' '));
+        if (fromLabels) return fromLabels;
+        const aria = text(el.getAttribute('aria-label'));
+        if (aria) return aria;
+        const labelledBy = el.getAttribute('aria-labelledby');
+        if (labelledBy) {
+            const byText = text(labelledBy.split(/\s+/)
+                .map(id => (document.getElementById(id) || {}).textContent || '').join(' '));
+            if (byText) return byText;
+        }
+        return text(el.getAttribute('placeholder')) || null;
+    };
+
+    // Named inputs can shadow form properties (`<input name="action">`)
+    const elementsOf = Object.getOwnPropertyDescriptor(HTMLFormElement.prototype, 'elements').get;
+
+    return Array.from(document.forms).map(form => ({
+        id: form.getAttribute('id'),
+        name: form.getAttribute('name'),
+        action: new URL(form.getAttribute('action') || '', document.baseURI).href,
+        method: (form.getAttribute('method') || 'get').toUpperCase(),
+        selector: selectorFor(form),
+        fields: Array.from(elementsOf.call(form))
+            .filter(el => /^(INPUT|SELECT|TEXTAREA)$/.test(el.tagName)
+                && !/^(submit|button|reset|image)$/.test(el.type))
+            .map(el => ({
+                name: el.getAttribute('name'),
+                id: el.getAttribute('id'),
+                type: el.type,
+                label: labelFor(el),
+                required: el.required,
+                value: el.value,
+                checked: !!el.checked,
+                options: el.tagName === 'SELECT'
+                    ? Array.from(el.options).map(o => ({ value: o.value, text: text(o.text), selected: o.selected }))
+                    : [],
+                selector: selectorFor(el),
+            })),
+    }));
+})()
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_lookup() {
+        let form: FormInfo = serde_json::from_value(serde_json::json!({
+            "id": null,
+            "name": "login",
+            "action": "https://example.com/session",
+            "method": "POST",
+            "selector": "body > form",
+            "fields": [
+                {
+                    "name": "email", "id": null, "type": "email", "label": "Email",
+                    "required": true, "value": "", "checked": false, "options": [],
+                    "selector": "body > form > input:nth-of-type(1)"
+                },
+                {
+                    "name": null, "id": "pw", "type": "password", "label": "Password",
+                    "required": true, "value": "", "checked": false, "options": [],
+                    "selector": "#pw"
+                }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(form.field("email").unwrap().field_type, "email");
+        assert_eq!(form.field("pw").unwrap().selector, "#pw");
+        assert!(form.field("missing").is_none());
+    }
+}
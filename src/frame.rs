@@ -0,0 +1,179 @@
+//! Frame Handles
+//!
+//! Scoped access to an iframe's document, including cross-origin
+//! (out-of-process) iframes that `Page::evaluate_in_frame` can't reach.
+
+use crate::cdp::Session;
+use crate::error::{Error, Result};
+use crate::page::{Element, Page};
+
+/// How commands reach the frame's document
+enum FrameContext {
+    /// Same-process frame: an isolated world in the page's own session
+    Local { context_id: i64 },
+    /// Out-of-process frame (typically cross-origin) with its own CDP session
+    Remote { session: Session },
+}
+
+/// An iframe on a page, obtained from [`Page::frame`].
+///
+/// Same-process frames are driven through an isolated world in the page's
+/// session (shares the frame's DOM, not its page-defined globals), created
+/// once per frame document and shared by every handle to it. Cross-origin
+/// frames that Chrome runs out of process are attached as their own target,
+/// detached again when the handle is dropped.
+/// The iframe's position is captured on creation; get a new handle if the
+/// page scrolls or reflows.
+pub struct Frame<'a> {
+    page: &'a Page,
+    context: FrameContext,
+    /// Top-left of the iframe's content box in page coordinates
+    origin: (f64, f64),
+}
+
+impl<'a> Frame<'a> {
+    /// Resolve the `<iframe>` matching `selector` on `page`
+    pub(crate) async fn attach(page: &'a Page, selector: &str) -> Result<Frame<'a>> {
+        let iframe = page.find(selector).await?;
+        let session = page.session();
+
+        let frame_id = session
+            .describe_node(iframe.node_id())
+            .await?
+            .frame_id
+            .ok_or_else(|| Error::ElementNotFound(format!("frame: {} is not a frame", selector)))?;
+
+        // Content box top-left: where the frame's viewport starts on the page
+        let origin = match session.get_box_model(iframe.node_id()).await {
+            Ok(model) if model.content.len() >= 2 => (model.content[0], model.content[1]),
+            _ => (0.0, 0.0),
+        };
+
+        // Out-of-process iframes show up as their own target with the frame id
+        let is_remote = session
+            .get_targets()
+            .await?
+            .iter()
+            .any(|t| t.r#type == "iframe" && t.target_id == frame_id);
+
+        let context = if is_remote {
+            let frame_session = session.attach_to_target(&frame_id).await?;
+            // DOM agent must have the document before requestNode works
+            frame_session.get_document(Some(0)).await?;
+            FrameContext::Remote {
+                session: frame_session,
+            }
+        } else {
+            FrameContext::Local {
+                context_id: page.frame_world(&frame_id).await?,
+            }
+        };
+
+        Ok(Frame {
+            page,
+            context,
+            origin,
+        })
+    }
+
+    /// Whether this frame runs out of process (its own CDP target)
+    pub fn is_out_of_process(&self) -> bool {
+        matches!(self.context, FrameContext::Remote { .. })
+    }
+
+    /// Session owning this frame's DOM nodes
+    fn session(&self) -> &Session {
+        match &self.context {
+            FrameContext::Local { .. } => self.page.session(),
+            FrameContext::Remote { session } => session,
+        }
+    }
+
+    /// Evaluate JavaScript inside the frame and return the result
+    pub async fn evaluate<T: serde::de::DeserializeOwned>(&self, expression: &str) -> Result<T> {
+        let result = match &self.context {
+            FrameContext::Local { context_id } => {
+                self.page
+                    .session()
+                    .evaluate_in_context(expression, *context_id, true)
+                    .await?
+            }
+            FrameContext::Remote { session } => session.evaluate(expression).await?,
+        };
+        let value = self
+            .page
+            .check_js_result(result)?
+            .value
+            .ok_or_else(|| Error::CdpSimple("No value returned from evaluate".into()))?;
+        serde_json::from_value(value).map_err(Into::into)
+    }
+
+    /// Find an element inside the frame by CSS selector
+    pub async fn find(&self, selector: &str) -> Result<Element<'_>> {
+        let js = format!(
+            "document.querySelector({})",
+            serde_json::to_string(selector)?
+        );
+        let result = match &self.context {
+            FrameContext::Local { context_id } => {
+                self.page
+                    .session()
+                    .evaluate_in_context(&js, *context_id, false)
+                    .await?
+            }
+            FrameContext::Remote { session } => session.evaluate_for_remote_object(&js).await?,
+        };
+        let remote = self.page.check_js_result(result)?;
+
+        let object_id = match remote.object_id {
+            Some(id) if remote.subtype.as_deref() != Some("null") => id,
+            _ => return Err(Error::ElementNotFound(selector.to_string())),
+        };
+
+        let session = self.session();
+        let node_id = session.request_node(&object_id).await?;
+        if node_id == 0 {
+            return Err(Error::ElementNotFound(selector.to_string()));
+        }
+
+        // Same-process box models are already in page coordinates
+        let origin = match self.context {
+            FrameContext::Local { .. } => (0.0, 0.0),
+            FrameContext::Remote { .. } => self.origin,
+        };
+        Ok(Element::in_session(self.page, session, node_id, origin))
+    }
+
+    /// Click an element inside the frame
+    pub async fn click(&self, selector: &str) -> Result<()> {
+        self.find(selector).await?.click().await
+    }
+
+    /// Fill a form field inside the frame: click, clear, type
+    pub async fn fill(&self, selector: &str, value: &str) -> Result<()> {
+        let element = self.find(selector).await?;
+        element.click().await?;
+        self.page.interaction_pause().await;
+        // Input goes through the page, which routes it to the focused frame
+        element.clear().await?;
+        self.page.session().insert_text(value).await
+    }
+}
+
+impl Drop for Frame<'_> {
+    fn drop(&mut self) {
+        let FrameContext::Remote { session } = &self.context else {
+            return;
+        };
+
+        // Detaching is async; without a runtime Chrome cleans up on exit
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let session = session.clone();
+            runtime.spawn(async move {
+                if let Err(e) = session.detach().await {
+                    tracing::debug!("Failed to detach frame session: {}", e);
+                }
+            });
+        }
+    }
+}
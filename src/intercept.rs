@@ -0,0 +1,146 @@
+//! Request Interception
+//!
+//! One hook for blocking, mocking and rewriting requests, built on the
+//! CDP `Fetch` domain. See [`crate::Page::intercept`].
+
+use std::collections::HashMap;
+
+use base64::Engine;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::cdp::types::{
+    FetchContinueRequest, FetchFulfillRequest, FetchRequestPausedEvent, HeaderEntry,
+};
+use crate::cdp::Session;
+use crate::error::Result;
+
+/// A request paused before being sent
+#[derive(Debug, Clone)]
+pub struct InterceptedRequest {
+    /// Fetch-domain id of the paused request
+    pub request_id: String,
+    pub url: String,
+    pub method: String,
+    pub headers: HashMap<String, String>,
+    pub post_data: Option<String>,
+    /// Resource type (Document, Script, XHR, Fetch, Image, ...)
+    pub resource_type: String,
+}
+
+/// What to do with an [`InterceptedRequest`]
+#[derive(Debug, Clone)]
+pub enum InterceptAction {
+    /// Send the request, replacing any field that is `Some`.
+    /// A new `url` is not visible to the page.
+    Continue {
+        url: Option<String>,
+        headers: Option<HashMap<String, String>>,
+        post_data: Option<String>,
+    },
+    /// Fail the request as if blocked by the client
+    Abort,
+    /// Answer without touching the network
+    Fulfill {
+        status: u16,
+        headers: HashMap<String, String>,
+        body: Vec<u8>,
+    },
+}
+
+impl Default for InterceptAction {
+    /// Continue unchanged
+    fn default() -> Self {
+        Self::Continue {
+            url: None,
+            headers: None,
+            post_data: None,
+        }
+    }
+}
+
+/// Interception callback, called once per request
+pub(crate) type InterceptHandler = Box<dyn Fn(InterceptedRequest) -> InterceptAction + Send>;
+
+/// Run `handler` for every `Fetch.requestPaused` event until the channel closes.
+///
+/// `paused` must be lossless: a dropped event leaves its request paused forever.
+pub(crate) fn spawn(
+    session: Session,
+    mut paused: mpsc::UnboundedReceiver<serde_json::Value>,
+    handler: InterceptHandler,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while let Some(params) = paused.recv().await {
+            let event: FetchRequestPausedEvent = match serde_json::from_value(params) {
+                Ok(event) => event,
+                Err(e) => {
+                    tracing::warn!("Malformed Fetch.requestPaused: {}", e);
+                    continue;
+                }
+            };
+
+            let request_id = event.request_id.clone();
+            let action = handler(InterceptedRequest {
+                request_id: event.request_id,
+                url: event.request.url,
+                method: event.request.method,
+                headers: event.request.headers,
+                post_data: event.request.post_data,
+                resource_type: event.resource_type,
+            });
+
+            if let Err(e) = resolve(&session, request_id, action).await {
+                tracing::debug!("Failed to resolve intercepted request: {}", e);
+            }
+        }
+    })
+}
+
+/// Send the CDP command matching `action`
+async fn resolve(session: &Session, request_id: String, action: InterceptAction) -> Result<()> {
+    let encode = |bytes: &[u8]| base64::engine::general_purpose::STANDARD.encode(bytes);
+
+    match action {
+        InterceptAction::Continue {
+            url,
+            headers,
+            post_data,
+        } => {
+            session
+                .fetch_continue_request(FetchContinueRequest {
+                    request_id,
+                    url,
+                    post_data: post_data.map(|data| encode(data.as_bytes())),
+                    headers: headers.map(header_entries),
+                })
+                .await
+        }
+        InterceptAction::Abort => {
+            session
+                .fetch_fail_request(&request_id, "BlockedByClient")
+                .await
+        }
+        InterceptAction::Fulfill {
+            status,
+            headers,
+            body,
+        } => {
+            session
+                .fetch_fulfill_request(FetchFulfillRequest {
+                    request_id,
+                    response_code: status,
+                    response_headers: Some(header_entries(headers)),
+                    body: Some(encode(&body)),
+                })
+                .await
+        }
+    }
+}
+
+fn header_entries(headers: HashMap<String, String>) -> Vec<HeaderEntry> {
+    headers
+        .into_iter()
+        .map(|(name, value)| HeaderEntry { name, value })
+        .collect()
+}
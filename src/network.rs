@@ -40,10 +40,17 @@ pub enum NetworkEvent {
     },
 }
 
+/// Capture filter, called once per new request
+type RequestFilter = Box<dyn Fn(&CapturedRequest) -> bool + Send + Sync>;
+
 /// Watches network events and provides a stream of captured requests
 pub struct NetworkWatcher {
     /// In-flight requests (request_id -> CapturedRequest)
     requests: Arc<Mutex<HashMap<String, CapturedRequest>>>,
+    /// Requests it rejects are neither stored nor streamed
+    filter: Mutex<Option<RequestFilter>>,
+    /// Max stored requests; the oldest completed ones are evicted beyond it
+    capacity: Option<usize>,
     /// Channel to send events to consumers
     event_tx: mpsc::Sender<NetworkEvent>,
     /// Channel to receive events
@@ -56,11 +63,23 @@ impl NetworkWatcher {
         let (event_tx, event_rx) = mpsc::channel(256);
         Self {
             requests: Arc::new(Mutex::new(HashMap::new())),
+            filter: Mutex::new(None),
+            capacity: None,
             event_tx,
             event_rx: Mutex::new(event_rx),
         }
     }
 
+    /// Create a NetworkWatcher storing at most `capacity` requests. Beyond
+    /// that the oldest completed requests are evicted; in-flight ones are
+    /// kept even if that means going over.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: Some(capacity),
+            ..Self::new()
+        }
+    }
+
     /// Process a CDP event
     /// Returns true if the event was a network event that was processed
     pub async fn process_event(&self, event: &CdpMessage) -> bool {
@@ -105,25 +124,18 @@ impl NetworkWatcher {
     }
 
     async fn on_request_will_be_sent(&self, event: NetworkRequestWillBeSentEvent) {
-        let request = CapturedRequest {
-            request_id: event.request_id.clone(),
-            url: event.request.url.clone(),
-            method: event.request.method.clone(),
-            headers: event.request.headers.clone(),
-            post_data: event.request.post_data.clone(),
-            resource_type: event.r#type.clone(),
-            status: None,
-            status_text: None,
-            response_headers: None,
-            mime_type: None,
-            timestamp: event.timestamp,
-            complete: false,
-        };
+        let request = CapturedRequest::from(event);
+        if let Some(filter) = self.filter.lock().await.as_ref() {
+            if !filter(&request) {
+                return;
+            }
+        }
 
         // Store the request
         {
             let mut requests = self.requests.lock().await;
-            requests.insert(event.request_id.clone(), request.clone());
+            requests.insert(request.request_id.clone(), request.clone());
+            self.evict(&mut requests);
         }
 
         // Send event
@@ -142,6 +154,8 @@ impl NetworkWatcher {
                 req.status_text = Some(event.response.status_text.clone());
                 req.response_headers = Some(event.response.headers.clone());
                 req.mime_type = event.response.mime_type.clone();
+            } else if self.filter.lock().await.is_some() {
+                return;
             }
         }
 
@@ -164,7 +178,10 @@ impl NetworkWatcher {
             let mut requests = self.requests.lock().await;
             if let Some(req) = requests.get_mut(&event.request_id) {
                 req.complete = true;
+            } else if self.filter.lock().await.is_some() {
+                return;
             }
+            self.evict(&mut requests);
         }
 
         // Send event
@@ -181,7 +198,9 @@ impl NetworkWatcher {
         // Remove failed request
         {
             let mut requests = self.requests.lock().await;
-            requests.remove(&event.request_id);
+            if requests.remove(&event.request_id).is_none() && self.filter.lock().await.is_some() {
+                return;
+            }
         }
 
         // Send event
@@ -195,6 +214,24 @@ impl NetworkWatcher {
             .await;
     }
 
+    /// Drop the oldest completed requests until within capacity
+    fn evict(&self, requests: &mut HashMap<String, CapturedRequest>) {
+        let Some(capacity) = self.capacity else {
+            return;
+        };
+        while requests.len() > capacity {
+            let oldest = requests
+                .values()
+                .filter(|r| r.complete)
+                .min_by(|a, b| a.timestamp.total_cmp(&b.timestamp))
+                .map(|r| r.request_id.clone());
+            match oldest {
+                Some(id) => requests.remove(&id),
+                None => break,
+            };
+        }
+    }
+
     /// Receive the next network event
     pub async fn recv(&self) -> Option<NetworkEvent> {
         let mut rx = self.event_rx.lock().await;
@@ -219,6 +256,38 @@ impl NetworkWatcher {
         requests.values().cloned().collect()
     }
 
+    /// Get captured requests of one resource type (`XHR`, `Fetch`, `Image`, ...)
+    pub async fn get_requests_by_type(&self, resource_type: &str) -> Vec<CapturedRequest> {
+        let requests = self.requests.lock().await;
+        requests
+            .values()
+            .filter(|r| r.resource_type.as_deref() == Some(resource_type))
+            .cloned()
+            .collect()
+    }
+
+    /// Only capture requests `filter` accepts; the rest are neither stored
+    /// nor streamed, e.g. to skip images and fonts:
+    ///
+    /// ```rust,no_run
+    /// # async fn example(watcher: &eoka::NetworkWatcher) {
+    /// watcher
+    ///     .set_filter(|r| matches!(r.resource_type.as_deref(), Some("XHR" | "Fetch")))
+    ///     .await;
+    /// # }
+    /// ```
+    pub async fn set_filter<F>(&self, filter: F)
+    where
+        F: Fn(&CapturedRequest) -> bool + Send + Sync + 'static,
+    {
+        *self.filter.lock().await = Some(Box::new(filter));
+    }
+
+    /// Capture every request again
+    pub async fn clear_filter(&self) {
+        *self.filter.lock().await = None;
+    }
+
     /// Clear all captured requests
     pub async fn clear(&self) {
         let mut requests = self.requests.lock().await;
@@ -226,6 +295,26 @@ impl NetworkWatcher {
     }
 }
 
+impl From<NetworkRequestWillBeSentEvent> for CapturedRequest {
+    /// A request as it is sent, before any response
+    fn from(event: NetworkRequestWillBeSentEvent) -> Self {
+        Self {
+            request_id: event.request_id,
+            url: event.request.url,
+            method: event.request.method,
+            headers: event.request.headers,
+            post_data: event.request.post_data,
+            resource_type: event.r#type,
+            status: None,
+            status_text: None,
+            response_headers: None,
+            mime_type: None,
+            timestamp: event.timestamp,
+            complete: false,
+        }
+    }
+}
+
 impl Default for NetworkWatcher {
     fn default() -> Self {
         Self::new()
@@ -241,4 +330,90 @@ mod tests {
         let watcher = NetworkWatcher::new();
         assert!(watcher.get_all_requests().await.is_empty());
     }
+
+    fn request_sent(id: &str, resource_type: &str) -> CdpMessage {
+        CdpMessage::Event {
+            method: "Network.requestWillBeSent".into(),
+            params: serde_json::json!({
+                "requestId": id,
+                "request": { "url": format!("https://example.com/{}", id), "method": "GET" },
+                "timestamp": id.parse::<f64>().unwrap(),
+                "type": resource_type,
+            }),
+            session_id: None,
+        }
+    }
+
+    fn loading_finished(id: &str) -> CdpMessage {
+        CdpMessage::Event {
+            method: "Network.loadingFinished".into(),
+            params: serde_json::json!({
+                "requestId": id,
+                "timestamp": 10.0,
+                "encodedDataLength": 100,
+            }),
+            session_id: None,
+        }
+    }
+
+    async fn stored_ids(watcher: &NetworkWatcher) -> Vec<String> {
+        let mut ids: Vec<_> = watcher
+            .get_all_requests()
+            .await
+            .into_iter()
+            .map(|r| r.request_id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[tokio::test]
+    async fn test_filter_by_resource_type() {
+        let watcher = NetworkWatcher::new();
+        watcher
+            .set_filter(|r| matches!(r.resource_type.as_deref(), Some("XHR" | "Fetch")))
+            .await;
+
+        for (id, resource_type) in [("1", "Image"), ("2", "XHR"), ("3", "Font"), ("4", "Fetch")] {
+            assert!(
+                watcher
+                    .process_event(&request_sent(id, resource_type))
+                    .await
+            );
+        }
+
+        assert_eq!(stored_ids(&watcher).await, ["2", "4"]);
+        assert_eq!(watcher.get_requests_by_type("XHR").await.len(), 1);
+        assert!(watcher.get_requests_by_type("Image").await.is_empty());
+
+        // Only the kept requests were streamed
+        let mut streamed = Vec::new();
+        while let Some(NetworkEvent::RequestStarted(r)) = watcher.try_recv().await {
+            streamed.push(r.request_id);
+        }
+        assert_eq!(streamed, ["2", "4"]);
+
+        watcher.clear_filter().await;
+        watcher.process_event(&request_sent("5", "Image")).await;
+        assert_eq!(watcher.get_requests_by_type("Image").await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_capacity_evicts_oldest_completed() {
+        let watcher = NetworkWatcher::with_capacity(2);
+        for id in ["1", "2"] {
+            watcher.process_event(&request_sent(id, "XHR")).await;
+            watcher.process_event(&loading_finished(id)).await;
+        }
+        watcher.process_event(&request_sent("3", "XHR")).await;
+        watcher.process_event(&request_sent("4", "XHR")).await;
+        assert_eq!(stored_ids(&watcher).await, ["3", "4"]);
+
+        // Only in-flight requests left: go over capacity rather than drop one
+        watcher.process_event(&request_sent("5", "XHR")).await;
+        assert_eq!(stored_ids(&watcher).await, ["3", "4", "5"]);
+
+        watcher.process_event(&loading_finished("3")).await;
+        assert_eq!(stored_ids(&watcher).await, ["4", "5"]);
+    }
 }
@@ -3,13 +3,35 @@
 //! High-level API for interacting with a browser page.
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-use crate::cdp::{Cookie, MouseButton, MouseEventType, Session};
+use crate::cdp::{
+    Cookie, MouseButton, MouseEventType, NetworkSetCookie, PagePrintToPdf, PerformanceMetric,
+    ScreenshotClip, Session,
+};
 use crate::error::{Error, Result};
+use crate::form::{FormInfo, FORMS_JS};
+use crate::frame::Frame;
+use crate::intercept::{self, InterceptAction, InterceptedRequest};
+use crate::selector::Selector;
+use crate::stealth::evasions::{accept_language, languages_script, locale_languages};
 use crate::stealth::Human;
 use crate::StealthConfig;
 
+/// Default time `hover` keeps the pointer on the element
+const HOVER_DWELL_MS: u64 = 100;
+
+/// Subscriptions to `Page.loadEventFired` and `Page.frameStoppedLoading`,
+/// with the main frame as it was when they were taken
+struct LoadEvents {
+    loaded: tokio::sync::broadcast::Receiver<serde_json::Value>,
+    stopped: tokio::sync::broadcast::Receiver<serde_json::Value>,
+    main_frame: String,
+    /// Loader id of the document that was current; it finishing doesn't count
+    previous_loader: String,
+}
+
 /// Escape a string for safe use in JavaScript string literals
 fn escape_js_string(s: &str) -> String {
     s.replace('\\', "\\\\")
@@ -24,16 +46,22 @@ fn escape_js_string(s: &str) -> String {
 /// Check if a CDP error is an element-related error (not found, not visible, etc.)
 fn is_element_cdp_error(e: &Error) -> bool {
     match e {
-        Error::ElementNotFound(_) | Error::ElementNotVisible { .. } => true,
+        Error::ElementNotFound(_) | Error::ElementNotVisible { .. } | Error::NodeNotVisible => true,
         Error::Cdp { message, .. } => {
-            message.contains("box model")
-                || message.contains("Could not find node")
+            message.contains("Could not find node")
                 || message.contains("No node with given id")
                 || message.contains("Node is not an element")
         }
         _ => false,
     }
 }
+
+/// Check if a CDP error means a node id belongs to a replaced document
+fn is_stale_node_error(e: &Error) -> bool {
+    matches!(e, Error::Cdp { message, .. }
+        if message.contains("Could not find node") || message.contains("No node with given id"))
+}
+
 /// Text matching strategy for find_by_text operations
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum TextMatch {
@@ -48,16 +76,136 @@ pub enum TextMatch {
     EndsWith,
 }
 
+/// Fine-grained text comparison options for [`Page::find_by_text_with`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextMatchOptions {
+    /// Compare case-sensitively
+    pub case_sensitive: bool,
+    /// Collapse runs of whitespace (including newlines) into a single space
+    pub normalize_whitespace: bool,
+    /// Trim leading/trailing whitespace
+    pub trim: bool,
+}
+
+impl TextMatchOptions {
+    /// The options [`Page::find_by_text_match`] applies for a strategy:
+    /// trimmed and case-sensitive for `Exact`, case-insensitive otherwise
+    pub fn for_match(match_type: TextMatch) -> Self {
+        match match_type {
+            TextMatch::Exact => Self {
+                case_sensitive: true,
+                trim: true,
+                ..Default::default()
+            },
+            _ => Self::default(),
+        }
+    }
+}
+
+/// Build the JS boolean expression comparing the element text `t` to `text`
+pub(crate) fn text_match_js(
+    text: &str,
+    match_type: TextMatch,
+    options: TextMatchOptions,
+) -> Result<String> {
+    // Apply the same transforms to both sides, in JS, so case folding and
+    // escaping can't disagree
+    let transform = |expr: String| {
+        let mut expr = expr;
+        if options.normalize_whitespace {
+            expr = format!("{}.replace(/\\s+/g, ' ')", expr);
+        }
+        if options.trim {
+            expr = format!("{}.trim()", expr);
+        }
+        if !options.case_sensitive {
+            expr = format!("{}.toLowerCase()", expr);
+        }
+        expr
+    };
+    let haystack = transform("t".to_string());
+    let needle = transform(serde_json::to_string(text)?);
+
+    Ok(match match_type {
+        TextMatch::Exact => format!("{} === {}", haystack, needle),
+        TextMatch::Contains => format!("{}.includes({})", haystack, needle),
+        TextMatch::StartsWith => format!("{}.startsWith({})", haystack, needle),
+        TextMatch::EndsWith => format!("{}.endsWith({})", haystack, needle),
+    })
+}
+
 /// A browser page with stealth capabilities
 pub struct Page {
     session: Session,
     config: Arc<StealthConfig>,
+    /// Cached document root node id with the document epoch it was fetched in
+    root_node: std::sync::Mutex<Option<(u64, i32)>>,
+    /// Task answering `Fetch.requestPaused` for `intercept`
+    interceptor: std::sync::Mutex<Option<tokio::task::JoinHandle<()>>>,
+    /// Last pointer position dispatched, so moves continue from where the mouse is
+    mouse: std::sync::Mutex<Option<(f64, f64)>>,
+    /// Button pressed with `mouse_down` and not yet released
+    held_button: std::sync::Mutex<Option<MouseButton>>,
+    /// Whether `warm_up` has run (auto warm-up happens once per page)
+    warmed_up: AtomicBool,
+    /// Headers from `set_headers`, sent together with the `set_locale` one
+    /// because `Network.setExtraHTTPHeaders` replaces the whole set
+    extra_headers: std::sync::Mutex<HashMap<String, String>>,
+    /// `Accept-Language` from `set_locale`
+    accept_language: std::sync::Mutex<Option<String>>,
+    /// Identifier of the evasion init script, `None` once disabled
+    evasion_script_id: std::sync::Mutex<Option<String>>,
+    /// Identifier of the `set_locale` init script, replaced on each call
+    locale_script_id: std::sync::Mutex<Option<String>>,
+    /// Isolated worlds made for same-process [`Frame`] handles: frame id to
+    /// the loader id of the document the world lives in and its context id
+    frame_worlds: std::sync::Mutex<HashMap<String, (String, i64)>>,
+    /// Behavior and directory from `set_download_behavior`, restored after
+    /// `download_and_read`; `None` means Chrome's default
+    download_behavior: std::sync::Mutex<Option<(String, Option<String>)>>,
 }
 
 impl Page {
     /// Create a new Page wrapping a CDP session
-    pub(crate) fn new(session: Session, config: Arc<StealthConfig>) -> Self {
-        Self { session, config }
+    pub(crate) fn new(
+        session: Session,
+        config: Arc<StealthConfig>,
+        evasion_script_id: String,
+    ) -> Self {
+        Self {
+            session,
+            config,
+            root_node: std::sync::Mutex::new(None),
+            interceptor: std::sync::Mutex::new(None),
+            mouse: std::sync::Mutex::new(None),
+            held_button: std::sync::Mutex::new(None),
+            warmed_up: AtomicBool::new(false),
+            extra_headers: std::sync::Mutex::new(HashMap::new()),
+            accept_language: std::sync::Mutex::new(None),
+            evasion_script_id: std::sync::Mutex::new(Some(evasion_script_id)),
+            locale_script_id: std::sync::Mutex::new(None),
+            frame_worlds: std::sync::Mutex::new(HashMap::new()),
+            download_behavior: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Get the document root node id, fetching it only when the cached one
+    /// predates the latest navigation
+    async fn root_node_id(&self) -> Result<i32> {
+        let epoch = self.session.document_epoch();
+        if let Some((cached_epoch, node_id)) = *self.root_node.lock().unwrap() {
+            if cached_epoch == epoch {
+                return Ok(node_id);
+            }
+        }
+        let doc = self.session.get_document(Some(0)).await?;
+        *self.root_node.lock().unwrap() = Some((epoch, doc.node_id));
+        Ok(doc.node_id)
+    }
+
+    /// Drop the cached root node id (the document is about to be replaced)
+    fn invalidate_root_node(&self) {
+        *self.root_node.lock().unwrap() = None;
     }
 
     /// Get the underlying CDP session
@@ -70,32 +218,312 @@ impl Page {
         self.session.target_id()
     }
 
+    /// Make this page the foreground tab
+    ///
+    /// Background tabs throttle timers and report `document.hidden`; call this
+    /// when switching between several pages.
+    pub async fn bring_to_front(&self) -> Result<()> {
+        self.session.bring_to_front().await
+    }
+
     /// Navigate to a URL
     pub async fn goto(&self, url: &str) -> Result<()> {
+        self.invalidate_root_node();
         let result = self.session.navigate(url).await?;
         if let Some(error) = result.error_text {
             return Err(Error::Navigation(error));
         }
-        // Brief settle time for navigation to start. Use wait_for_navigation()
-        // for reliable completion detection.
-        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        self.wait_for_document_ready(result.loader_id.as_deref())
+            .await;
+        Ok(())
+    }
+
+    /// Navigate to a URL and wait until the page has fully loaded
+    /// (the load event, i.e. `document.readyState === 'complete'`).
+    ///
+    /// Unlike `goto`, which returns once the DOM is ready, this also waits for
+    /// images, stylesheets and scripts. Fails with `Error::Timeout` if the
+    /// load doesn't finish within `timeout_ms`.
+    pub async fn goto_and_wait(&self, url: &str, timeout_ms: u64) -> Result<()> {
+        let start = std::time::Instant::now();
+        let load = self.load_events().await?;
+
+        self.invalidate_root_node();
+        let result = self.session.navigate(url).await?;
+        if let Some(error) = result.error_text {
+            return Err(Error::Navigation(error));
+        }
+        // Same-document navigations (e.g. `#anchor`) have no loader and no load event
+        if result.loader_id.is_none() {
+            return Ok(());
+        }
+
+        let remaining = timeout_ms.saturating_sub(start.elapsed().as_millis() as u64);
+        self.await_load(load, remaining).await
+    }
+
+    /// Wait for the next main-frame load to finish, e.g. after a click that
+    /// navigates. Call it right after triggering the navigation: a load that
+    /// already completed is not seen.
+    ///
+    /// Listens for `Page.loadEventFired`/`Page.frameStoppedLoading` rather than
+    /// polling `document.readyState`, which can still report the old document.
+    /// Fails with `Error::Timeout` if no load finishes within `timeout_ms`.
+    pub async fn wait_for_navigation(&self, timeout_ms: u64) -> Result<()> {
+        let load = self.load_events().await?;
+        self.await_load(load, timeout_ms).await
+    }
+
+    /// Run `action` (e.g. a click) and wait until it has navigated the main
+    /// frame to a new document, then for that document's DOM to be ready:
+    ///
+    /// ```rust,no_run
+    /// # async fn example(page: &eoka::Page) -> eoka::Result<()> {
+    /// page.expect_navigation(page.click("a.next"), 10_000).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Listens before the action runs, so fast navigations aren't missed, and
+    /// compares loader ids, so neither the old document nor a same-document
+    /// navigation (`#hash`, `pushState`) counts. Fails with `Error::Timeout`
+    /// if no navigation starts within `timeout_ms`.
+    pub async fn expect_navigation<F>(&self, action: F, timeout_ms: u64) -> Result<()>
+    where
+        F: std::future::Future<Output = Result<()>>,
+    {
+        let previous = self.session.get_frame_tree().await?.frame.loader_id;
+        let mut navigated = self.session.on("Page.frameNavigated");
+        action.await?;
+
+        let new_document = async {
+            loop {
+                let params = recv_event(&mut navigated).await?;
+                let frame = &params["frame"];
+                if frame.get("parentId").is_none()
+                    && frame["loaderId"].as_str() != Some(previous.as_str())
+                {
+                    return Ok(());
+                }
+            }
+        };
+        let timeout = std::time::Duration::from_millis(timeout_ms);
+        let result: Result<()> = match tokio::time::timeout(timeout, new_document).await {
+            Ok(result) => result,
+            Err(_) => Err(Error::Timeout(format!(
+                "No navigation within {}ms",
+                timeout_ms
+            ))),
+        };
+        self.invalidate_root_node();
+        result?;
+
+        self.wait_for_document_ready(None).await;
         Ok(())
     }
 
-    /// Reload the page
+    /// Subscribe to the events that mark the end of a main-frame load
+    async fn load_events(&self) -> Result<LoadEvents> {
+        let loaded = self.session.on("Page.loadEventFired");
+        let stopped = self.session.on("Page.frameStoppedLoading");
+        let frame = self.session.get_frame_tree().await?.frame;
+        Ok(LoadEvents {
+            loaded,
+            stopped,
+            main_frame: frame.id,
+            previous_loader: frame.loader_id,
+        })
+    }
+
+    /// Wait for one of the `load_events` for the main frame, up to `timeout_ms`
+    async fn await_load(&self, load: LoadEvents, timeout_ms: u64) -> Result<()> {
+        use tokio::sync::broadcast::error::RecvError;
+
+        let LoadEvents {
+            mut loaded,
+            mut stopped,
+            main_frame,
+            previous_loader,
+        } = load;
+        let finished = async {
+            loop {
+                let event = tokio::select! {
+                    event = loaded.recv() => event.map(|_| true),
+                    event = stopped.recv() => event.map(|params| {
+                        params["frameId"].as_str() == Some(main_frame.as_str())
+                    }),
+                };
+                match event {
+                    Ok(true) => return Ok(()),
+                    Ok(false) => {}
+                    // The load may be among the dropped events
+                    Err(RecvError::Lagged(_)) => {
+                        if self.new_document_complete(&previous_loader).await {
+                            return Ok(());
+                        }
+                    }
+                    Err(RecvError::Closed) => return Err(Error::SessionClosed),
+                }
+            }
+        };
+        let timeout = std::time::Duration::from_millis(timeout_ms);
+        let result = tokio::time::timeout(timeout, finished).await;
+        self.invalidate_root_node();
+
+        match result {
+            Ok(result) => result,
+            // The events can lag or be dropped; a new, loaded document is the
+            // final word. The old one being complete means nothing happened.
+            Err(_) if self.new_document_complete(&previous_loader).await => Ok(()),
+            Err(_) => Err(Error::Timeout(format!(
+                "Navigation did not finish loading within {}ms",
+                timeout_ms
+            ))),
+        }
+    }
+
+    /// Whether the main frame has replaced the document loaded by
+    /// `previous_loader` and the new one's `readyState` is `complete`
+    async fn new_document_complete(&self, previous_loader: &str) -> bool {
+        let replaced = self
+            .session
+            .get_frame_tree()
+            .await
+            .is_ok_and(|tree| tree.frame.loader_id != previous_loader);
+        if !replaced {
+            return false;
+        }
+        let state: Result<String> = self.evaluate_sync("document.readyState").await;
+        matches!(state.as_deref(), Ok("complete"))
+    }
+
+    /// Best-effort wait for the current document to leave the `loading` state,
+    /// bounded by `navigation_wait_ms`. Never fails: callers that need a specific
+    /// element should follow up with `wait_for(selector, timeout)`.
+    ///
+    /// Pass the `loader_id` from `Page.navigate` so the old document, still in
+    /// place until the new one commits, isn't mistaken for the new one.
+    pub(crate) async fn wait_for_document_ready(&self, loader_id: Option<&str>) {
+        let timeout = std::time::Duration::from_millis(self.config.navigation_wait_ms);
+        let start = std::time::Instant::now();
+        loop {
+            let committed = match loader_id {
+                Some(id) => self
+                    .session
+                    .get_frame_tree()
+                    .await
+                    .is_ok_and(|tree| tree.frame.loader_id == id),
+                None => true,
+            };
+            if committed {
+                let state: Result<String> = self.evaluate_sync("document.readyState").await;
+                if matches!(state.as_deref(), Ok("interactive") | Ok("complete")) {
+                    return;
+                }
+            }
+            if start.elapsed() >= timeout {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+    }
+
+    /// Pause between low-level input steps (`interaction_delay_ms`)
+    pub(crate) async fn interaction_pause(&self) {
+        if self.config.interaction_delay_ms > 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(
+                self.config.interaction_delay_ms,
+            ))
+            .await;
+        }
+    }
+
+    /// Reload the page without waiting for it to load
     pub async fn reload(&self) -> Result<()> {
+        self.invalidate_root_node();
         self.session.reload(false).await
     }
 
-    /// Go back in history
-    pub async fn back(&self) -> Result<()> {
-        self.session.go_back().await
+    /// Reload bypassing the cache, so every resource is fetched again
+    /// (like Ctrl+Shift+R). Doesn't wait for the load.
+    pub async fn hard_reload(&self) -> Result<()> {
+        self.invalidate_root_node();
+        self.session.reload(true).await
+    }
+
+    /// Reload and wait up to `timeout_ms` for the load to finish, like
+    /// [`Page::goto_and_wait`]
+    pub async fn reload_and_wait(&self, timeout_ms: u64) -> Result<()> {
+        let load = self.load_events().await?;
+        self.invalidate_root_node();
+        self.session.reload(false).await?;
+        self.await_load(load, timeout_ms).await
     }
 
-    /// Go forward in history
-    pub async fn forward(&self) -> Result<()> {
-        self.session.go_forward().await
+    /// Go back in history and wait for the navigation to settle.
+    /// Returns `Ok(false)` without navigating if already at the first entry.
+    pub async fn back(&self) -> Result<bool> {
+        self.history_step(false).await
     }
+
+    /// Go forward in history and wait for the navigation to settle.
+    /// Returns `Ok(false)` without navigating if already at the last entry.
+    pub async fn forward(&self) -> Result<bool> {
+        self.history_step(true).await
+    }
+
+    /// Move one history entry and wait for the main frame to navigate, so a
+    /// following `url()`/`find()` sees the new page. Fails with
+    /// `Error::Timeout` if it doesn't within `navigation_wait_ms`.
+    async fn history_step(&self, forward: bool) -> Result<bool> {
+        self.invalidate_root_node();
+        let main_frame = self.session.get_frame_tree().await?.frame.id;
+
+        // Subscribe before navigating so the event can't be missed
+        let mut navigated = self.session.on("Page.frameNavigated");
+        let mut within_document = self.session.on("Page.navigatedWithinDocument");
+
+        let moved = if forward {
+            self.session.go_forward().await?
+        } else {
+            self.session.go_back().await?
+        };
+        if !moved {
+            return Ok(false);
+        }
+
+        let settled = async {
+            loop {
+                tokio::select! {
+                    params = recv_event(&mut navigated) => {
+                        if params?["frame"].get("parentId").is_none() {
+                            return Ok(());
+                        }
+                    }
+                    params = recv_event(&mut within_document) => {
+                        if params?["frameId"].as_str() == Some(main_frame.as_str()) {
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+        };
+        let timeout_ms = self.config.navigation_wait_ms;
+        let timeout = std::time::Duration::from_millis(timeout_ms);
+        let result: Result<()> = match tokio::time::timeout(timeout, settled).await {
+            Ok(result) => result,
+            Err(_) => Err(Error::Timeout(format!(
+                "History navigation did not happen within {}ms",
+                timeout_ms
+            ))),
+        };
+        self.invalidate_root_node();
+        result?;
+
+        self.wait_for_document_ready(None).await;
+        Ok(true)
+    }
+
     /// Get current URL
     pub async fn url(&self) -> Result<String> {
         let frame_tree = self.session.get_frame_tree().await?;
@@ -107,16 +535,146 @@ impl Page {
         self.evaluate("document.title || ''").await
     }
 
-    /// Get page HTML content
+    /// Get page HTML content.
+    ///
+    /// The whole document comes back in one CDP message. For documents past
+    /// about 1M characters, prefer [`Page::content_to_writer`].
     pub async fn content(&self) -> Result<String> {
         self.evaluate("document.documentElement.outerHTML").await
     }
 
+    /// Stream the page HTML into `writer` in segments of 1M characters,
+    /// returning the number of bytes written.
+    ///
+    /// The HTML is snapshotted once, so the result is consistent even if
+    /// the page keeps changing while the chunks are fetched.
+    pub async fn content_to_writer<W: std::io::Write>(&self, mut writer: W) -> Result<usize> {
+        let snapshot = self.check_js_result(
+            self.session
+                .evaluate_for_remote_object("new String(document.documentElement.outerHTML)")
+                .await?,
+        )?;
+        let object_id = snapshot
+            .object_id
+            .ok_or_else(|| Error::CdpSimple("No object_id for content snapshot".into()))?;
+
+        let result = self.write_chunks(&object_id, &mut writer).await;
+        // Free the snapshot even when a chunk or the writer failed
+        if let Err(e) = self.session.release_object(&object_id).await {
+            tracing::debug!("Failed to release content snapshot: {}", e);
+        }
+        result
+    }
+
+    async fn write_chunks<W: std::io::Write>(
+        &self,
+        object_id: &str,
+        writer: &mut W,
+    ) -> Result<usize> {
+        let mut start = 0usize;
+        let mut written = 0usize;
+        loop {
+            let result = self.check_js_result(
+                self.session
+                    .call_function_on_with_args(
+                        object_id,
+                        CONTENT_CHUNK_JS,
+                        &[start.into(), CONTENT_CHUNK_CHARS.into()],
+                    )
+                    .await?,
+            )?;
+            let (chunk, next): (String, usize) =
+                serde_json::from_value(result.value.unwrap_or_default())?;
+
+            writer.write_all(chunk.as_bytes())?;
+            written += chunk.len();
+            if chunk.is_empty() || next <= start {
+                break;
+            }
+            start = next;
+        }
+        writer.flush()?;
+        Ok(written)
+    }
+
     /// Get page text content (body innerText)
     pub async fn text(&self) -> Result<String> {
         self.evaluate("document.body?.innerText || ''").await
     }
+
+    /// The page's main content as reader-friendly, Markdown-flavoured text:
+    /// `#` headings, `-`/`1.` list items, `>` quotes, paragraphs separated
+    /// by blank lines. Navigation, headers, footers, sidebars, forms, scripts,
+    /// SVG and hidden elements are left out; `<main>` (or a lone `<article>`)
+    /// is used as the root when present. A form wrapping most of the page
+    /// (as ASP.NET WebForms does) is read rather than skipped.
+    pub async fn readable_text(&self) -> Result<String> {
+        self.evaluate(READABLE_TEXT_JS).await
+    }
+
+    /// Resize the viewport after launch (e.g. 375x812 for responsive checks).
+    ///
+    /// The emulated screen is grown if needed so it still contains the window,
+    /// keeping the `outerWidth`/`outerHeight` vs `screen` relations consistent.
+    pub async fn set_viewport(
+        &self,
+        width: u32,
+        height: u32,
+        device_scale_factor: f64,
+    ) -> Result<()> {
+        use crate::cdp::types::EmulationSetDeviceMetricsOverride;
+
+        self.session
+            .set_device_metrics_override(EmulationSetDeviceMetricsOverride {
+                width,
+                height,
+                device_scale_factor,
+                mobile: false,
+                screen_width: Some(width.max(self.config.viewport_width)),
+                // Room for the browser chrome (85px) and taskbar (40px) the
+                // headless evasion reports
+                screen_height: Some((height + 125).max(self.config.viewport_height)),
+            })
+            .await
+    }
+
+    /// Pinch-zoom the page by `factor` (`1.0` restores it) via
+    /// `Emulation.setPageScaleFactor`; `window.visualViewport.scale` reports
+    /// it. Layout is unchanged. Clicks and element screenshots account for
+    /// the zoom.
+    ///
+    /// Chrome clamps the factor to the page's scale limits, so desktop pages
+    /// don't zoom out below `1.0`; to fit more into a screenshot, use a
+    /// larger [`Page::set_viewport`] instead.
+    pub async fn set_zoom(&self, factor: f64) -> Result<()> {
+        if !(factor.is_finite() && factor > 0.0) {
+            return Err(Error::InvalidState(format!(
+                "zoom factor must be positive, got {}",
+                factor
+            )));
+        }
+        self.session.set_page_scale_factor(factor).await
+    }
+
+    /// Restore the launch viewport after `set_viewport`
+    pub async fn clear_viewport_override(&self) -> Result<()> {
+        self.session.clear_device_metrics_override().await
+    }
+
+    /// Keep the page focused (`true`) even when its window isn't, so focus
+    /// and blur events, `:focus` styles and caret behave as in the foreground.
+    /// `false` restores real window focus.
+    ///
+    /// With `StealthConfig::visibility_spoof`, `document.hasFocus()` reports
+    /// `true` either way.
+    pub async fn set_window_focus(&self, focused: bool) -> Result<()> {
+        self.session.set_focus_emulation_enabled(focused).await
+    }
+
     /// Capture a screenshot as PNG bytes
+    ///
+    /// The image has device pixels: `devicePixelRatio` times the CSS size of
+    /// the viewport (e.g. twice as wide after `set_viewport(.., 2.0)`).
     pub async fn screenshot(&self) -> Result<Vec<u8>> {
         self.session.capture_screenshot(Some("png"), None).await
     }
@@ -127,36 +685,106 @@ impl Page {
             .capture_screenshot(Some("jpeg"), Some(quality))
             .await
     }
+
+    /// Capture just the element matching `selector` as PNG bytes
+    pub async fn screenshot_element(&self, selector: &str) -> Result<Vec<u8>> {
+        self.find(selector).await?.screenshot().await
+    }
+
+    /// Print the page to PDF (default paper size, backgrounds included)
+    pub async fn pdf(&self) -> Result<Vec<u8>> {
+        self.session
+            .print_to_pdf(PagePrintToPdf {
+                print_background: Some(true),
+                ..Default::default()
+            })
+            .await
+    }
+
+    /// Print only the element matching `selector` to a one-page PDF sized to it
+    ///
+    /// Everything else is hidden with a temporary print stylesheet, removed
+    /// again afterwards.
+    pub async fn pdf_element(&self, selector: &str) -> Result<Vec<u8>> {
+        const CSS_PX_PER_INCH: f64 = 96.0;
+
+        let element = self.find(selector).await?;
+        let result = async {
+            let size = element.eval_on_element(PDF_ISOLATE_JS).await?;
+            let (width, height) = match (size[0].as_f64(), size[1].as_f64()) {
+                (Some(w), Some(h)) if w > 0.0 && h > 0.0 => (w, h),
+                _ => return Err(Error::NodeNotVisible),
+            };
+
+            self.session
+                .print_to_pdf(PagePrintToPdf {
+                    print_background: Some(true),
+                    paper_width: Some(width / CSS_PX_PER_INCH),
+                    paper_height: Some(height / CSS_PX_PER_INCH),
+                    margin_top: Some(0.0),
+                    margin_bottom: Some(0.0),
+                    margin_left: Some(0.0),
+                    margin_right: Some(0.0),
+                    page_ranges: Some("1".into()),
+                })
+                .await
+        }
+        .await;
+        // Undo the isolation on every path, including the zero-size one
+        if let Err(e) = self.execute(PDF_RESTORE_JS).await {
+            tracing::debug!("Failed to remove PDF print stylesheet: {}", e);
+        }
+        result
+    }
+
     /// Find an element by CSS selector
     pub async fn find(&self, selector: &str) -> Result<Element<'_>> {
-        let doc = self.session.get_document(Some(0)).await?;
-        let node_id = self.session.query_selector(doc.node_id, selector).await?;
+        let root = self.root_node_id().await?;
+        let node_id = match self.session.query_selector(root, selector).await {
+            // A document swap we haven't seen the event for yet: refetch once
+            Err(e) if is_stale_node_error(&e) => {
+                self.invalidate_root_node();
+                let root = self.root_node_id().await?;
+                self.session.query_selector(root, selector).await?
+            }
+            result => result?,
+        };
 
         if node_id == 0 {
             return Err(Error::ElementNotFound(selector.to_string()));
         }
 
-        Ok(Element {
-            page: self,
-            node_id,
-        })
+        Ok(Element::new(self, node_id))
+    }
+
+    /// Find an element by CSS selector, `Ok(None)` if nothing matches (like
+    /// `document.querySelector`). Errors are left for real failures, such as
+    /// an invalid selector or a closed session; [`Page::find`] treats a
+    /// missing element as one too.
+    pub async fn query(&self, selector: &str) -> Result<Option<Element<'_>>> {
+        match self.find(selector).await {
+            Ok(element) => Ok(Some(element)),
+            Err(Error::ElementNotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
     }
 
     /// Find all elements matching a CSS selector
     pub async fn find_all(&self, selector: &str) -> Result<Vec<Element<'_>>> {
-        let doc = self.session.get_document(Some(0)).await?;
-        let node_ids = self
-            .session
-            .query_selector_all(doc.node_id, selector)
-            .await?;
+        let root = self.root_node_id().await?;
+        let node_ids = match self.session.query_selector_all(root, selector).await {
+            Err(e) if is_stale_node_error(&e) => {
+                self.invalidate_root_node();
+                let root = self.root_node_id().await?;
+                self.session.query_selector_all(root, selector).await?
+            }
+            result => result?,
+        };
 
         Ok(node_ids
             .into_iter()
             .filter(|&id| id != 0)
-            .map(|node_id| Element {
-                page: self,
-                node_id,
-            })
+            .map(|node_id| Element::new(self, node_id))
             .collect())
     }
 
@@ -165,6 +793,25 @@ impl Page {
     pub async fn exists(&self, selector: &str) -> bool {
         self.find(selector).await.is_ok()
     }
+
+    /// Count elements matching a CSS selector without resolving node ids
+    pub async fn count(&self, selector: &str) -> Result<usize> {
+        let escaped = escape_js_string(selector);
+        self.evaluate(&format!("document.querySelectorAll('{}').length", escaped))
+            .await
+    }
+
+    /// Attribute of the element matching `selector`. Shortcut for
+    /// [`Page::find`] + [`Element::get_attribute`].
+    pub async fn get_attribute(&self, selector: &str, name: &str) -> Result<Option<String>> {
+        self.find(selector).await?.get_attribute(name).await
+    }
+
+    /// Computed CSS `property` of the element matching `selector`. Shortcut
+    /// for [`Page::find`] + [`Element::css`].
+    pub async fn css(&self, selector: &str, property: &str) -> Result<String> {
+        self.find(selector).await?.css(property).await
+    }
     /// Find an element by its text content (case-insensitive contains)
     pub async fn find_by_text(&self, text: &str) -> Result<Element<'_>> {
         self.find_by_text_match(text, TextMatch::Contains).await
@@ -178,26 +825,31 @@ impl Page {
         &self,
         text: &str,
         match_type: TextMatch,
+    ) -> Result<Element<'_>> {
+        self.find_by_text_with(text, match_type, TextMatchOptions::for_match(match_type))
+            .await
+    }
+
+    /// Find an element by text with explicit case/whitespace handling
+    ///
+    /// ```rust,no_run
+    /// # use eoka::{Page, TextMatch, TextMatchOptions};
+    /// # async fn example(page: &Page) -> eoka::Result<()> {
+    /// let options = TextMatchOptions { case_sensitive: true, normalize_whitespace: true, ..Default::default() };
+    /// page.find_by_text_with("Sign In", TextMatch::Contains, options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn find_by_text_with(
+        &self,
+        text: &str,
+        match_type: TextMatch,
+        options: TextMatchOptions,
     ) -> Result<Element<'_>> {
         // Ensure DOM agent is initialized so requestNode works
-        self.session.get_document(Some(0)).await?;
-
-        let escaped_text = escape_js_string(text);
-        let match_js = match match_type {
-            TextMatch::Exact => format!("t.trim() === '{}'", escaped_text),
-            TextMatch::Contains => format!(
-                "t.toLowerCase().includes('{}')",
-                escaped_text.to_lowercase()
-            ),
-            TextMatch::StartsWith => format!(
-                "t.toLowerCase().startsWith('{}')",
-                escaped_text.to_lowercase()
-            ),
-            TextMatch::EndsWith => format!(
-                "t.toLowerCase().endsWith('{}')",
-                escaped_text.to_lowercase()
-            ),
-        };
+        self.root_node_id().await?;
+
+        let match_js = text_match_js(text, match_type, options)?;
 
         // Find the element and return its node via DOM.querySelector with a unique class?
         // Better: evaluate JS that returns the element as a remote object, then get its node_id.
@@ -235,96 +887,132 @@ impl Page {
             match_js = match_js
         );
 
-        let result = self.session.evaluate_for_remote_object(&js).await?;
+        self.element_from_js(&js, || format!("text: {}", text))
+            .await
+    }
+
+    /// Find an element with a unified selector: `css=`, `text=`, `xpath=`,
+    /// chained with `>>` (e.g. `css=.card >> text=Buy`). Bare strings are CSS.
+    pub async fn locate(&self, selector: &str) -> Result<Element<'_>> {
+        let parsed = Selector::parse(selector)?;
+
+        // Ensure DOM agent is initialized so requestNode works
+        self.root_node_id().await?;
+
+        let js = parsed.to_js()?;
+        self.element_from_js(&js, || selector.to_string()).await
+    }
+
+    /// Evaluate JS returning an element (or null) and wrap it as an [`Element`]
+    async fn element_from_js(
+        &self,
+        js: &str,
+        describe: impl Fn() -> String,
+    ) -> Result<Element<'_>> {
+        let result = self.session.evaluate_for_remote_object(js).await?;
         let remote = self.check_js_result(result)?;
 
         if remote.subtype.as_deref() == Some("null") {
-            return Err(Error::ElementNotFound(format!("text: {}", text)));
+            return Err(Error::ElementNotFound(describe()));
         }
 
         let object_id = remote
             .object_id
-            .ok_or_else(|| Error::ElementNotFound(format!("text: {}", text)))?;
+            .ok_or_else(|| Error::ElementNotFound(describe()))?;
 
         // Convert remote object to DOM node_id
         let node_id = self.session.request_node(&object_id).await?;
 
         if node_id == 0 {
-            return Err(Error::ElementNotFound(format!("text: {}", text)));
+            return Err(Error::ElementNotFound(describe()));
         }
 
-        Ok(Element {
-            page: self,
-            node_id,
-        })
+        Ok(Element::new(self, node_id))
     }
 
     /// Find all elements matching the given text
+    ///
+    /// The matches come back as one remote array, resolved to nodes with a
+    /// `DOM.requestNode` per entry, so the page's DOM is never touched.
     pub async fn find_all_by_text(&self, text: &str) -> Result<Vec<Element<'_>>> {
         // Ensure DOM agent is initialized so requestNode works
-        self.session.get_document(Some(0)).await?;
+        self.root_node_id().await?;
 
-        let escaped_text = escape_js_string(text).to_lowercase();
-
-        let js = format!(
-            r#"
-            (() => {{
-                const selectors = 'a, button, input, label, span, div, p, h1, h2, h3, h4, h5, h6, li, td, th';
-                const elements = document.querySelectorAll(selectors);
-                const matches = [];
-                for (const el of elements) {{
-                    const t = (el.innerText || el.textContent || el.value || '').toLowerCase();
-                    if (t.includes('{escaped_text}')) {{
-                        matches.push(el);
-                    }}
-                }}
-                return matches.filter(el => !matches.some(other => el !== other && el.contains(other)));
-            }})()
-            "#,
-            escaped_text = escaped_text
-        );
-
-        // Evaluate without returnByValue to get remote object references
-        let result = self.session.evaluate_for_remote_object(&js).await?;
-
-        let remote = self.check_js_result(result)?;
-
-        let array_object_id = match &remote.object_id {
-            Some(id) => id.clone(),
+        let js = deepest_text_matches_js(text)?;
+        let remote = self.check_js_result(self.session.evaluate_for_remote_object(&js).await?)?;
+        let array_object_id = match remote.object_id {
+            Some(id) => id,
             None => return Ok(Vec::new()),
         };
 
-        // Get all indexed properties of the array in one CDP call
-        let properties = self.session.get_properties(&array_object_id).await?;
+        let result = self.resolve_node_array(&array_object_id).await;
+        if let Err(e) = self.session.release_object(&array_object_id).await {
+            tracing::debug!("Failed to release text matches: {}", e);
+        }
+        result
+    }
+
+    /// Resolve every element of a remote array to an [`Element`]
+    async fn resolve_node_array(&self, array_object_id: &str) -> Result<Vec<Element<'_>>> {
+        let properties = self.session.get_properties(array_object_id).await?;
 
         let mut elements = Vec::new();
-        for prop in &properties {
+        for prop in properties {
             // Array elements have numeric names; skip "length" and prototype props
             if prop.name.parse::<usize>().is_err() {
                 continue;
             }
-            if let Some(ref obj_id) = prop.value.as_ref().and_then(|v| v.object_id.clone()) {
-                if let Ok(node_id) = self.session.request_node(obj_id).await {
-                    if node_id != 0 {
-                        elements.push(Element {
-                            page: self,
-                            node_id,
-                        });
-                    }
+            if let Some(object_id) = prop.value.and_then(|v| v.object_id) {
+                let node_id = self.session.request_node(&object_id).await?;
+                if node_id != 0 {
+                    elements.push(Element::new(self, node_id));
                 }
             }
         }
-
         Ok(elements)
     }
 
+    /// Get the element that currently has focus, or `None` when focus is on
+    /// `<body>` (nothing focused)
+    pub async fn focused_element(&self) -> Result<Option<Element<'_>>> {
+        // Ensure DOM agent is initialized so requestNode works
+        self.root_node_id().await?;
+
+        let js = "(() => { const el = document.activeElement; return el && el !== document.body ? el : null; })()";
+        match self
+            .element_from_js(js, || "focused element".to_string())
+            .await
+        {
+            Ok(element) => Ok(Some(element)),
+            Err(Error::ElementNotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Check if an element with the given text exists
     #[must_use = "returns true if text exists on page"]
     pub async fn text_exists(&self, text: &str) -> bool {
         self.find_by_text(text).await.is_ok()
     }
-    /// Click at coordinates
+
+    /// Number of elements [`Page::find_all_by_text`] would return, counted in
+    /// one pass without resolving any nodes
+    pub async fn text_occurrences(&self, text: &str) -> Result<usize> {
+        self.evaluate_sync(&format!("{}.length", deepest_text_matches_js(text)?))
+            .await
+    }
+
+    /// Count elements containing `text`. Alias for [`Page::text_occurrences`],
+    /// the text counterpart of [`Page::count`].
+    pub async fn count_by_text(&self, text: &str) -> Result<usize> {
+        self.text_occurrences(text).await
+    }
+    /// Click at coordinates, in CSS pixels relative to the visual viewport
+    /// (the space of [`Element::center`] and [`Element::bounding_box`]).
+    /// `devicePixelRatio` doesn't apply: Chrome scales input events itself.
     pub async fn click_at(&self, x: f64, y: f64) -> Result<()> {
+        *self.mouse.lock().unwrap() = Some((x, y));
+
         // Mouse down
         self.session
             .dispatch_mouse_event(
@@ -336,7 +1024,7 @@ impl Page {
             )
             .await?;
 
-        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        self.interaction_pause().await;
 
         // Mouse up
         self.session
@@ -358,19 +1046,84 @@ impl Page {
         element.click().await
     }
 
+    /// Click with a real mouse event when the element can receive one, else
+    /// fall back to [`Element::dispatch_click`]: for elements with no hit
+    /// area, or covered by another element (overlays) at their center.
+    ///
+    /// The fallback click is `isTrusted: false` and has no mouse events
+    /// around it, which pages can detect; prefer [`Page::click`] where it works.
+    pub async fn click_with_fallback(&self, selector: &str) -> Result<()> {
+        self.find(selector).await?.click_with_fallback().await
+    }
+
     /// Type text into focused element
     pub async fn type_text(&self, text: &str) -> Result<()> {
         self.session.insert_text(text).await
     }
 
-    /// Type text into an element by selector
+    /// Type text into an element by selector.
+    ///
+    /// Uses `Input.insertText`: fast, but no key events fire. Use
+    /// [`Page::key_type`] for fields driven by key handlers.
     pub async fn type_into(&self, selector: &str, text: &str) -> Result<()> {
         let element = self.find(selector).await?;
         element.click().await?;
-        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        self.interaction_pause().await;
         self.session.insert_text(text).await
     }
 
+    /// Type into an element by selector one key at a time, with a real
+    /// `keydown`/`keypress`/`input`/`keyup` sequence per character, so
+    /// autocompletes and input masks see the keystrokes
+    pub async fn key_type(&self, selector: &str, text: &str) -> Result<()> {
+        use crate::cdp::types::{modifiers, InputDispatchKeyEventFull, KeyEventType};
+
+        let element = self.find(selector).await?;
+        element.click().await?;
+        self.interaction_pause().await;
+
+        for ch in text.chars() {
+            let (key, code, vk, shift) = char_to_codes(ch);
+            let text = if ch == '\n' {
+                "\r".to_string()
+            } else {
+                ch.to_string()
+            };
+            let event = InputDispatchKeyEventFull {
+                modifiers: shift.then_some(modifiers::SHIFT),
+                key: Some(key),
+                code: (!code.is_empty()).then_some(code),
+                windows_virtual_key_code: vk,
+                native_virtual_key_code: vk,
+                ..Default::default()
+            };
+
+            self.session
+                .dispatch_key_event_full(InputDispatchKeyEventFull {
+                    r#type: KeyEventType::RawKeyDown,
+                    ..event.clone()
+                })
+                .await?;
+            self.session
+                .dispatch_key_event_full(InputDispatchKeyEventFull {
+                    r#type: KeyEventType::Char,
+                    text: Some(text.clone()),
+                    unmodified_text: Some(text),
+                    ..event.clone()
+                })
+                .await?;
+            self.session
+                .dispatch_key_event_full(InputDispatchKeyEventFull {
+                    r#type: KeyEventType::KeyUp,
+                    ..event
+                })
+                .await?;
+
+            self.interaction_pause().await;
+        }
+        Ok(())
+    }
+
     /// Click an element by its text content
     pub async fn click_by_text(&self, text: &str) -> Result<()> {
         let element = self.find_by_text(text).await?;
@@ -409,7 +1162,7 @@ impl Page {
     pub async fn fill(&self, selector: &str, value: &str) -> Result<()> {
         let element = self.find(selector).await?;
         element.click().await?;
-        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        self.interaction_pause().await;
 
         // Focus + select via selector (don't rely on activeElement — popups can steal focus)
         let escaped = escape_js_string(selector);
@@ -422,13 +1175,101 @@ impl Page {
         // Now type the new value
         self.session.insert_text(value).await
     }
+
+    /// Check a checkbox/radio; clicks only if it isn't checked already
+    pub async fn check(&self, selector: &str) -> Result<()> {
+        self.find(selector).await?.check().await
+    }
+
+    /// Uncheck a checkbox; clicks only if it is checked. Fails for a checked radio.
+    pub async fn uncheck(&self, selector: &str) -> Result<()> {
+        self.find(selector).await?.uncheck().await
+    }
+
+    /// Submit a form the way pressing Enter in it would: `requestSubmit()`
+    /// validates first and fires the `submit` event, so handlers and
+    /// `required` fields behave as for a user. An invalid form isn't sent;
+    /// Chrome fires `invalid` events instead. Works without a submit button.
+    ///
+    /// `form_selector` may also match an element inside the form. Where
+    /// `requestSubmit` is missing, clicks the form's submit button instead.
+    pub async fn submit(&self, form_selector: &str) -> Result<()> {
+        let element = self.find(form_selector).await?;
+        match element.eval_on_element(SUBMIT_FORM_JS).await?.as_str() {
+            Some("submitted") => Ok(()),
+            Some("unsupported") => {
+                let not_found =
+                    || Error::ElementNotFound(format!("submit button of {}", form_selector));
+                let object_id = self.session.resolve_node(element.node_id()).await?;
+                let result = self
+                    .session
+                    .call_function_on_for_remote_object(&object_id, SUBMIT_BUTTON_JS, &[])
+                    .await?;
+                let remote = self.check_js_result(result)?;
+                let button = remote
+                    .object_id
+                    .filter(|_| remote.subtype.as_deref() != Some("null"))
+                    .ok_or_else(not_found)?;
+                let node_id = self.session.request_node(&button).await?;
+                if node_id == 0 {
+                    return Err(not_found());
+                }
+                Element::new(self, node_id).click().await
+            }
+            _ => Err(Error::InvalidState(format!(
+                "'{}' is neither a form nor inside one",
+                form_selector
+            ))),
+        }
+    }
+
     /// Get a Human helper for human-like interactions
     pub fn human(&self) -> Human<'_> {
         Human::new(&self.session)
     }
 
+    /// Build a plausible interaction history before the first real action:
+    /// a few idle mouse movements from the tracked cursor position and a
+    /// small scroll down and back, all with human timing (about 1-4s).
+    /// Sessions whose first event is an instant click stand out.
+    ///
+    /// Runs automatically before the first human interaction when
+    /// `StealthConfig::warm_up` is set.
+    pub async fn warm_up(&self) -> Result<()> {
+        self.warmed_up.store(true, Ordering::SeqCst);
+        let [width, height, scroll_x, scroll_y]: [f64; 4] = self
+            .evaluate("[innerWidth, innerHeight, scrollX, scrollY]")
+            .await?;
+        let start = *self.mouse.lock().unwrap();
+        let end = self.human().warm_up(start, (width, height)).await?;
+        *self.mouse.lock().unwrap() = Some(end);
+
+        // The wheel back up doesn't always cancel the way down (clamped at
+        // the bottom, smooth scrolling still running), so let the scroll
+        // settle and put the page exactly where it was
+        let _: bool = self
+            .evaluate(&format!(
+                "({})({}, {})",
+                WARM_UP_RESTORE_JS, scroll_x, scroll_y
+            ))
+            .await?;
+        Ok(())
+    }
+
+    /// Run `warm_up` if configured and not done yet on this page.
+    ///
+    /// Call it before locating or measuring the target element: the warm-up
+    /// scrolls, so coordinates taken earlier may be stale.
+    async fn auto_warm_up(&self) -> Result<()> {
+        if self.config.warm_up && !self.warmed_up.load(Ordering::SeqCst) {
+            self.warm_up().await?;
+        }
+        Ok(())
+    }
+
     /// Human-like click on an element
     pub async fn human_click(&self, selector: &str) -> Result<()> {
+        self.auto_warm_up().await?;
         let element = self.find(selector).await?;
         let (x, y) = element.center().await?;
         self.human_click_at_center_xy(x, y).await
@@ -449,6 +1290,7 @@ impl Page {
 
     /// Human-like click on an element found by text content
     pub async fn human_click_by_text(&self, text: &str) -> Result<()> {
+        self.auto_warm_up().await?;
         let element = self.find_by_text(text).await?;
         let (x, y) = element.center().await?;
         self.human_click_at_center_xy(x, y).await
@@ -457,12 +1299,14 @@ impl Page {
     /// Try to human-click an element, returning Ok(true) if clicked, Ok(false) if not found or not clickable
     #[must_use = "returns true if clicked, false if not found/visible"]
     pub async fn try_human_click(&self, selector: &str) -> Result<bool> {
+        self.auto_warm_up().await?;
         self.try_human_click_impl(self.find(selector).await).await
     }
 
     /// Try to human-click an element by text, returning Ok(true) if clicked, Ok(false) if not found or not clickable
     #[must_use = "returns true if clicked, false if not found/visible"]
     pub async fn try_human_click_by_text(&self, text: &str) -> Result<bool> {
+        self.auto_warm_up().await?;
         self.try_human_click_impl(self.find_by_text(text).await)
             .await
     }
@@ -489,7 +1333,10 @@ impl Page {
 
     async fn human_click_at_center_xy(&self, x: f64, y: f64) -> Result<()> {
         if self.config.human_mouse {
-            self.human().move_and_click(x, y).await
+            let start = self.mouse_origin();
+            self.human().move_from_and_click(start, x, y).await?;
+            *self.mouse.lock().unwrap() = Some((x, y));
+            Ok(())
         } else {
             self.click_at(x, y).await
         }
@@ -510,7 +1357,11 @@ impl Page {
             Err(e) => Err(e),
         }
     }
-    /// Evaluate JavaScript and return the result
+    /// Evaluate JavaScript and return the result.
+    ///
+    /// If the expression evaluates to a promise, its resolved value is
+    /// returned. To use `await` inside the expression, see
+    /// [`Page::evaluate_async`]; to not wait at all, [`Page::evaluate_sync`].
     pub async fn evaluate<T: serde::de::DeserializeOwned>(&self, expression: &str) -> Result<T> {
         let result = self.check_js_result(self.session.evaluate(expression).await?)?;
         let value = result
@@ -519,6 +1370,27 @@ impl Page {
         Ok(serde_json::from_value(value)?)
     }
 
+    /// Evaluate JavaScript and return the raw JSON value, for results whose
+    /// shape isn't known upfront. `null` and `undefined` give `Value::Null`
+    /// instead of an error; exceptions still fail.
+    pub async fn evaluate_json(&self, expression: &str) -> Result<serde_json::Value> {
+        let result = self.check_js_result(self.session.evaluate(expression).await?)?;
+        Ok(result.value.unwrap_or(serde_json::Value::Null))
+    }
+
+    /// Evaluate an expression that may use `await`, e.g.
+    /// `await fetch('/api').then(r => r.status)`, and return its result.
+    ///
+    /// The expression runs as `(async () => { return (expr); })()`, so it
+    /// must be a single expression, not statements.
+    pub async fn evaluate_async<T: serde::de::DeserializeOwned>(
+        &self,
+        expression: &str,
+    ) -> Result<T> {
+        self.evaluate(&format!("(async () => {{ return ({}); }})()", expression))
+            .await
+    }
+
     /// Evaluate JavaScript synchronously (don't await promises).
     /// Use when the page may have unresolved promises that block normal evaluate.
     pub async fn evaluate_sync<T: serde::de::DeserializeOwned>(
@@ -544,16 +1416,67 @@ impl Page {
         Ok(())
     }
 
+    /// Run `source` once in the current document, e.g. to install helpers.
+    /// Gone after the next navigation; see [`Page::add_init_script`].
+    pub async fn inject_script(&self, source: &str) -> Result<()> {
+        self.execute(source).await
+    }
+
+    /// Run `source` in every future document of this page, before the page's
+    /// own scripts (after the stealth evasions). Returns an identifier for
+    /// [`Page::remove_init_script`].
+    ///
+    /// The current document is not affected; pair with
+    /// [`Page::inject_script`] if it should be.
+    pub async fn add_init_script(&self, source: &str) -> Result<String> {
+        self.session
+            .add_script_to_evaluate_on_new_document(source)
+            .await
+    }
+
+    /// Stop running a script added with [`Page::add_init_script`]
+    pub async fn remove_init_script(&self, identifier: &str) -> Result<()> {
+        self.session
+            .remove_script_to_evaluate_on_new_document(identifier)
+            .await
+    }
+
+    /// Stop injecting the stealth evasions into this page's future
+    /// documents, e.g. to check whether an issue comes from them. The
+    /// current document keeps its evasions until the next navigation.
+    pub async fn disable_evasions(&self) -> Result<()> {
+        let id = self.evasion_script_id.lock().unwrap().take();
+        match id {
+            Some(id) => self.remove_init_script(&id).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Whether new documents of this page get the stealth evasions
+    pub fn evasions_enabled(&self) -> bool {
+        self.evasion_script_id.lock().unwrap().is_some()
+    }
+
     /// Check a JS evaluation result for exceptions
-    fn check_js_result(
+    pub(crate) fn check_js_result(
         &self,
         result: crate::cdp::types::RuntimeEvaluateResult,
     ) -> Result<crate::cdp::types::RemoteObject> {
         if let Some(exception) = result.exception_details {
-            return Err(Error::CdpSimple(format!(
-                "JavaScript error: {} at {}:{}",
-                exception.text, exception.line_number, exception.column_number
-            )));
+            // `text` is often just "Uncaught"; the thrown value says what happened
+            let text = match exception.exception.and_then(|e| e.description) {
+                Some(desc) => format!(
+                    "{} {}",
+                    exception.text,
+                    desc.lines().next().unwrap_or_default()
+                ),
+                None => exception.text,
+            };
+            return Err(Error::JavaScriptException {
+                text,
+                line: exception.line_number,
+                column: exception.column_number,
+            });
         }
         Ok(result.result)
     }
@@ -582,6 +1505,64 @@ impl Page {
         Ok(())
     }
 
+    /// Get the cookie named `name`, if the page has one
+    pub async fn cookie(&self, name: &str) -> Result<Option<Cookie>> {
+        Ok(self.cookies().await?.into_iter().find(|c| c.name == name))
+    }
+
+    /// `Cookie` request header value (`a=1; b=2`) for a request to `url`,
+    /// e.g. to hand the session to an HTTP client. Chrome picks the cookies,
+    /// so domain, path and `secure` match as for its own requests. Empty
+    /// when no cookie applies.
+    pub async fn cookie_header(&self, url: &str) -> Result<String> {
+        let cookies = self
+            .session
+            .get_cookies(Some(vec![url.to_string()]))
+            .await?;
+        Ok(cookie_header(cookies))
+    }
+
+    /// Set a cookie from a full [`Cookie`], including `secure`, `http_only`,
+    /// `same_site` and `expires`
+    ///
+    /// An empty `domain` scopes the cookie to the current page's URL, an empty
+    /// `path` defaults to `/`, and `session` (or `expires <= 0`) makes it a
+    /// session cookie. `size` is ignored.
+    pub async fn set_cookie_full(&self, cookie: Cookie) -> Result<()> {
+        let url = if cookie.domain.is_empty() {
+            Some(self.url().await?)
+        } else {
+            None
+        };
+        let non_empty = |s: String| (!s.is_empty()).then_some(s);
+        let expires = (!cookie.session && cookie.expires > 0.0).then_some(cookie.expires);
+
+        let success = self
+            .session
+            .set_cookie_full(NetworkSetCookie {
+                name: cookie.name,
+                value: cookie.value,
+                url,
+                domain: non_empty(cookie.domain),
+                path: non_empty(cookie.path),
+                secure: Some(cookie.secure),
+                http_only: Some(cookie.http_only),
+                same_site: cookie.same_site,
+                expires,
+            })
+            .await?;
+
+        if !success {
+            return Err(Error::CdpSimple("Failed to set cookie".into()));
+        }
+        Ok(())
+    }
+
+    /// Delete every cookie in the browser, for all sites (not just this page's)
+    pub async fn clear_cookies(&self) -> Result<()> {
+        self.session.clear_browser_cookies().await
+    }
+
     /// Delete a cookie
     pub async fn delete_cookie(&self, name: &str, domain: Option<&str>) -> Result<()> {
         let url = self.url().await.ok();
@@ -634,6 +1615,45 @@ impl Page {
         }
     }
 
+    /// Wait for an element to stop moving (e.g. a modal sliding in) so a click
+    /// lands where intended. Returns once its bounding box has been unchanged
+    /// for 200ms.
+    pub async fn wait_for_stable(&self, selector: &str, timeout_ms: u64) -> Result<Element<'_>> {
+        const STABLE_FOR: std::time::Duration = std::time::Duration::from_millis(200);
+
+        let start = std::time::Instant::now();
+        let timeout = std::time::Duration::from_millis(timeout_ms);
+        let mut last: Option<(BoundingBox, std::time::Instant)> = None;
+
+        loop {
+            if let Ok(element) = self.find(selector).await {
+                if let Some(bbox) = element.bounding_box().await {
+                    match last {
+                        Some((prev, since)) if prev == bbox => {
+                            if since.elapsed() >= STABLE_FOR {
+                                return Ok(element);
+                            }
+                        }
+                        _ => last = Some((bbox, std::time::Instant::now())),
+                    }
+                } else {
+                    last = None;
+                }
+            } else {
+                last = None;
+            }
+
+            if start.elapsed() > timeout {
+                return Err(Error::Timeout(format!(
+                    "Element '{}' did not stop moving within {}ms",
+                    selector, timeout_ms
+                )));
+            }
+
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+    }
+
     /// Wait for an element to disappear
     pub async fn wait_for_hidden(&self, selector: &str, timeout_ms: u64) -> Result<()> {
         let start = std::time::Instant::now();
@@ -681,6 +1701,35 @@ impl Page {
         }
     }
 
+    /// Wait for an element with any of `texts`, e.g. a success or an error
+    /// message, and return the index of the text that appeared with its
+    /// element. If several are present at once, the lowest index wins.
+    pub async fn wait_for_any_text(
+        &self,
+        texts: &[&str],
+        timeout_ms: u64,
+    ) -> Result<(usize, Element<'_>)> {
+        let start = std::time::Instant::now();
+        let timeout = std::time::Duration::from_millis(timeout_ms);
+
+        loop {
+            for (index, text) in texts.iter().enumerate() {
+                if let Ok(element) = self.find_by_text(text).await {
+                    return Ok((index, element));
+                }
+            }
+
+            if start.elapsed() > timeout {
+                return Err(Error::Timeout(format!(
+                    "None of texts found within {}ms: {:?}",
+                    timeout_ms, texts
+                )));
+            }
+
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    }
+
     /// Wait for the URL to contain a specific string
     pub async fn wait_for_url_contains(&self, pattern: &str, timeout_ms: u64) -> Result<()> {
         let start = std::time::Instant::now();
@@ -728,6 +1777,42 @@ impl Page {
             tokio::time::sleep(std::time::Duration::from_millis(100)).await;
         }
     }
+
+    /// Poll an async condition every `poll_ms` until it returns `Ok(true)`
+    ///
+    /// Compose anything the page exposes, e.g. "URL contains X and element Y
+    /// is visible". An `Err` from `cond` counts as "not yet" and is reported in
+    /// the timeout error if nothing else succeeds.
+    pub async fn wait_until<F, Fut>(&self, poll_ms: u64, timeout_ms: u64, cond: F) -> Result<()>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<bool>>,
+    {
+        let start = std::time::Instant::now();
+        let timeout = std::time::Duration::from_millis(timeout_ms);
+        let mut last_error = None;
+
+        loop {
+            match cond().await {
+                Ok(true) => return Ok(()),
+                Ok(false) => {}
+                Err(e) => last_error = Some(e.to_string()),
+            }
+
+            if start.elapsed() > timeout {
+                return Err(Error::Timeout(match last_error {
+                    Some(e) => format!(
+                        "Condition not met within {}ms (last error: {})",
+                        timeout_ms, e
+                    ),
+                    None => format!("Condition not met within {}ms", timeout_ms),
+                }));
+            }
+
+            tokio::time::sleep(std::time::Duration::from_millis(poll_ms)).await;
+        }
+    }
+
     /// Enable network request capture
     /// NOTE: This enables Network.enable which may be slightly detectable by advanced anti-bot
     pub async fn enable_request_capture(&self) -> Result<()> {
@@ -739,21 +1824,265 @@ impl Page {
         self.session.network_disable().await
     }
 
-    /// Get response body for a captured request
-    /// The request_id comes from CapturedRequest.request_id
-    pub async fn get_response_body(&self, request_id: &str) -> Result<ResponseBody> {
-        let (body, base64_encoded) = self.session.get_response_body(request_id).await?;
+    /// Wait for a request whose URL contains `url_pattern` and for its
+    /// response headers, returning it with `status`, `response_headers` and
+    /// `mime_type` filled in (the body may still be loading). Event-driven,
+    /// so short-lived requests are never missed; start waiting before
+    /// triggering the request, e.g. with `tokio::join!`. Fails if the request
+    /// fails before a response arrives. Requires [`Page::enable_request_capture`].
+    pub async fn wait_for_request(
+        &self,
+        url_pattern: &str,
+        timeout_ms: u64,
+    ) -> Result<CapturedRequest> {
+        use crate::cdp::types::{
+            NetworkLoadingFailedEvent, NetworkRequestWillBeSentEvent, NetworkResponseReceivedEvent,
+        };
+
+        let mut sent = self.session.on("Network.requestWillBeSent");
+        let mut received = self.session.on("Network.responseReceived");
+        let mut failed = self.session.on("Network.loadingFailed");
+        let matched = async {
+            let mut request = loop {
+                let event: NetworkRequestWillBeSentEvent =
+                    serde_json::from_value(recv_event(&mut sent).await?)?;
+                if event.request.url.contains(url_pattern) {
+                    break CapturedRequest::from(event);
+                }
+            };
+            loop {
+                tokio::select! {
+                    params = recv_event(&mut received) => {
+                        let event: NetworkResponseReceivedEvent = serde_json::from_value(params?)?;
+                        if event.request_id == request.request_id {
+                            request.status = Some(event.response.status);
+                            request.status_text = Some(event.response.status_text);
+                            request.response_headers = Some(event.response.headers);
+                            request.mime_type = event.response.mime_type;
+                            return Ok(request);
+                        }
+                    }
+                    params = recv_event(&mut failed) => {
+                        let event: NetworkLoadingFailedEvent = serde_json::from_value(params?)?;
+                        if event.request_id == request.request_id {
+                            return Err(Error::CdpSimple(format!(
+                                "Request to {} failed: {}",
+                                request.url, event.error_text
+                            )));
+                        }
+                    }
+                }
+            }
+        };
+        tokio::time::timeout(std::time::Duration::from_millis(timeout_ms), matched)
+            .await
+            .map_err(|_| {
+                Error::Timeout(format!(
+                    "No response to a request matching '{}' within {}ms",
+                    url_pattern, timeout_ms
+                ))
+            })?
+    }
+
+    /// Get response body for a captured request
+    /// The request_id comes from CapturedRequest.request_id
+    pub async fn get_response_body(&self, request_id: &str) -> Result<ResponseBody> {
+        let (body, base64_encoded) = self.session.get_response_body(request_id).await?;
+
+        if base64_encoded {
+            use base64::Engine;
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(&body)
+                .map_err(|e| Error::Decode(e.to_string()))?;
+            Ok(ResponseBody::Binary(bytes))
+        } else {
+            Ok(ResponseBody::Text(body))
+        }
+    }
+
+    /// Stream the body of a captured request into `writer` as it arrives,
+    /// returning the number of bytes written. Call it while the request is
+    /// in flight (e.g. right after [`Page::wait_for_request`]); once the
+    /// request has finished loading, Chrome no longer streams it and the
+    /// buffered body is written in one piece instead.
+    ///
+    /// Fails with `Error::Timeout` if the request hasn't finished within
+    /// `timeout_ms`, and with an error rather than a corrupt body if any
+    /// chunk event was dropped.
+    pub async fn stream_response_body<W: std::io::Write>(
+        &self,
+        request_id: &str,
+        mut writer: W,
+        timeout_ms: u64,
+    ) -> Result<usize> {
+        use crate::cdp::types::{
+            NetworkDataReceivedEvent, NetworkLoadingFailedEvent, NetworkLoadingFinishedEvent,
+        };
+
+        // Subscribe first so the end of the request can't slip past
+        let mut data = self.session.on("Network.dataReceived");
+        let mut finished = self.session.on("Network.loadingFinished");
+        let mut failed = self.session.on("Network.loadingFailed");
+
+        let buffered = match self.session.stream_resource_content(request_id).await {
+            Ok(buffered) => buffered,
+            Err(e) => {
+                tracing::debug!("Not streaming {}, reading whole body: {}", request_id, e);
+                let body = self.get_response_body(request_id).await?;
+                writer.write_all(body.as_bytes())?;
+                return Ok(body.as_bytes().len());
+            }
+        };
+        let mut written = write_base64(&buffered, &mut writer)?;
+
+        let deadline = tokio::time::sleep(std::time::Duration::from_millis(timeout_ms));
+        tokio::pin!(deadline);
+        loop {
+            // Biased so chunks queued before loadingFinished are written first
+            tokio::select! {
+                biased;
+                params = recv_event_lossless(&mut data) => {
+                    let event: NetworkDataReceivedEvent = serde_json::from_value(params?)?;
+                    if event.request_id == request_id {
+                        if let Some(chunk) = event.data {
+                            written += write_base64(&chunk, &mut writer)?;
+                        }
+                    }
+                }
+                params = recv_event_lossless(&mut finished) => {
+                    let event: NetworkLoadingFinishedEvent = serde_json::from_value(params?)?;
+                    if event.request_id == request_id {
+                        writer.flush()?;
+                        return Ok(written);
+                    }
+                }
+                params = recv_event_lossless(&mut failed) => {
+                    let event: NetworkLoadingFailedEvent = serde_json::from_value(params?)?;
+                    if event.request_id == request_id {
+                        return Err(Error::CdpSimple(format!(
+                            "Request failed while streaming: {}",
+                            event.error_text
+                        )));
+                    }
+                }
+                _ = &mut deadline => {
+                    return Err(Error::Timeout(format!(
+                        "streaming body of {} after {}ms ({} bytes written)",
+                        request_id, timeout_ms, written
+                    )));
+                }
+            }
+        }
+    }
+
+    /// Set what happens to downloads in this page's browser context:
+    /// `deny`, `allow` (into `download_path`), `allowAndName` (files named
+    /// by guid) or `default`. Other browser contexts are not affected.
+    pub async fn set_download_behavior(
+        &self,
+        behavior: &str,
+        download_path: Option<&str>,
+    ) -> Result<()> {
+        self.session
+            .set_download_behavior(behavior, download_path, false)
+            .await?;
+        *self.download_behavior.lock().unwrap() =
+            Some((behavior.to_string(), download_path.map(String::from)));
+        Ok(())
+    }
+
+    /// Run `trigger` (e.g. a click on a download link), wait up to
+    /// `timeout_ms` for the download it starts to finish, and return the
+    /// suggested filename with the file's bytes.
+    ///
+    /// The file goes to a temp directory that is removed afterwards, and the
+    /// download behavior from [`Page::set_download_behavior`] (or Chrome's
+    /// default) is put back. Only this page's browser context is touched.
+    pub async fn download_and_read<F, T>(
+        &self,
+        trigger: F,
+        timeout_ms: u64,
+    ) -> Result<(String, Vec<u8>)>
+    where
+        F: std::future::Future<Output = Result<T>>,
+    {
+        let dir = std::env::temp_dir().join(format!(
+            "eoka-download-{}-{}",
+            std::process::id(),
+            rand::random::<u32>()
+        ));
+        std::fs::create_dir_all(&dir)?;
+
+        let result = self.capture_download(&dir, trigger, timeout_ms).await;
+
+        let previous = self.download_behavior.lock().unwrap().clone();
+        let (behavior, download_path) = match &previous {
+            Some((behavior, path)) => (behavior.as_str(), path.as_deref()),
+            None => ("default", None),
+        };
+        if let Err(e) = self
+            .session
+            .set_download_behavior(behavior, download_path, false)
+            .await
+        {
+            tracing::debug!("Failed to restore download behavior: {}", e);
+        }
+        std::fs::remove_dir_all(&dir).ok();
+        result
+    }
+
+    async fn capture_download<F, T>(
+        &self,
+        dir: &std::path::Path,
+        trigger: F,
+        timeout_ms: u64,
+    ) -> Result<(String, Vec<u8>)>
+    where
+        F: std::future::Future<Output = Result<T>>,
+    {
+        use crate::cdp::types::{BrowserDownloadProgressEvent, BrowserDownloadWillBeginEvent};
 
-        if base64_encoded {
-            use base64::Engine;
-            let bytes = base64::engine::general_purpose::STANDARD
-                .decode(&body)
-                .map_err(|e| Error::Decode(e.to_string()))?;
-            Ok(ResponseBody::Binary(bytes))
-        } else {
-            Ok(ResponseBody::Text(body))
-        }
+        // Subscribe before triggering so no event is missed
+        let mut began = self.session.on("Browser.downloadWillBegin");
+        let mut progress = self.session.on("Browser.downloadProgress");
+        self.session
+            .set_download_behavior("allowAndName", Some(&dir.to_string_lossy()), true)
+            .await?;
+
+        trigger.await?;
+
+        let finished = async {
+            let started: BrowserDownloadWillBeginEvent =
+                serde_json::from_value(recv_event(&mut began).await?)?;
+            loop {
+                let event: BrowserDownloadProgressEvent =
+                    serde_json::from_value(recv_event(&mut progress).await?)?;
+                if event.guid != started.guid {
+                    continue;
+                }
+                match event.state.as_str() {
+                    "completed" => return Ok(started),
+                    "canceled" => {
+                        return Err(Error::CdpSimple(format!(
+                            "Download canceled: {}",
+                            started.url
+                        )))
+                    }
+                    _ => {}
+                }
+            }
+        };
+        let started = tokio::time::timeout(std::time::Duration::from_millis(timeout_ms), finished)
+            .await
+            .map_err(|_| {
+                Error::Timeout(format!("Download not finished within {}ms", timeout_ms))
+            })??;
+
+        // `allowAndName` stores the file under its guid
+        let bytes = std::fs::read(dir.join(&started.guid))?;
+        Ok((started.suggested_filename, bytes))
     }
+
     /// Find the first element matching any of the given selectors
     pub async fn find_any(&self, selectors: &[&str]) -> Result<Element<'_>> {
         for selector in selectors {
@@ -767,6 +2096,45 @@ impl Page {
         )))
     }
 
+    /// Wait until at least `min_count` elements match `selector`, e.g. a list
+    /// loading incrementally, and return all matches. Polls
+    /// [`Page::count`], so nodes are only resolved once the count is reached.
+    pub async fn wait_for_count(
+        &self,
+        selector: &str,
+        min_count: usize,
+        timeout_ms: u64,
+    ) -> Result<Vec<Element<'_>>> {
+        let start = std::time::Instant::now();
+        let timeout = std::time::Duration::from_millis(timeout_ms);
+
+        // Errors while the page navigates are transient, like in
+        // `wait_for_any`; the last outcome goes into the timeout message
+        loop {
+            let last = match self.count(selector).await {
+                Ok(count) if count >= min_count => match self.find_all(selector).await {
+                    // The list can shrink between the count and the lookup
+                    Ok(elements) if elements.len() >= min_count => return Ok(elements),
+                    Ok(elements) => format!("found {}", elements.len()),
+                    Err(e @ Error::InvalidSelector(_)) => return Err(e),
+                    Err(e) => format!("last error: {}", e),
+                },
+                Ok(count) => format!("found {}", count),
+                Err(e @ Error::InvalidSelector(_)) => return Err(e),
+                Err(e) => format!("last error: {}", e),
+            };
+
+            if start.elapsed() > timeout {
+                return Err(Error::Timeout(format!(
+                    "Fewer than {} elements matching '{}' within {}ms ({})",
+                    min_count, selector, timeout_ms, last
+                )));
+            }
+
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    }
+
     /// Wait for any of the given selectors to appear
     ///
     /// Returns the first selector that matches.
@@ -791,6 +2159,108 @@ impl Page {
             tokio::time::sleep(std::time::Duration::from_millis(100)).await;
         }
     }
+    /// Switch this page's locale (e.g. `de-DE`): `navigator.language(s)` on the
+    /// current and future documents, and the `Accept-Language` request header,
+    /// so JS and HTTP report the same languages.
+    ///
+    /// Enables the Network domain for the header override.
+    pub async fn set_locale(&self, locale: &str) -> Result<()> {
+        let languages = locale_languages(locale);
+        let script = languages_script(&languages);
+
+        // Drop the previous locale's script so it can't run after this one
+        let previous = self.locale_script_id.lock().unwrap().take();
+        if let Some(identifier) = previous {
+            self.session
+                .remove_script_to_evaluate_on_new_document(&identifier)
+                .await?;
+        }
+        let identifier = self
+            .session
+            .add_script_to_evaluate_on_new_document(&script)
+            .await?;
+        *self.locale_script_id.lock().unwrap() = Some(identifier);
+        self.execute(&script).await?;
+
+        *self.accept_language.lock().unwrap() = Some(accept_language(&languages));
+        self.apply_extra_headers().await
+    }
+
+    /// Make Chrome report `timezone` (IANA id like `Asia/Tokyo`) for this page
+    /// via `Emulation.setTimezoneOverride`: `Date`, `Intl` and everything
+    /// else agree because the engine itself changes, unlike the
+    /// `timezone_spoof` JS evasion. Pass `""` to restore the host timezone.
+    ///
+    /// Combine with `timezone_spoof: false` (or use
+    /// `StealthConfig::timezone_override` for every page), since the JS
+    /// evasion pins `Intl` to its own zone.
+    pub async fn set_timezone(&self, timezone: &str) -> Result<()> {
+        self.session.set_timezone_override(timezone).await
+    }
+
+    /// Send `headers` with every request from this page, navigations
+    /// included, until changed. Replaces headers from an earlier call.
+    ///
+    /// Lighter than [`Page::intercept`] when only headers change, but
+    /// enables the Network domain.
+    pub async fn set_headers(&self, headers: HashMap<String, String>) -> Result<()> {
+        *self.extra_headers.lock().unwrap() = headers;
+        self.apply_extra_headers().await
+    }
+
+    /// Stop sending the headers from [`Page::set_headers`]. The
+    /// `Accept-Language` from [`Page::set_locale`] stays.
+    pub async fn clear_headers(&self) -> Result<()> {
+        self.extra_headers.lock().unwrap().clear();
+        self.apply_extra_headers().await
+    }
+
+    /// Push the `set_headers` headers plus the locale's `Accept-Language`
+    /// (unless overridden) to Chrome
+    async fn apply_extra_headers(&self) -> Result<()> {
+        let mut headers = self.extra_headers.lock().unwrap().clone();
+        if let Some(value) = self.accept_language.lock().unwrap().clone() {
+            let overridden = headers
+                .keys()
+                .any(|name| name.eq_ignore_ascii_case("Accept-Language"));
+            if !overridden {
+                headers.insert("Accept-Language".to_string(), value);
+            }
+        }
+
+        self.session.network_enable().await?;
+        self.session.set_extra_http_headers(headers).await
+    }
+
+    /// Route every request from this page through `handler`, which decides
+    /// to continue (optionally rewritten), abort, or fulfill it.
+    ///
+    /// Replaces any previous interceptor. The handler runs on the event task,
+    /// so keep it fast: requests stay paused until it returns.
+    pub async fn intercept<F>(&self, handler: F) -> Result<()>
+    where
+        F: Fn(InterceptedRequest) -> InterceptAction + Send + 'static,
+    {
+        // Subscribe before enabling so no paused request is missed; the
+        // channel is unbounded because a dropped event would hang its request
+        let paused = self.session.on_unbounded("Fetch.requestPaused");
+        let task = intercept::spawn(self.session.clone(), paused, Box::new(handler));
+        if let Some(previous) = self.interceptor.lock().unwrap().replace(task) {
+            previous.abort();
+        }
+        self.session.fetch_enable().await
+    }
+
+    /// Remove the interceptor installed by `intercept`
+    pub async fn stop_intercept(&self) -> Result<()> {
+        let task = self.interceptor.lock().unwrap().take();
+        if let Some(task) = task {
+            self.session.fetch_disable().await?;
+            task.abort();
+        }
+        Ok(())
+    }
+
     /// Wait for network to become idle (no pending XHR/fetch for `idle_time_ms`)
     pub async fn wait_for_network_idle(&self, idle_time_ms: u64, timeout_ms: u64) -> Result<()> {
         let start = std::time::Instant::now();
@@ -893,6 +2363,40 @@ impl Page {
         Ok(frames)
     }
 
+    /// Get a handle to an iframe for finding, clicking, filling and evaluating
+    /// inside it. Works for cross-origin (out-of-process) iframes too.
+    pub async fn frame(&self, selector: &str) -> Result<Frame<'_>> {
+        Frame::attach(self, selector).await
+    }
+
+    /// Execution context of the isolated world for a same-process frame.
+    /// One world per frame document: reused until the frame navigates.
+    pub(crate) async fn frame_world(&self, frame_id: &str) -> Result<i64> {
+        fn loader_id(tree: &crate::cdp::types::FrameTree, frame_id: &str) -> Option<String> {
+            if tree.frame.id == frame_id {
+                return Some(tree.frame.loader_id.clone());
+            }
+            tree.child_frames
+                .iter()
+                .find_map(|child| loader_id(child, frame_id))
+        }
+
+        let tree = self.session.get_frame_tree().await?;
+        let loader = loader_id(&tree, frame_id).unwrap_or_default();
+        if let Some((cached_loader, context_id)) = self.frame_worlds.lock().unwrap().get(frame_id) {
+            if *cached_loader == loader {
+                return Ok(*context_id);
+            }
+        }
+
+        let context_id = self.session.create_isolated_world(frame_id).await?;
+        self.frame_worlds
+            .lock()
+            .unwrap()
+            .insert(frame_id.to_string(), (loader, context_id));
+        Ok(context_id)
+    }
+
     /// Execute JavaScript inside an iframe
     pub async fn evaluate_in_frame<T: serde::de::DeserializeOwned>(
         &self,
@@ -929,24 +2433,28 @@ impl Page {
         F: Fn() -> Fut,
         Fut: std::future::Future<Output = Result<T>>,
     {
-        let mut last_error = String::new();
-
-        for attempt in 1..=attempts {
-            match operation().await {
-                Ok(result) => return Ok(result),
-                Err(e) => {
-                    last_error = e.to_string();
-                    if attempt < attempts {
-                        tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
-                    }
-                }
-            }
-        }
+        retry_with_backoff(attempts, delay_ms, 1.0, |_| true, operation).await
+    }
 
-        Err(Error::RetryExhausted {
-            attempts,
-            last_error,
-        })
+    /// Retry with exponential backoff, only while `retry_if` accepts the error
+    ///
+    /// Waits `initial_ms`, then `initial_ms * multiplier`, and so on between
+    /// attempts. An error rejected by `retry_if` (e.g. a JavaScript syntax
+    /// error) is returned as-is without further attempts.
+    pub async fn with_retry_backoff<F, Fut, T, P>(
+        &self,
+        attempts: u32,
+        initial_ms: u64,
+        multiplier: f64,
+        retry_if: P,
+        operation: F,
+    ) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+        P: Fn(&Error) -> bool,
+    {
+        retry_with_backoff(attempts, initial_ms, multiplier, retry_if, operation).await
     }
     /// Take a debug screenshot and save it with a timestamp
     ///
@@ -972,9 +2480,12 @@ impl Page {
         Ok(filename)
     }
 
-    /// Log the current page state for debugging
+    /// Log the current page state for debugging.
+    ///
+    /// The DOM fields come from a single evaluation, so they are a consistent
+    /// snapshot even while the page is mutating.
     pub async fn debug_state(&self) -> Result<PageState> {
-        let state: PageState = self
+        let mut state: PageState = self
             .evaluate(
                 r#"({
                 url: location.href,
@@ -982,7 +2493,11 @@ impl Page {
                 input_count: document.querySelectorAll('input').length,
                 button_count: document.querySelectorAll('button').length,
                 link_count: document.querySelectorAll('a').length,
-                form_count: document.querySelectorAll('form').length
+                form_count: document.querySelectorAll('form').length,
+                iframe_count: document.querySelectorAll('iframe').length,
+                image_count: document.images.length,
+                script_count: document.scripts.length,
+                ready_state: document.readyState
             })"#,
             )
             .await
@@ -993,24 +2508,50 @@ impl Page {
                 button_count: 0,
                 link_count: 0,
                 form_count: 0,
+                iframe_count: 0,
+                image_count: 0,
+                script_count: 0,
+                ready_state: "unknown".to_string(),
+                dom_node_count: 0,
+                js_heap_used: 0,
             });
+        if let Ok(metrics) = self.performance_metrics().await {
+            state.dom_node_count = metric(&metrics, "Nodes");
+            state.js_heap_used = metric(&metrics, "JSHeapUsedSize");
+        }
         Ok(state)
     }
 
-    /// Upload file(s) to a file input element
+    /// Number of live DOM nodes in the page's renderer (all documents and
+    /// detached nodes included), for spotting leaks in long sessions
+    pub async fn dom_node_count(&self) -> Result<u64> {
+        Ok(metric(&self.performance_metrics().await?, "Nodes"))
+    }
+
+    /// Bytes of JS heap in use
+    pub async fn js_heap_used(&self) -> Result<u64> {
+        Ok(metric(&self.performance_metrics().await?, "JSHeapUsedSize"))
+    }
+
+    async fn performance_metrics(&self) -> Result<Vec<PerformanceMetric>> {
+        self.session.performance_enable().await?;
+        self.session.get_performance_metrics().await
+    }
+
+    /// List the forms on the page with their fields, labels and current
+    /// values, e.g. to plan which fields to fill
+    pub async fn forms(&self) -> Result<Vec<FormInfo>> {
+        self.evaluate(FORMS_JS).await
+    }
+
+    /// Upload a file to a file input element
     pub async fn upload_file(&self, selector: &str, path: &str) -> Result<()> {
         self.upload_files(selector, &[path]).await
     }
 
-    /// Upload multiple files to a file input element
+    /// Upload multiple files to a file input element (needs `multiple` for more than one)
     pub async fn upload_files(&self, selector: &str, paths: &[&str]) -> Result<()> {
-        let element = self.find(selector).await?;
-        self.session
-            .set_file_input_files(
-                element.node_id,
-                paths.iter().map(|p| p.to_string()).collect(),
-            )
-            .await
+        self.find(selector).await?.set_input_files(paths).await
     }
 
     /// Select option by value
@@ -1038,20 +2579,201 @@ impl Page {
         )).await
     }
 
+    /// Set an `<input type=range>` to `value` (snapped to its `step` and
+    /// clamped by the browser) and fire `input` and `change`, like a user
+    /// releasing the thumb there. Use [`Page::drag_slider`] to really drag.
+    pub async fn set_range(&self, selector: &str, value: f64) -> Result<()> {
+        let element = self.find(selector).await?;
+        let result = element
+            .call_on_element(SET_RANGE_JS, &[value.into()])
+            .await?;
+        if result.is_null() {
+            return Err(Error::InvalidState(format!(
+                "'{}' is not a range input",
+                selector
+            )));
+        }
+        Ok(())
+    }
+
+    /// Drag a slider to `to_fraction` (0.0 = left end, 1.0 = right end) with
+    /// real mouse events: press on the thumb, move (human path when
+    /// `human_mouse` is on), release.
+    ///
+    /// `selector` is either an `<input type=range>` or the handle of a custom
+    /// slider, whose parent element is taken as the track. Only horizontal
+    /// sliders are supported.
+    pub async fn drag_slider(&self, selector: &str, to_fraction: f64) -> Result<()> {
+        let element = self.find(selector).await?;
+        // Mouse events outside the viewport never reach the slider
+        element.scroll_into_view_if_needed().await?;
+        let geometry: SliderGeometry =
+            serde_json::from_value(element.call_on_element(SLIDER_GEOMETRY_JS, &[]).await?)?;
+        let to_fraction = to_fraction.clamp(0.0, 1.0);
+
+        let (y, start_x, end_x) = match geometry {
+            SliderGeometry::Range { fraction } => {
+                // The thumb's center travels the track inset by half its
+                // width; Chrome's thumbs are about as wide as the input is tall
+                let bbox = element.bounding_box().await.ok_or(Error::NodeNotVisible)?;
+                let thumb = bbox.height.min(bbox.width);
+                let at = |f: f64| bbox.x + thumb / 2.0 + f * (bbox.width - thumb);
+                (bbox.y + bbox.height / 2.0, at(fraction), at(to_fraction))
+            }
+            SliderGeometry::Handle {
+                handle_x,
+                handle_width,
+                track_x,
+                track_width,
+            } => {
+                // Client rects are border boxes: scale them into viewport
+                // space with the handle's border box, not its content box
+                let bbox = element.border_box().await.ok_or(Error::NodeNotVisible)?;
+                let scale = if handle_width > 0.0 {
+                    bbox.width / handle_width
+                } else {
+                    1.0
+                };
+                let track_left = bbox.x + (track_x - handle_x) * scale;
+                let travel = track_width * scale - bbox.width;
+                (
+                    bbox.y + bbox.height / 2.0,
+                    bbox.x + bbox.width / 2.0,
+                    track_left + bbox.width / 2.0 + to_fraction * travel,
+                )
+            }
+        };
+
+        self.mouse_move(start_x, y).await?;
+        self.mouse_down(MouseButton::Left).await?;
+        self.interaction_pause().await;
+        self.mouse_move(end_x, y).await?;
+        self.interaction_pause().await;
+        self.mouse_up(MouseButton::Left).await
+    }
+
     /// Hover over element (for revealing menus)
     pub async fn hover(&self, selector: &str) -> Result<()> {
-        let (x, y) = self.find(selector).await?.center().await?;
+        self.find(selector).await?.hover().await
+    }
+
+    /// Hover over element and stay there for `dwell_ms`
+    pub async fn hover_for(&self, selector: &str, dwell_ms: u64) -> Result<()> {
+        self.find(selector).await?.hover_for(dwell_ms).await
+    }
+
+    /// Scroll down a viewport at a time for infinite-scroll pages: after each
+    /// scroll, wait up to `settle_ms` for the document to grow. Stops once
+    /// the bottom is reached and nothing new loads, or after `max_scrolls`.
+    ///
+    /// Scrolls with mouse-wheel events, human-paced when `human_mouse` is on.
+    /// Returns the number of scrolls performed.
+    pub async fn scroll_to_bottom(&self, max_scrolls: usize, settle_ms: u64) -> Result<usize> {
+        const METRICS_JS: &str = "[document.documentElement.scrollHeight, window.scrollY + window.innerHeight, window.innerWidth, window.innerHeight]";
+        let poll = std::time::Duration::from_millis(settle_ms.clamp(1, 100));
+
+        let mut scrolls = 0;
+        while scrolls < max_scrolls {
+            let (height, _, width, viewport): (f64, f64, f64, f64) =
+                self.evaluate(METRICS_JS).await?;
+
+            if self.config.human_mouse {
+                self.human().scroll(viewport).await?;
+            } else {
+                self.session
+                    .dispatch_mouse_wheel(width / 2.0, viewport / 2.0, 0.0, viewport)
+                    .await?;
+            }
+            scrolls += 1;
+
+            // Wait for lazy-loaded content to extend the page
+            let start = std::time::Instant::now();
+            let (grown, at_bottom) = loop {
+                let (new_height, bottom, _, _): (f64, f64, f64, f64) =
+                    self.evaluate(METRICS_JS).await?;
+                if new_height > height {
+                    break (true, false);
+                }
+                if start.elapsed().as_millis() as u64 >= settle_ms {
+                    break (false, bottom >= new_height - 1.0);
+                }
+                tokio::time::sleep(poll).await;
+            };
+            if !grown && at_bottom {
+                break;
+            }
+        }
+        Ok(scrolls)
+    }
+
+    /// Where the next human move starts: the last known mouse position, or a
+    /// random point before the mouse has moved
+    fn mouse_origin(&self) -> (f64, f64) {
+        self.mouse
+            .lock()
+            .unwrap()
+            .unwrap_or_else(crate::stealth::human::random_start)
+    }
+
+    /// Move the mouse to `(x, y)`: a Bezier path from the last known position
+    /// when `human_mouse` is on, otherwise a single `mouseMoved`
+    pub(crate) async fn move_mouse(&self, x: f64, y: f64) -> Result<()> {
+        let last = *self.mouse.lock().unwrap();
+        let held = *self.held_button.lock().unwrap();
+        let human = match held {
+            Some(button) => self.human().with_button(button),
+            None => self.human(),
+        };
+        match last {
+            Some(start) if self.config.human_mouse => human.move_from(start, x, y).await?,
+            None if self.config.human_mouse => human.move_to(x, y).await?,
+            _ => {
+                self.session
+                    .dispatch_mouse_event(MouseEventType::MouseMoved, x, y, held, None)
+                    .await?
+            }
+        }
+        *self.mouse.lock().unwrap() = Some((x, y));
+        Ok(())
+    }
+
+    /// Move the mouse to `(x, y)` (CSS pixels, like [`Page::click_at`]),
+    /// along a human path when `human_mouse` is on. While a button is held
+    /// with [`Page::mouse_down`] the moves report it, so this drags.
+    pub async fn mouse_move(&self, x: f64, y: f64) -> Result<()> {
+        self.move_mouse(x, y).await
+    }
+
+    /// Press `button` at the current mouse position (the viewport origin if
+    /// the mouse hasn't moved yet). Pair with [`Page::mouse_up`] to build
+    /// gestures like slider drags or canvas strokes.
+    pub async fn mouse_down(&self, button: MouseButton) -> Result<()> {
+        let (x, y) = self.mouse.lock().unwrap().unwrap_or((0.0, 0.0));
         self.session
-            .dispatch_mouse_event(MouseEventType::MouseMoved, x, y, None, None)
+            .dispatch_mouse_event(MouseEventType::MousePressed, x, y, Some(button), Some(1))
+            .await?;
+        *self.held_button.lock().unwrap() = Some(button);
+        Ok(())
+    }
+
+    /// Release `button` at the current mouse position
+    pub async fn mouse_up(&self, button: MouseButton) -> Result<()> {
+        let (x, y) = self.mouse.lock().unwrap().unwrap_or((0.0, 0.0));
+        *self.held_button.lock().unwrap() = None;
+        self.session
+            .dispatch_mouse_event(MouseEventType::MouseReleased, x, y, Some(button), Some(1))
             .await
     }
 
     /// Human-like hover with Bezier curve movement
     pub async fn human_hover(&self, selector: &str) -> Result<()> {
+        self.auto_warm_up().await?;
         let element = self.find(selector).await?;
         element.scroll_into_view().await?;
         let (x, y) = element.center().await?;
-        Human::new(&self.session).move_to(x, y).await?;
+        let start = self.mouse_origin();
+        Human::new(&self.session).move_from(start, x, y).await?;
+        *self.mouse.lock().unwrap() = Some((x, y));
         tokio::time::sleep(std::time::Duration::from_millis(100)).await;
         Ok(())
     }
@@ -1063,20 +2785,28 @@ impl Page {
         let (mods, key_name) = parse_key_combo(key);
         let (key_str, code_str, vk) = key_to_codes(key_name);
         let modifiers = if mods != 0 { Some(mods) } else { None };
+        let text = key_text(key_str, mods);
 
+        // Keys that produce text go out as keyDown (fires keypress/input),
+        // the rest as rawKeyDown like a real keyboard
         self.session
             .dispatch_key_event_full(InputDispatchKeyEventFull {
-                r#type: KeyEventType::KeyDown,
+                r#type: if text.is_some() {
+                    KeyEventType::KeyDown
+                } else {
+                    KeyEventType::RawKeyDown
+                },
                 modifiers,
+                text: text.map(Into::into),
+                unmodified_text: text.map(Into::into),
                 key: Some(key_str.into()),
                 code: Some(code_str.into()),
                 windows_virtual_key_code: vk,
                 native_virtual_key_code: vk,
-                ..Default::default()
             })
             .await?;
 
-        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        self.interaction_pause().await;
 
         self.session
             .dispatch_key_event_full(InputDispatchKeyEventFull {
@@ -1101,6 +2831,21 @@ impl Page {
         .await
     }
 
+    /// The currently selected text: the selection inside the focused
+    /// input/textarea if it has one, else `window.getSelection()`
+    pub async fn selection(&self) -> Result<String> {
+        self.evaluate(
+            r#"(() => {
+                const el = document.activeElement;
+                if (el && typeof el.selectionStart === 'number' && el.selectionEnd > el.selectionStart) {
+                    return el.value.slice(el.selectionStart, el.selectionEnd);
+                }
+                return window.getSelection().toString();
+            })()"#,
+        )
+        .await
+    }
+
     /// Platform-aware copy (Cmd+C on Mac, Ctrl+C elsewhere)
     pub async fn copy(&self) -> Result<()> {
         self.press_key(if cfg!(target_os = "macos") {
@@ -1120,6 +2865,80 @@ impl Page {
         })
         .await
     }
+
+    /// Press Tab `n` times and return the element that ends up focused
+    pub async fn tab(&self, n: usize) -> Result<Element<'_>> {
+        self.tab_with("Tab", n).await
+    }
+
+    /// Press Shift+Tab `n` times and return the element that ends up focused
+    pub async fn shift_tab(&self, n: usize) -> Result<Element<'_>> {
+        self.tab_with("Shift+Tab", n).await
+    }
+
+    /// Press `key` `n` times with human-like gaps, then report focus
+    async fn tab_with(&self, key: &str, n: usize) -> Result<Element<'_>> {
+        for i in 0..n {
+            if i > 0 {
+                if self.config.human_typing {
+                    self.human().pause_between_keys().await;
+                } else {
+                    self.interaction_pause().await;
+                }
+            }
+            self.press_key(key).await?;
+        }
+
+        self.focused_element()
+            .await?
+            .ok_or_else(|| Error::ElementNotFound(format!("focused element after {} x {}", n, key)))
+    }
+
+    /// Write text to the system clipboard (grants clipboard permission for the
+    /// page's origin first)
+    pub async fn set_clipboard(&self, text: &str) -> Result<()> {
+        self.grant_clipboard().await?;
+        let js = format!(
+            "navigator.clipboard.writeText({}).then(() => true)",
+            serde_json::to_string(text)?
+        );
+        self.clipboard_call::<bool>(&js).await?;
+        Ok(())
+    }
+
+    /// Read text from the system clipboard (grants clipboard permission for the
+    /// page's origin first)
+    pub async fn get_clipboard(&self) -> Result<String> {
+        self.grant_clipboard().await?;
+        self.clipboard_call("navigator.clipboard.readText()").await
+    }
+
+    /// Grant clipboard access to the current origin and focus the page,
+    /// since the Clipboard API rejects unfocused documents
+    async fn grant_clipboard(&self) -> Result<()> {
+        let origin: String = self.evaluate("location.origin").await?;
+        if origin == "null" {
+            return Err(Error::PermissionDenied(
+                "clipboard needs a page with a real origin (http/https), not an opaque one like data: or about:blank".into(),
+            ));
+        }
+        self.session
+            .grant_permissions(&origin, &["clipboardReadWrite", "clipboardSanitizedWrite"])
+            .await?;
+        self.session.bring_to_front().await
+    }
+
+    /// Evaluate a Clipboard API call, turning rejections into `PermissionDenied`
+    async fn clipboard_call<T: serde::de::DeserializeOwned>(&self, js: &str) -> Result<T> {
+        match self.evaluate(js).await {
+            Err(Error::JavaScriptException { text, .. })
+                if text.contains("NotAllowedError") || text.contains("denied") =>
+            {
+                Err(Error::PermissionDenied(format!("clipboard: {}", text)))
+            }
+            result => result,
+        }
+    }
 }
 
 fn parse_key_combo(combo: &str) -> (i32, &str) {
@@ -1187,6 +3006,16 @@ fn key_to_codes(key: &str) -> (&str, &str, Option<i32>) {
         ("x", "x", "KeyX", 88),
         ("y", "y", "KeyY", 89),
         ("z", "z", "KeyZ", 90),
+        ("0", "0", "Digit0", 48),
+        ("1", "1", "Digit1", 49),
+        ("2", "2", "Digit2", 50),
+        ("3", "3", "Digit3", 51),
+        ("4", "4", "Digit4", 52),
+        ("5", "5", "Digit5", 53),
+        ("6", "6", "Digit6", 54),
+        ("7", "7", "Digit7", 55),
+        ("8", "8", "Digit8", 56),
+        ("9", "9", "Digit9", 57),
         ("f1", "F1", "F1", 112),
         ("f2", "F2", "F2", 113),
         ("f3", "F3", "F3", 114),
@@ -1207,6 +3036,209 @@ fn key_to_codes(key: &str) -> (&str, &str, Option<i32>) {
         .unwrap_or((key, key, None))
 }
 
+/// Decode one base64 chunk into `writer`, returning the decoded length
+fn write_base64<W: std::io::Write>(chunk: &str, writer: &mut W) -> Result<usize> {
+    use base64::Engine;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(chunk)
+        .map_err(|e| Error::Decode(e.to_string()))?;
+    writer.write_all(&bytes)?;
+    Ok(bytes.len())
+}
+
+/// Join cookies into a `Cookie` header, longer paths first like browsers
+/// send them (RFC 6265 section 5.4)
+fn cookie_header(mut cookies: Vec<Cookie>) -> String {
+    cookies.sort_by_key(|c| std::cmp::Reverse(c.path.len()));
+    cookies
+        .iter()
+        .map(|c| format!("{}={}", c.name, c.value))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Next event from `rx`, skipping over lag; fails once the session is gone
+async fn recv_event(
+    rx: &mut tokio::sync::broadcast::Receiver<serde_json::Value>,
+) -> Result<serde_json::Value> {
+    loop {
+        match rx.recv().await {
+            Ok(params) => return Ok(params),
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                return Err(Error::SessionClosed)
+            }
+        }
+    }
+}
+
+/// Like `recv_event`, but fails instead of skipping events dropped by lag
+async fn recv_event_lossless(
+    rx: &mut tokio::sync::broadcast::Receiver<serde_json::Value>,
+) -> Result<serde_json::Value> {
+    match rx.recv().await {
+        Ok(params) => Ok(params),
+        Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => Err(Error::CdpSimple(format!(
+            "Fell behind the event stream, {} events dropped",
+            n
+        ))),
+        Err(tokio::sync::broadcast::error::RecvError::Closed) => Err(Error::SessionClosed),
+    }
+}
+
+/// Shared loop behind `Page::with_retry` and `Page::with_retry_backoff`
+async fn retry_with_backoff<F, Fut, T, P>(
+    attempts: u32,
+    initial_ms: u64,
+    multiplier: f64,
+    retry_if: P,
+    operation: F,
+) -> Result<T>
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+    P: Fn(&Error) -> bool,
+{
+    let mut last_error = String::new();
+
+    for attempt in 1..=attempts {
+        match operation().await {
+            Ok(result) => return Ok(result),
+            Err(e) if !retry_if(&e) => return Err(e),
+            Err(e) => {
+                last_error = e.to_string();
+                if attempt < attempts {
+                    let delay = backoff_delay(initial_ms, multiplier, attempt - 1);
+                    tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+                }
+            }
+        }
+    }
+
+    Err(Error::RetryExhausted {
+        attempts,
+        last_error,
+    })
+}
+
+/// Delay before retry number `retry` (0-based): `initial_ms * multiplier^retry`
+fn backoff_delay(initial_ms: u64, multiplier: f64, retry: u32) -> u64 {
+    (initial_ms as f64 * multiplier.powi(retry as i32)) as u64
+}
+
+/// Absolute path of `path` if it is a regular, readable file
+fn readable_file(path: &str) -> Result<String> {
+    let unreadable = |reason: String| Error::FileNotReadable {
+        path: path.to_string(),
+        reason,
+    };
+    let absolute = std::fs::canonicalize(path).map_err(|e| unreadable(e.to_string()))?;
+    if !absolute.is_file() {
+        return Err(unreadable("not a regular file".into()));
+    }
+    std::fs::File::open(&absolute).map_err(|e| unreadable(e.to_string()))?;
+    Ok(absolute.to_string_lossy().into_owned())
+}
+
+/// Evaluates to the innermost elements containing `text` (case-insensitive)
+fn deepest_text_matches_js(text: &str) -> Result<String> {
+    // JSON keeps the needle a plain string literal; lowercase it in JS so
+    // escaping and case folding can't interfere with each other
+    let needle = serde_json::to_string(text)?;
+
+    Ok(format!(
+        r#"
+        (() => {{
+            const needle = {needle}.toLowerCase();
+            const selectors = 'a, button, input, label, span, div, p, h1, h2, h3, h4, h5, h6, li, td, th';
+            const elements = document.querySelectorAll(selectors);
+            const matches = [];
+            for (const el of elements) {{
+                const t = (el.innerText || el.textContent || el.value || '').toLowerCase();
+                if (t.includes(needle)) {{
+                    matches.push(el);
+                }}
+            }}
+            return matches.filter(el => !matches.some(other => el !== other && el.contains(other)));
+        }})()"#,
+        needle = needle
+    ))
+}
+
+/// `(key, code, virtual key code, needs Shift)` for typing `ch` on a US
+/// layout. Characters off the layout keep their key and get no code.
+fn char_to_codes(ch: char) -> (String, String, Option<i32>, bool) {
+    static SYMBOLS: &[(char, char, &str, i32)] = &[
+        ('-', '_', "Minus", 189),
+        ('=', '+', "Equal", 187),
+        ('[', '{', "BracketLeft", 219),
+        (']', '}', "BracketRight", 221),
+        ('\\', '|', "Backslash", 220),
+        (';', ':', "Semicolon", 186),
+        ('\'', '"', "Quote", 222),
+        (',', '<', "Comma", 188),
+        ('.', '>', "Period", 190),
+        ('/', '?', "Slash", 191),
+        ('`', '~', "Backquote", 192),
+        ('1', '!', "Digit1", 49),
+        ('2', '@', "Digit2", 50),
+        ('3', '#', "Digit3", 51),
+        ('4', '$', "Digit4", 52),
+        ('5', '%', "Digit5", 53),
+        ('6', '^', "Digit6", 54),
+        ('7', '&', "Digit7", 55),
+        ('8', '*', "Digit8", 56),
+        ('9', '(', "Digit9", 57),
+        ('0', ')', "Digit0", 48),
+    ];
+
+    if ch == '\n' {
+        return ("Enter".into(), "Enter".into(), Some(13), false);
+    }
+    if ch.is_ascii_alphabetic() {
+        let upper = ch.to_ascii_uppercase();
+        let code = format!("Key{}", upper);
+        return (ch.into(), code, Some(upper as i32), ch.is_ascii_uppercase());
+    }
+    if ch == ' ' {
+        return (" ".into(), "Space".into(), Some(32), false);
+    }
+    SYMBOLS
+        .iter()
+        .find_map(|&(plain, shifted, code, vk)| {
+            if ch == plain {
+                Some((ch.into(), code.into(), Some(vk), false))
+            } else if ch == shifted {
+                Some((ch.into(), code.into(), Some(vk), true))
+            } else {
+                None
+            }
+        })
+        .unwrap_or((ch.into(), String::new(), None, false))
+}
+
+/// Text a key press types, if any: Enter types `\r`, printable keys type
+/// themselves unless Ctrl/Alt/Cmd turn the press into a shortcut
+fn key_text(key: &str, mods: i32) -> Option<&str> {
+    use crate::cdp::types::modifiers;
+    if mods & (modifiers::CTRL | modifiers::ALT | modifiers::META) != 0 {
+        return None;
+    }
+    match key {
+        "Enter" => Some("\r"),
+        k if k.chars().count() == 1 => Some(k),
+        _ => None,
+    }
+}
+
+impl Drop for Page {
+    fn drop(&mut self) {
+        if let Some(task) = self.interceptor.lock().unwrap().take() {
+            task.abort();
+        }
+    }
+}
+
 /// A captured HTTP request with its response
 #[derive(Debug, Clone)]
 pub struct CapturedRequest {
@@ -1260,8 +3292,10 @@ pub struct FrameInfo {
     pub name: Option<String>,
 }
 
-/// Debug information about page state
+/// Debug information about page state. More fields may be added, so it
+/// can't be built or destructured exhaustively outside this crate.
 #[derive(Debug, Clone, serde::Deserialize)]
+#[non_exhaustive]
 pub struct PageState {
     pub url: String,
     pub title: String,
@@ -1269,10 +3303,331 @@ pub struct PageState {
     pub button_count: u32,
     pub link_count: u32,
     pub form_count: u32,
+    pub iframe_count: u32,
+    pub image_count: u32,
+    pub script_count: u32,
+    /// `document.readyState` (`loading`, `interactive` or `complete`)
+    pub ready_state: String,
+    /// See [`Page::dom_node_count`]
+    #[serde(default)]
+    pub dom_node_count: u64,
+    /// See [`Page::js_heap_used`]
+    #[serde(default)]
+    pub js_heap_used: u64,
+}
+
+/// Value of a `Performance.getMetrics` entry, 0 if missing
+fn metric(metrics: &[PerformanceMetric], name: &str) -> u64 {
+    metrics
+        .iter()
+        .find(|m| m.name == name)
+        .map_or(0, |m| m.value as u64)
+}
+
+/// Element state polled by `Element::wait_for_*`
+#[derive(Debug, Clone, Copy)]
+enum ElementState {
+    Enabled,
+    Checked,
+    Unchecked,
+}
+
+impl ElementState {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Enabled => "enabled",
+            Self::Checked => "checked",
+            Self::Unchecked => "unchecked",
+        }
+    }
+}
+
+/// UTF-16 code units fetched per [`Page::content_to_writer`] round trip.
+/// Documents larger than this are where `content()`'s single message starts
+/// to hold up the connection.
+const CONTENT_CHUNK_CHARS: usize = 1 << 20;
+
+/// Returns `[slice, next offset]` of the `String` object `this` from
+/// `start`, at most `size` UTF-16 units, never splitting a surrogate pair
+const CONTENT_CHUNK_JS: &str = r#"function(start, size) {
+    let end = Math.min(start + size, this.length);
+    if (end < this.length && (this.charCodeAt(end - 1) & 0xFC00) === 0xD800) end--;
+    return [this.slice(start, end), end];
+}"#;
+
+/// Marks `this` for printing, hides everything else in print media and
+/// returns the element's `[width, height]` in CSS pixels
+const PDF_ISOLATE_JS: &str = r#"(() => {
+    const rect = this.getBoundingClientRect();
+    this.setAttribute('data-eoka-pdf', '');
+    const style = document.createElement('style');
+    style.id = 'eoka-pdf-style';
+    style.textContent = `@media print {
+        @page { margin: 0; }
+        html, body { margin: 0 !important; padding: 0 !important; }
+        body * { visibility: hidden !important; }
+        [data-eoka-pdf], [data-eoka-pdf] * { visibility: visible !important; }
+        [data-eoka-pdf] {
+            position: fixed !important; left: 0 !important; top: 0 !important;
+            margin: 0 !important; box-sizing: border-box !important;
+            width: ${rect.width}px !important; height: ${rect.height}px !important;
+        }
+    }`;
+    document.head.appendChild(style);
+    return [rect.width, rect.height];
+})()"#;
+
+/// Undoes [`PDF_ISOLATE_JS`]
+const PDF_RESTORE_JS: &str = r#"
+    document.querySelectorAll('[data-eoka-pdf]').forEach(el => el.removeAttribute('data-eoka-pdf'));
+    document.getElementById('eoka-pdf-style')?.remove();
+"#;
+
+/// Text selected inside `this`; document ranges are clipped to its contents
+const ELEMENT_SELECTION_JS: &str = r#"(() => {
+    if (typeof this.selectionStart === 'number') {
+        return this.value.slice(this.selectionStart, this.selectionEnd);
+    }
+    const selection = this.ownerDocument.getSelection();
+    const bounds = this.ownerDocument.createRange();
+    bounds.selectNodeContents(this);
+    let text = '';
+    for (let i = 0; i < selection.rangeCount; i++) {
+        const range = selection.getRangeAt(i).cloneRange();
+        if (!range.intersectsNode(this)) continue;
+        if (range.compareBoundaryPoints(Range.START_TO_START, bounds) < 0) {
+            range.setStart(bounds.startContainer, bounds.startOffset);
+        }
+        if (range.compareBoundaryPoints(Range.END_TO_END, bounds) > 0) {
+            range.setEnd(bounds.endContainer, bounds.endOffset);
+        }
+        text += range.toString();
+    }
+    return text;
+})()"#;
+
+/// Focuses `this` and selects all of it: `select()` for text fields, a
+/// document range over the contents for anything else
+const SELECT_TEXT_JS: &str = r#"(() => {
+    this.focus();
+    if (typeof this.select === 'function') {
+        this.select();
+    } else {
+        const range = this.ownerDocument.createRange();
+        range.selectNodeContents(this);
+        const selection = this.ownerDocument.getSelection();
+        selection.removeAllRanges();
+        selection.addRange(range);
+    }
+    return true;
+})()"#;
+
+/// Walks the main content collecting text blocks, see [`Page::readable_text`]
+const READABLE_TEXT_JS: &str = r#"(() => {
+    // Compared against `localName`: lowercase for HTML and SVG alike
+    const SKIP_TAGS = new Set(['script', 'style', 'noscript', 'template', 'svg', 'canvas', 'iframe',
+        'nav', 'footer', 'aside', 'form', 'button', 'select', 'textarea', 'input', 'dialog']);
+    const SKIP_ROLES = new Set(['navigation', 'banner', 'contentinfo', 'complementary', 'search',
+        'menu', 'menubar', 'dialog']);
+    const INLINE = new Set(['a', 'abbr', 'b', 'bdi', 'bdo', 'cite', 'code', 'data', 'dfn', 'em', 'i',
+        'kbd', 'label', 'mark', 'q', 's', 'samp', 'small', 'span', 'strong', 'sub', 'sup', 'time',
+        'u', 'var', 'td', 'th']);
+
+    const articles = document.querySelectorAll('article');
+    let root = document.querySelector('main, [role="main"]')
+        || (articles.length === 1 ? articles[0] : document.body);
+    if (!root) return '';
+    // ASP.NET WebForms and the like wrap the whole page in one <form>: that
+    // form holds the content, so read from inside it instead of skipping it.
+    // Other forms are still skipped.
+    const textLength = el => (el.innerText || '').length;
+    const wrapper = Array.from(root.querySelectorAll('form'))
+        .find(form => textLength(form) > textLength(root) / 2);
+    if (wrapper) root = wrapper;
+
+    const blocks = [];
+    let line = '';
+    // Marker for the next non-empty block (heading level, list bullet)
+    let prefix = '';
+    const flush = () => {
+        const text = line.replace(/\s+/g, ' ').trim();
+        line = '';
+        if (!text) return;
+        blocks.push(prefix + text);
+        prefix = '';
+    };
+    const skipped = el => {
+        if (SKIP_TAGS.has(el.localName) || SKIP_ROLES.has(el.getAttribute('role'))) return true;
+        // A page header, not an article's own
+        if (el.localName === 'header' && !el.closest('article')) return true;
+        if (el.hidden || el.getAttribute('aria-hidden') === 'true') return true;
+        const style = getComputedStyle(el);
+        return style.display === 'none' || style.visibility === 'hidden';
+    };
+
+    const walk = (node, depth) => {
+        for (const child of node.childNodes) {
+            if (child.nodeType === Node.TEXT_NODE) {
+                line += child.textContent;
+                continue;
+            }
+            if (child.nodeType !== Node.ELEMENT_NODE || skipped(child)) continue;
+
+            const tag = child.localName;
+            const heading = /^h([1-6])$/.exec(tag);
+            if (INLINE.has(tag)) {
+                if (tag === 'td' || tag === 'th') line += ' ';
+                walk(child, depth);
+            } else if (tag === 'br') {
+                flush();
+            } else if (tag === 'pre') {
+                flush();
+                const code = child.textContent.replace(/\s+$/, '');
+                if (code) blocks.push(code);
+            } else if (heading) {
+                flush();
+                walk(child, depth);
+                prefix = '#'.repeat(Number(heading[1])) + ' ';
+                flush();
+                prefix = '';
+            } else if (tag === 'li') {
+                flush();
+                const list = child.parentElement;
+                const marker = list && list.localName === 'ol'
+                    ? (Array.from(list.children).filter(c => c.localName === 'li').indexOf(child) + 1) + '. '
+                    : '- ';
+                prefix = '  '.repeat(depth) + marker;
+                walk(child, depth + 1);
+                flush();
+                prefix = '';
+            } else if (tag === 'blockquote') {
+                flush();
+                const start = blocks.length;
+                walk(child, depth);
+                flush();
+                for (let i = start; i < blocks.length; i++) blocks[i] = '> ' + blocks[i];
+            } else {
+                flush();
+                walk(child, depth);
+                flush();
+            }
+        }
+    };
+
+    walk(root, 0);
+    flush();
+    return blocks.join('\n\n');
+})()"#;
+
+/// Calls `requestSubmit()` on the form `this` is or belongs to; returns
+/// `submitted`, `unsupported` (no `requestSubmit`) or `null` (no form)
+const SUBMIT_FORM_JS: &str = r#"(() => {
+    const form = this instanceof HTMLFormElement ? this : this.form || this.closest('form');
+    if (!form) return null;
+    if (typeof form.requestSubmit !== 'function') return 'unsupported';
+    form.requestSubmit();
+    return 'submitted';
+})()"#;
+
+/// The submit button of the form `this` is or belongs to, including buttons
+/// attached with `form=`; `null` if there is no form or no such button
+const SUBMIT_BUTTON_JS: &str = r#"function() {
+    const form = this instanceof HTMLFormElement ? this : this.form || this.closest('form');
+    if (!form) return null;
+    return Array.from(form.elements).find(c => c.type === 'submit') || null;
+}"#;
+
+/// Replaces `this` with the parsed `html` and returns the first element
+/// among the inserted nodes, or `null`
+const SET_OUTER_HTML_JS: &str = r#"function(html) {
+    const parent = this.parentNode;
+    if (!parent) throw new Error('element is detached');
+    const before = this.previousSibling;
+    const after = this.nextSibling;
+    this.outerHTML = html;
+    for (let node = before ? before.nextSibling : parent.firstChild; node && node !== after; node = node.nextSibling) {
+        if (node.nodeType === Node.ELEMENT_NODE) return node;
+    }
+    return null;
+}"#;
+
+/// Sets a range input's value through the native setter (so framework
+/// value tracking notices) and fires `input` and `change`; `null` if `this`
+/// isn't a range input
+const SET_RANGE_JS: &str = r#"function(value) {
+    if (this.type !== 'range') return null;
+    Object.getOwnPropertyDescriptor(HTMLInputElement.prototype, 'value').set.call(this, String(value));
+    this.dispatchEvent(new Event('input', { bubbles: true }));
+    this.dispatchEvent(new Event('change', { bubbles: true }));
+    return this.value;
+}"#;
+
+/// [`SliderGeometry`] of `this`
+const SLIDER_GEOMETRY_JS: &str = r#"function() {
+    if (this.type === 'range') {
+        const min = this.min === '' ? 0 : parseFloat(this.min);
+        const max = this.max === '' ? 100 : parseFloat(this.max);
+        const fraction = max > min ? (parseFloat(this.value) - min) / (max - min) : 0;
+        return { kind: 'range', fraction };
+    }
+    const handle = this.getBoundingClientRect();
+    const track = (this.parentElement || this).getBoundingClientRect();
+    return {
+        kind: 'handle',
+        handle_x: handle.x,
+        handle_width: handle.width,
+        track_x: track.x,
+        track_width: track.width,
+    };
+}"#;
+
+/// Centers the element in its viewport without animating, unless it is
+/// already fully visible (or not rendered at all)
+const SCROLL_INTO_VIEW_IF_NEEDED_JS: &str = r#"function() {
+    const rect = this.getBoundingClientRect();
+    const view = this.ownerDocument.defaultView;
+    const visible = rect.top >= 0 && rect.left >= 0
+        && rect.bottom <= view.innerHeight && rect.right <= view.innerWidth;
+    if (!visible) {
+        this.scrollIntoView({ block: 'center', inline: 'center', behavior: 'instant' });
+    }
+    return !visible;
+}"#;
+
+/// Waits (up to ~1s) until the scroll position stops changing for a few
+/// frames, then jumps back to `(x, y)` without smooth scrolling
+const WARM_UP_RESTORE_JS: &str = r#"(x, y) => new Promise(resolve => {
+    let last = -1, still = 0, frames = 0;
+    const step = () => {
+        still = scrollY === last ? still + 1 : 0;
+        last = scrollY;
+        if (still >= 3 || ++frames > 60) {
+            scrollTo({ left: x, top: y, behavior: 'instant' });
+            resolve(true);
+        } else {
+            setTimeout(step, 16);
+        }
+    };
+    setTimeout(step, 16);
+})"#;
+
+/// Where a slider's thumb is, for [`Page::drag_slider`]
+#[derive(serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum SliderGeometry {
+    /// Native range input; `fraction` is the current value's position
+    Range { fraction: f64 },
+    /// Custom slider handle and its track, as client rects
+    Handle {
+        handle_x: f64,
+        handle_width: f64,
+        track_x: f64,
+        track_width: f64,
+    },
 }
 
 /// Bounding box of an element
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct BoundingBox {
     pub x: f64,
     pub y: f64,
@@ -1289,14 +3644,120 @@ impl BoundingBox {
 /// An element on the page (holds a CDP node_id, can become stale on DOM changes)
 pub struct Element<'a> {
     page: &'a Page,
+    /// Session the node id belongs to: the page's, or an out-of-process frame's
+    session: &'a Session,
     node_id: i32,
+    /// Offset of the owning frame's viewport in page coordinates
+    origin: (f64, f64),
 }
 
 impl<'a> Element<'a> {
-    /// Get the element's center coordinates
+    /// Wrap a node id from the page's own session
+    fn new(page: &'a Page, node_id: i32) -> Self {
+        Self::in_session(page, &page.session, node_id, (0.0, 0.0))
+    }
+
+    /// Wrap a node id from another session (out-of-process frame) whose box
+    /// model coordinates are offset by `origin` within the page
+    pub(crate) fn in_session(
+        page: &'a Page,
+        session: &'a Session,
+        node_id: i32,
+        origin: (f64, f64),
+    ) -> Self {
+        Self {
+            page,
+            session,
+            node_id,
+            origin,
+        }
+    }
+
+    /// The CDP node id (scoped to the owning session)
+    pub(crate) fn node_id(&self) -> i32 {
+        self.node_id
+    }
+
+    /// Find the first descendant matching a CSS selector
+    pub async fn find(&self, selector: &str) -> Result<Element<'a>> {
+        let node_id = self.session.query_selector(self.node_id, selector).await?;
+        if node_id == 0 {
+            return Err(Error::ElementNotFound(selector.to_string()));
+        }
+        Ok(self.child(node_id))
+    }
+
+    /// Find all descendants matching a CSS selector
+    pub async fn find_all(&self, selector: &str) -> Result<Vec<Element<'a>>> {
+        let node_ids = self
+            .session
+            .query_selector_all(self.node_id, selector)
+            .await?;
+        Ok(node_ids
+            .into_iter()
+            .filter(|&id| id != 0)
+            .map(|node_id| self.child(node_id))
+            .collect())
+    }
+
+    /// Wait for a descendant matching a CSS selector to appear, e.g. content
+    /// loading into a specific card or row. Fails right away if this element
+    /// itself is gone from the DOM.
+    pub async fn wait_for(&self, selector: &str, timeout_ms: u64) -> Result<Element<'a>> {
+        let start = std::time::Instant::now();
+        let timeout = std::time::Duration::from_millis(timeout_ms);
+
+        loop {
+            match self.find(selector).await {
+                Err(Error::ElementNotFound(_)) => {}
+                result => return result,
+            }
+
+            if start.elapsed() > timeout {
+                let description = self.describe().await;
+                return Err(Error::Timeout(format!(
+                    "Element '{}' not found in <{}> within {}ms",
+                    selector, description, timeout_ms
+                )));
+            }
+
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    }
+
+    /// Wrap a node id from the same session and frame as this element
+    fn child(&self, node_id: i32) -> Element<'a> {
+        Element::in_session(self.page, self.session, node_id, self.origin)
+    }
+
+    /// Focus the element and select everything in it, so typing replaces it.
+    /// Inputs and textareas select their value; other elements (e.g.
+    /// `contenteditable`) get a document selection spanning their contents.
+    pub async fn select_text(&self) -> Result<()> {
+        self.eval_on_element(SELECT_TEXT_JS).await?;
+        Ok(())
+    }
+
+    /// Selected text within this element: the field's own selection for
+    /// inputs/textareas, else the part of the document selection inside it
+    pub async fn selection(&self) -> Result<String> {
+        let value = self.eval_on_element(ELEMENT_SELECTION_JS).await?;
+        Ok(value.as_str().unwrap_or_default().to_string())
+    }
+
+    /// Empty the field with real key events: select everything, then
+    /// Backspace. Fires `keydown`/`input` like a user clearing it by hand.
+    pub async fn clear(&self) -> Result<()> {
+        self.select_text().await?;
+        self.page.press_key("Backspace").await
+    }
+
+    /// Get the element's center coordinates, in CSS pixels like
+    /// [`Element::bounding_box`], ready for [`Page::click_at`]
     pub async fn center(&self) -> Result<(f64, f64)> {
-        let model = self.page.session.get_box_model(self.node_id).await?;
-        Ok(model.center())
+        let model = self.session.get_box_model(self.node_id).await?;
+        let (x, y) = model.center();
+        Ok((x + self.origin.0, y + self.origin.1))
     }
 
     /// Click this element
@@ -1305,27 +3766,133 @@ impl<'a> Element<'a> {
         self.page.click_at(x, y).await
     }
 
+    /// Click via the DOM (`this.click()`), without any mouse events. Works
+    /// behind overlays and for zero-size elements, but the event has
+    /// `isTrusted: false` and no preceding pointer events, which bot checks
+    /// can see. Prefer [`Element::click`].
+    pub async fn dispatch_click(&self) -> Result<()> {
+        self.eval_on_element("(this.click(), true)").await?;
+        Ok(())
+    }
+
+    /// Real mouse click if this element is what's under its center point,
+    /// else [`Element::dispatch_click`]. See [`Page::click_with_fallback`].
+    pub async fn click_with_fallback(&self) -> Result<()> {
+        // Hit-testing only sees what's inside the viewport
+        self.scroll_into_view_if_needed().await?;
+        let center = match self.center().await {
+            Ok(center) => center,
+            Err(Error::NodeNotVisible) => return self.dispatch_click().await,
+            Err(e) => return Err(e),
+        };
+        if self.receives_pointer_at(center).await? {
+            self.page.click_at(center.0, center.1).await
+        } else {
+            tracing::debug!("Element covered at {:?}, dispatching DOM click", center);
+            self.dispatch_click().await
+        }
+    }
+
+    /// Scroll this element into view at once (no smooth scrolling), so its
+    /// box model is final when read right after
+    async fn scroll_into_view_if_needed(&self) -> Result<()> {
+        self.call_on_element(SCROLL_INTO_VIEW_IF_NEEDED_JS, &[])
+            .await?;
+        Ok(())
+    }
+
+    /// Whether a click at page point `(x, y)` would land on this element
+    /// (or one of its descendants)
+    async fn receives_pointer_at(&self, (x, y): (f64, f64)) -> Result<bool> {
+        let (local_x, local_y) = (x - self.origin.0, y - self.origin.1);
+        if local_x < 0.0 || local_y < 0.0 {
+            return Ok(false);
+        }
+        let value = self
+            .eval_on_element(&format!(
+                "(el => !!el && (el === this || this.contains(el)))(this.ownerDocument.elementFromPoint({}, {}))",
+                local_x, local_y
+            ))
+            .await?;
+        Ok(value.as_bool().unwrap_or(false))
+    }
+
     /// Human-like click
     pub async fn human_click(&self) -> Result<()> {
+        self.page.auto_warm_up().await?;
+        let (x, y) = self.center().await?;
+        let start = self.page.mouse_origin();
+        self.page.human().move_from_and_click(start, x, y).await?;
+        *self.page.mouse.lock().unwrap() = Some((x, y));
+        Ok(())
+    }
+
+    /// Move the mouse onto this element so `mouseenter`/`mouseover` and CSS
+    /// `:hover` apply, then dwell briefly
+    pub async fn hover(&self) -> Result<()> {
+        self.hover_for(HOVER_DWELL_MS).await
+    }
+
+    /// Hover over this element and stay there for `dwell_ms`
+    pub async fn hover_for(&self, dwell_ms: u64) -> Result<()> {
         let (x, y) = self.center().await?;
-        self.page.human().move_and_click(x, y).await
+        self.page.move_mouse(x, y).await?;
+        tokio::time::sleep(std::time::Duration::from_millis(dwell_ms)).await;
+        Ok(())
     }
 
     /// Get outer HTML
     pub async fn outer_html(&self) -> Result<String> {
-        self.page.session.get_outer_html(self.node_id).await
+        self.session.get_outer_html(self.node_id).await
     }
 
-    /// Get inner text
-    ///
-    /// Extracts text content from the element's outerHTML without using focus.
+    /// Replace the element's children with `html`
+    pub async fn set_inner_html(&self, html: &str) -> Result<()> {
+        self.call_on_element("function(html) { this.innerHTML = html; }", &[html.into()])
+            .await?;
+        Ok(())
+    }
+
+    /// Replace the element itself with `html`. This element then refers to
+    /// a detached node, so use the returned one: the first element parsed
+    /// from `html`, or `None` if it has no element (e.g. only text).
+    pub async fn set_outer_html(&self, html: &str) -> Result<Option<Element<'a>>> {
+        let object_id = self.session.resolve_node(self.node_id).await?;
+        let result = self
+            .session
+            .call_function_on_for_remote_object(&object_id, SET_OUTER_HTML_JS, &[html.into()])
+            .await?;
+        let remote = self.page.check_js_result(result)?;
+
+        let Some(object_id) = remote
+            .object_id
+            .filter(|_| remote.subtype.as_deref() != Some("null"))
+        else {
+            return Ok(None);
+        };
+        let node_id = self.session.request_node(&object_id).await?;
+        Ok((node_id != 0).then(|| self.child(node_id)))
+    }
+
+    /// Get the element's text. Alias for [`Element::text_content`].
     pub async fn text(&self) -> Result<String> {
+        self.text_content().await
+    }
+
+    /// Raw `textContent`: all descendant text, including hidden elements,
+    /// `<script>`/`<style>` contents and source whitespace
+    pub async fn text_content(&self) -> Result<String> {
         let value = self.eval_on_element("this.textContent || ''").await?;
+        Ok(value.as_str().unwrap_or_default().to_string())
+    }
 
-        if let Some(s) = value.as_str() {
-            return Ok(s.to_string());
-        }
-        Ok(String::new())
+    /// Rendered `innerText`: what the user sees, with hidden descendants left
+    /// out and whitespace laid out as on screen (line breaks for blocks)
+    pub async fn inner_text(&self) -> Result<String> {
+        let value = self
+            .eval_on_element("this.innerText ?? this.textContent ?? ''")
+            .await?;
+        Ok(value.as_str().unwrap_or_default().to_string())
     }
 
     /// Evaluate a JavaScript expression on this element via Runtime.callFunctionOn.
@@ -1333,14 +3900,22 @@ impl<'a> Element<'a> {
     /// The expression should use `this` to refer to the element.
     /// Example: `"this.textContent || ''"`, `"this.tagName.toLowerCase()"`
     async fn eval_on_element(&self, js_body: &str) -> Result<serde_json::Value> {
-        let object_id = self.page.session.resolve_node(self.node_id).await?;
-
         let func = format!("function() {{ return {}; }}", js_body);
+        self.call_on_element(&func, &[]).await
+    }
 
+    /// Call `function_declaration` with the element as `this` and `args` as
+    /// its parameters, e.g. `function(name) { return this.getAttribute(name); }`.
+    /// Prefer this over splicing strings into [`Element::eval_on_element`].
+    async fn call_on_element(
+        &self,
+        function_declaration: &str,
+        args: &[serde_json::Value],
+    ) -> Result<serde_json::Value> {
+        let object_id = self.session.resolve_node(self.node_id).await?;
         let result = self
-            .page
             .session
-            .call_function_on(&object_id, &func)
+            .call_function_on_with_args(&object_id, function_declaration, args)
             .await?;
         Ok(result.result.value.unwrap_or(serde_json::Value::Null))
     }
@@ -1348,60 +3923,138 @@ impl<'a> Element<'a> {
     /// Type text into this element
     pub async fn type_text(&self, text: &str) -> Result<()> {
         self.click().await?;
-        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        self.page.interaction_pause().await;
         self.page.session.insert_text(text).await
     }
 
+    /// Check if this element currently has focus
+    pub async fn is_focused(&self) -> Result<bool> {
+        let value = self
+            .eval_on_element("this === this.ownerDocument.activeElement")
+            .await?;
+        Ok(value.as_bool().unwrap_or(false))
+    }
+
     /// Focus this element
     pub async fn focus(&self) -> Result<()> {
-        self.page.session.focus(self.node_id).await
+        self.session.focus(self.node_id).await
+    }
+
+    /// Set the files of this `<input type="file">`. Every path must be a
+    /// readable file; it is checked before anything is sent to Chrome.
+    pub async fn set_input_files(&self, paths: &[&str]) -> Result<()> {
+        let files = paths
+            .iter()
+            .map(|path| readable_file(path))
+            .collect::<Result<Vec<_>>>()?;
+        self.session.set_file_input_files(self.node_id, files).await
+    }
+
+    /// Focus this element and press a key (same syntax as `Page::press_key`)
+    pub async fn press_key(&self, key: &str) -> Result<()> {
+        self.focus().await?;
+        self.page.press_key(key).await
     }
+
     /// Check if the element is visible (has a computable box model)
     pub async fn is_visible(&self) -> Result<bool> {
-        match self.page.session.get_box_model(self.node_id).await {
+        match self.session.get_box_model(self.node_id).await {
             Ok(_) => Ok(true),
-            Err(Error::Cdp { message, .. }) if message.contains("box model") => Ok(false),
+            Err(Error::NodeNotVisible) => Ok(false),
             Err(e) => Err(e),
         }
     }
 
-    /// Get the element's bounding box
+    /// Get the element's bounding box: the content box (no padding or
+    /// border) from the DOM box model, in CSS pixels relative to the top-level
+    /// visual viewport, so it moves when the page scrolls and scales with
+    /// [`Page::set_zoom`]. Mouse events use the same space.
     ///
     /// Returns None if the element is not visible/rendered.
+    /// See [`Element::client_rect`] for the border box as page JS sees it.
     pub async fn bounding_box(&self) -> Option<BoundingBox> {
-        match self.page.session.get_box_model(self.node_id).await {
-            Ok(model) => {
-                let content = &model.content;
-                if content.len() >= 8 {
-                    // content is [x1,y1, x2,y2, x3,y3, x4,y4] for a quad
-                    // Handle rotated/transformed elements by finding actual bounds
-                    let xs = [content[0], content[2], content[4], content[6]];
-                    let ys = [content[1], content[3], content[5], content[7]];
-
-                    let min_x = xs.iter().copied().fold(f64::INFINITY, f64::min);
-                    let max_x = xs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
-                    let min_y = ys.iter().copied().fold(f64::INFINITY, f64::min);
-                    let max_y = ys.iter().copied().fold(f64::NEG_INFINITY, f64::max);
-
-                    Some(BoundingBox {
-                        x: min_x,
-                        y: min_y,
-                        width: max_x - min_x,
-                        height: max_y - min_y,
-                    })
-                } else {
-                    None
-                }
-            }
-            Err(_) => None,
+        let model = self.session.get_box_model(self.node_id).await.ok()?;
+        self.quad_bounds(&model.content)
+    }
+
+    /// Like [`Element::bounding_box`], but the border box: padding and
+    /// border included
+    async fn border_box(&self) -> Option<BoundingBox> {
+        let model = self.session.get_box_model(self.node_id).await.ok()?;
+        self.quad_bounds(&model.border)
+    }
+
+    /// Axis-aligned bounds of a box model quad, offset into top-level space
+    fn quad_bounds(&self, quad: &[f64]) -> Option<BoundingBox> {
+        if quad.len() < 8 {
+            return None;
         }
+        // The quad is [x1,y1, x2,y2, x3,y3, x4,y4]
+        // Handle rotated/transformed elements by finding actual bounds
+        let xs = [quad[0], quad[2], quad[4], quad[6]];
+        let ys = [quad[1], quad[3], quad[5], quad[7]];
+
+        let min_x = xs.iter().copied().fold(f64::INFINITY, f64::min);
+        let max_x = xs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let min_y = ys.iter().copied().fold(f64::INFINITY, f64::min);
+        let max_y = ys.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        Some(BoundingBox {
+            x: min_x + self.origin.0,
+            y: min_y + self.origin.1,
+            width: max_x - min_x,
+            height: max_y - min_y,
+        })
+    }
+
+    /// `getBoundingClientRect()`: the border box relative to the layout
+    /// viewport of the element's own frame, exactly as page scripts see it
+    /// (unaffected by [`Page::set_zoom`]). For elements in an unzoomed top
+    /// document this matches screenshot pixel positions.
+    pub async fn client_rect(&self) -> Result<BoundingBox> {
+        let value = self
+            .eval_on_element("(r => [r.x, r.y, r.width, r.height])(this.getBoundingClientRect())")
+            .await?;
+        let [x, y, width, height]: [f64; 4] = serde_json::from_value(value)?;
+        Ok(BoundingBox {
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    /// Capture just this element as PNG bytes. It doesn't need to be in the
+    /// viewport. Like [`Page::screenshot`], the image has device pixels, so
+    /// it is `devicePixelRatio` times the element's CSS size. The capture
+    /// covers the border box, so padding and border are included.
+    pub async fn screenshot(&self) -> Result<Vec<u8>> {
+        let bbox = self.border_box().await.ok_or(Error::NodeNotVisible)?;
+        // Box models are relative to the (possibly zoomed) visual viewport;
+        // screenshot clips are document-relative CSS pixels
+        let (scale, page_left, page_top): (f64, f64, f64) = self
+            .page
+            .evaluate("(v => [v.scale, v.pageLeft, v.pageTop])(window.visualViewport)")
+            .await?;
+        self.page
+            .session
+            .capture_screenshot_clip(ScreenshotClip {
+                x: bbox.x / scale + page_left,
+                y: bbox.y / scale + page_top,
+                width: bbox.width / scale,
+                height: bbox.height / scale,
+                scale: 1.0,
+            })
+            .await
     }
 
     /// Get an attribute value
     pub async fn get_attribute(&self, name: &str) -> Result<Option<String>> {
-        let escaped_name = escape_js_string(name);
         let value = self
-            .eval_on_element(&format!("this.getAttribute('{}')", escaped_name))
+            .call_on_element(
+                "function(name) { return this.getAttribute(name); }",
+                &[name.into()],
+            )
             .await?;
 
         if value.is_null() {
@@ -1415,7 +4068,9 @@ impl<'a> Element<'a> {
 
     /// Get the tag name of the element (e.g., "div", "input", "a")
     pub async fn tag_name(&self) -> Result<String> {
-        let value = self.eval_on_element("this.tagName.toLowerCase()").await?;
+        let value = self
+            .call_on_element("function() { return this.tagName.toLowerCase(); }", &[])
+            .await?;
 
         if let Some(s) = value.as_str() {
             return Ok(s.to_string());
@@ -1425,7 +4080,9 @@ impl<'a> Element<'a> {
 
     /// Check if the element is enabled (not disabled)
     pub async fn is_enabled(&self) -> Result<bool> {
-        let value = self.eval_on_element("!this.disabled").await?;
+        let value = self
+            .call_on_element("function() { return !this.disabled; }", &[])
+            .await?;
 
         if let Some(b) = value.as_bool() {
             return Ok(b);
@@ -1435,7 +4092,9 @@ impl<'a> Element<'a> {
 
     /// Check if a checkbox/radio is checked
     pub async fn is_checked(&self) -> Result<bool> {
-        let value = self.eval_on_element("this.checked === true").await?;
+        let value = self
+            .call_on_element("function() { return this.checked === true; }", &[])
+            .await?;
 
         if let Some(b) = value.as_bool() {
             return Ok(b);
@@ -1443,9 +4102,110 @@ impl<'a> Element<'a> {
         Ok(false)
     }
 
+    /// Check a checkbox/radio; clicks only if it isn't checked already
+    pub async fn check(&self) -> Result<()> {
+        self.set_checked(true).await
+    }
+
+    /// Uncheck a checkbox; clicks only if it is checked.
+    ///
+    /// A checked radio can't be unchecked by clicking it, so that returns
+    /// `Error::InvalidState`; an unchecked radio is a no-op.
+    pub async fn uncheck(&self) -> Result<()> {
+        self.set_checked(false).await
+    }
+
+    /// Click to reach the desired checked state, then verify it took effect
+    async fn set_checked(&self, checked: bool) -> Result<()> {
+        if self.is_checked().await? == checked {
+            return Ok(());
+        }
+
+        if !checked {
+            let is_radio = self
+                .eval_on_element("this.type === 'radio'")
+                .await?
+                .as_bool()
+                .unwrap_or(false);
+            if is_radio {
+                return Err(Error::InvalidState(format!(
+                    "cannot uncheck radio <{}>; check another option in its group instead",
+                    self.describe().await
+                )));
+            }
+        }
+
+        self.click().await?;
+
+        if self.is_checked().await? != checked {
+            return Err(Error::InvalidState(format!(
+                "<{}> is still {} after clicking",
+                self.describe().await,
+                if checked { "unchecked" } else { "checked" }
+            )));
+        }
+        Ok(())
+    }
+
+    /// Wait until the element is enabled (e.g. a submit button unlocked by validation)
+    pub async fn wait_for_enabled(&self, timeout_ms: u64) -> Result<()> {
+        self.wait_for_state(ElementState::Enabled, timeout_ms).await
+    }
+
+    /// Wait until a checkbox/radio is checked
+    pub async fn wait_for_checked(&self, timeout_ms: u64) -> Result<()> {
+        self.wait_for_state(ElementState::Checked, timeout_ms).await
+    }
+
+    /// Wait until a checkbox/radio is unchecked
+    pub async fn wait_for_unchecked(&self, timeout_ms: u64) -> Result<()> {
+        self.wait_for_state(ElementState::Unchecked, timeout_ms)
+            .await
+    }
+
+    /// Poll until the element reaches `state`
+    async fn wait_for_state(&self, state: ElementState, timeout_ms: u64) -> Result<()> {
+        let start = std::time::Instant::now();
+        let timeout = std::time::Duration::from_millis(timeout_ms);
+
+        loop {
+            let reached = match state {
+                ElementState::Enabled => self.is_enabled().await?,
+                ElementState::Checked => self.is_checked().await?,
+                ElementState::Unchecked => !self.is_checked().await?,
+            };
+            if reached {
+                return Ok(());
+            }
+
+            if start.elapsed() > timeout {
+                let description = self.describe().await;
+                return Err(Error::Timeout(format!(
+                    "Element <{}> not {} within {}ms",
+                    description,
+                    state.as_str(),
+                    timeout_ms
+                )));
+            }
+
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    }
+
+    /// Short description for error messages, e.g. `button#submit`
+    async fn describe(&self) -> String {
+        self.eval_on_element("this.tagName.toLowerCase() + (this.id ? '#' + this.id : '')")
+            .await
+            .ok()
+            .and_then(|v| v.as_str().map(String::from))
+            .unwrap_or_else(|| format!("node {}", self.node_id))
+    }
+
     /// Get the value of an input element
     pub async fn value(&self) -> Result<String> {
-        let value = self.eval_on_element("this.value || ''").await?;
+        let value = self
+            .call_on_element("function() { return this.value || ''; }", &[])
+            .await?;
 
         if let Some(s) = value.as_str() {
             return Ok(s.to_string());
@@ -1455,12 +4215,11 @@ impl<'a> Element<'a> {
 
     /// Get computed CSS property value
     pub async fn css(&self, property: &str) -> Result<String> {
-        let escaped = escape_js_string(property);
         let value = self
-            .eval_on_element(&format!(
-                "getComputedStyle(this).getPropertyValue('{}')",
-                escaped
-            ))
+            .call_on_element(
+                "function(property) { return getComputedStyle(this).getPropertyValue(property); }",
+                &[property.into()],
+            )
             .await?;
 
         if let Some(s) = value.as_str() {
@@ -1471,9 +4230,8 @@ impl<'a> Element<'a> {
 
     /// Scroll this element into view
     pub async fn scroll_into_view(&self) -> Result<()> {
-        let object_id = self.page.session.resolve_node(self.node_id).await?;
-        self.page
-            .session
+        let object_id = self.session.resolve_node(self.node_id).await?;
+        self.session
             .call_function_on(
                 &object_id,
                 "function() { this.scrollIntoView({ behavior: 'smooth', block: 'center' }); }",
@@ -1487,6 +4245,78 @@ impl<'a> Element<'a> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_text_match_js_defaults_preserve_behavior() {
+        let exact = text_match_js(
+            "Buy",
+            TextMatch::Exact,
+            TextMatchOptions::for_match(TextMatch::Exact),
+        )
+        .unwrap();
+        assert_eq!(exact, r#"t.trim() === "Buy".trim()"#);
+
+        let contains = text_match_js(
+            "Buy",
+            TextMatch::Contains,
+            TextMatchOptions::for_match(TextMatch::Contains),
+        )
+        .unwrap();
+        assert_eq!(contains, r#"t.toLowerCase().includes("Buy".toLowerCase())"#);
+
+        let starts =
+            text_match_js("Buy", TextMatch::StartsWith, TextMatchOptions::default()).unwrap();
+        assert_eq!(starts, r#"t.toLowerCase().startsWith("Buy".toLowerCase())"#);
+
+        let ends = text_match_js("Buy", TextMatch::EndsWith, TextMatchOptions::default()).unwrap();
+        assert_eq!(ends, r#"t.toLowerCase().endsWith("Buy".toLowerCase())"#);
+    }
+
+    #[test]
+    fn test_text_match_js_option_combinations() {
+        let case_sensitive = TextMatchOptions {
+            case_sensitive: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            text_match_js("Buy", TextMatch::Contains, case_sensitive).unwrap(),
+            r#"t.includes("Buy")"#
+        );
+
+        let all = TextMatchOptions {
+            case_sensitive: false,
+            normalize_whitespace: true,
+            trim: true,
+        };
+        assert_eq!(
+            text_match_js("Add  to\ncart", TextMatch::Exact, all).unwrap(),
+            r#"t.replace(/\s+/g, ' ').trim().toLowerCase() === "Add  to\ncart".replace(/\s+/g, ' ').trim().toLowerCase()"#
+        );
+
+        let normalize_only = TextMatchOptions {
+            case_sensitive: true,
+            normalize_whitespace: true,
+            trim: false,
+        };
+        assert_eq!(
+            text_match_js("a b", TextMatch::StartsWith, normalize_only).unwrap(),
+            r#"t.replace(/\s+/g, ' ').startsWith("a b".replace(/\s+/g, ' '))"#
+        );
+    }
+
+    #[test]
+    fn test_text_match_js_escapes_quotes() {
+        let js = text_match_js(
+            r#"say "hi" \ ${x}"#,
+            TextMatch::Contains,
+            TextMatchOptions {
+                case_sensitive: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(js, r#"t.includes("say \"hi\" \\ ${x}")"#);
+    }
+
     #[test]
     fn test_parse_key_combo_simple() {
         let (mods, key) = parse_key_combo("Enter");
@@ -1537,6 +4367,42 @@ mod tests {
         assert_eq!(vk, Some(13));
     }
 
+    #[test]
+    fn test_readable_file() {
+        let path = std::env::temp_dir().join(format!("eoka-upload-{}", std::process::id()));
+        std::fs::write(&path, "x").unwrap();
+        let resolved = readable_file(path.to_str().unwrap()).unwrap();
+        assert!(std::path::Path::new(&resolved).is_absolute());
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            readable_file(path.to_str().unwrap()),
+            Err(Error::FileNotReadable { .. })
+        ));
+        assert!(matches!(
+            readable_file(std::env::temp_dir().to_str().unwrap()),
+            Err(Error::FileNotReadable { .. })
+        ));
+    }
+
+    #[test]
+    fn test_key_text() {
+        use crate::cdp::types::modifiers;
+        assert_eq!(key_text("Enter", 0), Some("\r"));
+        assert_eq!(key_text("a", 0), Some("a"));
+        assert_eq!(key_text(" ", 0), Some(" "));
+        assert_eq!(key_text("a", modifiers::SHIFT), Some("a"));
+        assert_eq!(key_text("a", modifiers::CTRL), None);
+        assert_eq!(key_text("Tab", 0), None);
+        assert_eq!(key_text("Escape", 0), None);
+        assert_eq!(key_text("F5", 0), None);
+    }
+
+    #[test]
+    fn test_key_to_codes_digit() {
+        assert_eq!(key_to_codes("7"), ("7", "Digit7", Some(55)));
+    }
+
     #[test]
     fn test_key_to_codes_tab() {
         let (key, code, vk) = key_to_codes("Tab");
@@ -1601,4 +4467,79 @@ mod tests {
         assert_eq!(escape_js_string("back\\slash"), "back\\\\slash");
         assert_eq!(escape_js_string("${var}"), "\\${var}");
     }
+
+    #[test]
+    fn test_cookie_header() {
+        let cookie = |name: &str, value: &str, path: &str| Cookie {
+            name: name.into(),
+            value: value.into(),
+            path: path.into(),
+            ..Default::default()
+        };
+        assert_eq!(cookie_header(Vec::new()), "");
+        assert_eq!(
+            cookie_header(vec![
+                cookie("sid", "abc", "/"),
+                cookie("cart", "1", "/shop"),
+            ]),
+            "cart=1; sid=abc"
+        );
+    }
+
+    #[test]
+    fn test_char_to_codes() {
+        assert_eq!(
+            char_to_codes('a'),
+            ("a".into(), "KeyA".into(), Some(65), false)
+        );
+        assert_eq!(
+            char_to_codes('Q'),
+            ("Q".into(), "KeyQ".into(), Some(81), true)
+        );
+        assert_eq!(
+            char_to_codes('@'),
+            ("@".into(), "Digit2".into(), Some(50), true)
+        );
+        assert_eq!(char_to_codes('/').1, "Slash");
+        assert_eq!(char_to_codes('\n').0, "Enter");
+        assert_eq!(char_to_codes('é'), ("é".into(), String::new(), None, false));
+    }
+
+    #[test]
+    fn test_backoff_delay_grows() {
+        assert_eq!(backoff_delay(100, 2.0, 0), 100);
+        assert_eq!(backoff_delay(100, 2.0, 1), 200);
+        assert_eq!(backoff_delay(100, 2.0, 2), 400);
+        // Multiplier 1.0 is the fixed delay of `with_retry`
+        assert_eq!(backoff_delay(250, 1.0, 5), 250);
+    }
+
+    #[tokio::test]
+    async fn test_retry_stops_on_non_retryable_error() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let calls = AtomicU32::new(0);
+        let syntax_error = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(Error::JavaScriptException {
+                text: "SyntaxError".into(),
+                line: 0,
+                column: 0,
+            })
+        };
+        let transient = |e: &Error| !matches!(e, Error::JavaScriptException { .. });
+
+        let result = retry_with_backoff(5, 1, 2.0, transient, syntax_error).await;
+        assert!(matches!(result, Err(Error::JavaScriptException { .. })));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Retryable errors use every attempt
+        calls.store(0, Ordering::SeqCst);
+        let result = retry_with_backoff(3, 1, 2.0, |_| true, syntax_error).await;
+        assert!(matches!(
+            result,
+            Err(Error::RetryExhausted { attempts: 3, .. })
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
 }
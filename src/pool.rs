@@ -0,0 +1,187 @@
+//! Browser Pool
+//!
+//! Drives many pages across a fixed set of browsers with a cap on how many
+//! are open at once. See [`BrowserPool`].
+
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::browser::{chrome_binary, Browser};
+use crate::error::{Error, Result};
+use crate::page::Page;
+use crate::StealthConfig;
+
+/// A fixed set of browsers handing out pages, at most
+/// `browsers * pages_per_browser` at a time.
+///
+/// Chrome is patched once for the whole pool. Pages are opened on the least
+/// busy browser and closed when their [`PagePermit`] is dropped; pages on
+/// the same browser share its cookies and storage.
+pub struct BrowserPool {
+    browsers: Vec<Browser>,
+    /// Open pages per browser
+    load: Arc<Mutex<Vec<usize>>>,
+    permits: Arc<Semaphore>,
+}
+
+impl BrowserPool {
+    /// Launch `browsers` browsers, each allowed `pages_per_browser` pages.
+    ///
+    /// A `user_data_dir` in `config` becomes one subdirectory per browser
+    /// (`<dir>/0`, `<dir>/1`, ...), since Chrome can't share a profile.
+    pub async fn launch(
+        browsers: usize,
+        pages_per_browser: usize,
+        config: StealthConfig,
+    ) -> Result<Self> {
+        if browsers == 0 || pages_per_browser == 0 {
+            return Err(Error::Launch(
+                "pool needs at least one browser and one page per browser".into(),
+            ));
+        }
+
+        // Patch once; every browser then launches the same binary as is
+        let chrome_path = chrome_binary(&config)?;
+        let config = StealthConfig {
+            chrome_path: Some(chrome_path.to_string_lossy().into_owned()),
+            patch_binary: false,
+            ..config
+        };
+
+        let launches: Vec<_> = (0..browsers)
+            .map(|i| {
+                let config = StealthConfig {
+                    user_data_dir: config
+                        .user_data_dir
+                        .as_ref()
+                        .map(|dir| dir.join(i.to_string())),
+                    ..config.clone()
+                };
+                tokio::spawn(Browser::launch_with_config(config))
+            })
+            .collect();
+
+        let mut launched = Vec::with_capacity(browsers);
+        for launch in launches {
+            let browser = launch
+                .await
+                .map_err(|e| Error::Launch(format!("pool launch task failed: {}", e)))??;
+            launched.push(browser);
+        }
+
+        Ok(Self {
+            load: Arc::new(Mutex::new(vec![0; browsers])),
+            permits: Arc::new(Semaphore::new(browsers * pages_per_browser)),
+            browsers: launched,
+        })
+    }
+
+    /// Open a blank page, waiting while the pool is at capacity.
+    ///
+    /// Wrap in `tokio::time::timeout` to bound the wait.
+    pub async fn acquire(&self) -> Result<PagePermit<'_>> {
+        let permit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .map_err(|_| Error::SessionClosed)?;
+
+        let index = {
+            let mut load = self.load.lock().unwrap();
+            let index = (0..load.len()).min_by_key(|&i| load[i]).unwrap_or(0);
+            load[index] += 1;
+            index
+        };
+
+        match self.browsers[index].new_blank_page().await {
+            Ok(page) => Ok(PagePermit {
+                page,
+                pool: self,
+                browser: index,
+                permit: Some(permit),
+            }),
+            Err(e) => {
+                self.load.lock().unwrap()[index] -= 1;
+                Err(e)
+            }
+        }
+    }
+
+    /// Pages that can be acquired right now without waiting
+    pub fn available(&self) -> usize {
+        self.permits.available_permits()
+    }
+
+    /// The pool's browsers
+    pub fn browsers(&self) -> &[Browser] {
+        &self.browsers
+    }
+
+    /// Close every browser, returning the first error
+    pub async fn close(self) -> Result<()> {
+        self.permits.close();
+        let mut result = Ok(());
+        for browser in self.browsers {
+            if let Err(e) = browser.close().await {
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+        result
+    }
+}
+
+/// A page on loan from a [`BrowserPool`]. Derefs to [`Page`]; dropping it
+/// closes the tab and frees the slot once the tab is gone.
+pub struct PagePermit<'a> {
+    page: Page,
+    pool: &'a BrowserPool,
+    browser: usize,
+    /// Taken on drop and released after the tab has closed
+    permit: Option<OwnedSemaphorePermit>,
+}
+
+impl PagePermit<'_> {
+    /// The browser this page belongs to
+    pub fn browser(&self) -> &Browser {
+        &self.pool.browsers[self.browser]
+    }
+}
+
+impl Deref for PagePermit<'_> {
+    type Target = Page;
+
+    fn deref(&self) -> &Page {
+        &self.page
+    }
+}
+
+impl Drop for PagePermit<'_> {
+    fn drop(&mut self) {
+        let load = Arc::clone(&self.pool.load);
+        let browser = self.browser;
+        let permit = self.permit.take();
+        let release = move || {
+            load.lock().unwrap()[browser] -= 1;
+            drop(permit);
+        };
+
+        // Closing is async; without a runtime Chrome cleans up on exit.
+        // The slot frees only once the tab is closed, so a new page never
+        // lands next to one that is still open.
+        match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => {
+                let session = self.page.session().clone();
+                runtime.spawn(async move {
+                    if let Err(e) = session.close_target().await {
+                        tracing::debug!("Failed to close pooled page: {}", e);
+                    }
+                    release();
+                });
+            }
+            Err(_) => release(),
+        }
+    }
+}
@@ -0,0 +1,306 @@
+//! Unified Selector Syntax
+//!
+//! Playwright-style selectors: `css=`, `text=`, `xpath=` prefixes chained with `>>`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::page::{text_match_js, TextMatch, TextMatchOptions};
+
+/// One step of a [`Selector`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectorPart {
+    /// CSS selector (`css=.card`, or a bare string)
+    Css(String),
+    /// Visible text. `text=Buy` matches case-insensitively by substring;
+    /// `text="Buy"` (quoted) matches the trimmed text exactly.
+    Text { text: String, exact: bool },
+    /// XPath expression (`xpath=//button`, or a bare string starting with `//`)
+    XPath(String),
+}
+
+/// A parsed selector such as `css=.card >> text=Buy`.
+///
+/// Each part is searched for inside the matches of the previous one; bare
+/// strings are CSS. Used by [`crate::Page::locate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    parts: Vec<SelectorPart>,
+}
+
+impl Selector {
+    /// Parse a selector string
+    pub fn parse(input: &str) -> Result<Self> {
+        let parts = split_chain(input)
+            .into_iter()
+            .map(|raw| parse_part(raw.trim(), input))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { parts })
+    }
+
+    /// The chained parts, outermost first
+    pub fn parts(&self) -> &[SelectorPart] {
+        &self.parts
+    }
+
+    /// JS expression evaluating to the first matching element, or `null`
+    pub(crate) fn to_js(&self) -> Result<String> {
+        let mut steps = String::new();
+        for part in &self.parts {
+            let step = match part {
+                SelectorPart::Css(css) => format!(
+                    "roots = roots.flatMap(r => Array.from(r.querySelectorAll({})));",
+                    serde_json::to_string(css)?
+                ),
+                SelectorPart::XPath(xpath) => format!(
+                    "roots = roots.flatMap(r => byXPath(r, {}));",
+                    serde_json::to_string(xpath)?
+                ),
+                SelectorPart::Text { text, exact } => {
+                    let match_type = if *exact {
+                        TextMatch::Exact
+                    } else {
+                        TextMatch::Contains
+                    };
+                    let check =
+                        text_match_js(text, match_type, TextMatchOptions::for_match(match_type))?;
+                    format!("roots = roots.flatMap(r => byText(r, t => {}));", check)
+                }
+            };
+            steps.push_str(&step);
+            steps.push('\n');
+        }
+
+        Ok(format!(
+            r#"
+            (() => {{
+                const interactive = 'a, button, input[type="submit"], input[type="button"], [role="button"], [onclick]';
+                const secondary = 'label, span, div, p, h1, h2, h3, h4, h5, h6, li, td, th';
+
+                const all = interactive + ', ' + secondary;
+                const matches = (el, check) => check(el.innerText || el.textContent || el.value || '');
+
+                function findDeepest(el, check) {{
+                    for (const child of el.querySelectorAll(all)) {{
+                        if (matches(child, check)) return findDeepest(child, check);
+                    }}
+                    return el;
+                }}
+
+                // Interactive matches (and what they contain) come first,
+                // like Page::find_by_text
+                function byText(root, check) {{
+                    const found = [];
+                    for (const sel of [interactive, secondary]) {{
+                        for (const el of root.querySelectorAll(sel)) {{
+                            if (!matches(el, check)) continue;
+                            const hit = findDeepest(el, check);
+                            if (!found.includes(hit)) found.push(hit);
+                        }}
+                    }}
+                    return found;
+                }}
+
+                function byXPath(root, xpath) {{
+                    const snap = document.evaluate(xpath, root, null, XPathResult.ORDERED_NODE_SNAPSHOT_TYPE, null);
+                    const out = [];
+                    for (let i = 0; i < snap.snapshotLength; i++) {{
+                        const node = snap.snapshotItem(i);
+                        if (node.nodeType === Node.ELEMENT_NODE) out.push(node);
+                    }}
+                    return out;
+                }}
+
+                let roots = [document];
+                {steps}
+                return roots[0] || null;
+            }})()
+            "#,
+            steps = steps
+        ))
+    }
+}
+
+/// Split `input` at each `>>` outside single or double quotes, so
+/// `text="a >> b"` and `[title='x>>y']` stay one part
+fn split_chain(input: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    let mut chars = input.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), '\\') => {
+                chars.next();
+            }
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') if input[i..].starts_with(">>") => {
+                parts.push(&input[start..i]);
+                chars.next();
+                start = i + 2;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&input[start..]);
+    parts
+}
+
+/// Parse one `>>`-separated part
+fn parse_part(raw: &str, input: &str) -> Result<SelectorPart> {
+    let invalid = |reason: &str| Error::InvalidSelector(format!("{} in '{}'", reason, input));
+
+    let part = if let Some(css) = raw.strip_prefix("css=") {
+        SelectorPart::Css(css.trim().to_string())
+    } else if let Some(xpath) = raw.strip_prefix("xpath=") {
+        SelectorPart::XPath(xpath.trim().to_string())
+    } else if let Some(text) = raw.strip_prefix("text=") {
+        let text = text.trim();
+        match text
+            .strip_prefix('"')
+            .and_then(|t| t.strip_suffix('"'))
+            .filter(|_| text.len() >= 2)
+        {
+            Some(quoted) => SelectorPart::Text {
+                text: quoted.to_string(),
+                exact: true,
+            },
+            None => SelectorPart::Text {
+                text: text.to_string(),
+                exact: false,
+            },
+        }
+    } else if raw.starts_with("//") || raw.starts_with("(//") {
+        SelectorPart::XPath(raw.to_string())
+    } else {
+        SelectorPart::Css(raw.to_string())
+    };
+
+    let empty = match &part {
+        SelectorPart::Css(s) | SelectorPart::XPath(s) => s.is_empty(),
+        SelectorPart::Text { text, .. } => text.is_empty(),
+    };
+    if empty {
+        return Err(invalid("empty selector part"));
+    }
+    Ok(part)
+}
+
+impl FromStr for Selector {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, part) in self.parts.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" >> ")?;
+            }
+            match part {
+                SelectorPart::Css(css) => write!(f, "css={}", css)?,
+                SelectorPart::XPath(xpath) => write!(f, "xpath={}", xpath)?,
+                SelectorPart::Text { text, exact: true } => write!(f, "text=\"{}\"", text)?,
+                SelectorPart::Text { text, exact: false } => write!(f, "text={}", text)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bare_string_is_css() {
+        let sel = Selector::parse("#login > button").unwrap();
+        assert_eq!(sel.parts(), &[SelectorPart::Css("#login > button".into())]);
+    }
+
+    #[test]
+    fn test_prefixes() {
+        assert_eq!(
+            Selector::parse("css=.card").unwrap().parts(),
+            &[SelectorPart::Css(".card".into())]
+        );
+        assert_eq!(
+            Selector::parse("xpath=//div[@id='a']").unwrap().parts(),
+            &[SelectorPart::XPath("//div[@id='a']".into())]
+        );
+        assert_eq!(
+            Selector::parse("//button").unwrap().parts(),
+            &[SelectorPart::XPath("//button".into())]
+        );
+        assert_eq!(
+            Selector::parse("text=Add to cart").unwrap().parts(),
+            &[SelectorPart::Text {
+                text: "Add to cart".into(),
+                exact: false
+            }]
+        );
+        assert_eq!(
+            Selector::parse("text=\"Buy\"").unwrap().parts(),
+            &[SelectorPart::Text {
+                text: "Buy".into(),
+                exact: true
+            }]
+        );
+    }
+
+    #[test]
+    fn test_chained() {
+        let sel = Selector::parse("css=.card >> text=Buy >> xpath=./span").unwrap();
+        assert_eq!(
+            sel.parts(),
+            &[
+                SelectorPart::Css(".card".into()),
+                SelectorPart::Text {
+                    text: "Buy".into(),
+                    exact: false
+                },
+                SelectorPart::XPath("./span".into()),
+            ]
+        );
+        assert_eq!(sel.to_string(), "css=.card >> text=Buy >> xpath=./span");
+    }
+
+    #[test]
+    fn test_chain_separator_inside_quotes() {
+        let sel = Selector::parse("css=.card >> text=\"a >> b\"").unwrap();
+        assert_eq!(
+            sel.parts(),
+            &[
+                SelectorPart::Css(".card".into()),
+                SelectorPart::Text {
+                    text: "a >> b".into(),
+                    exact: true
+                },
+            ]
+        );
+
+        let sel = Selector::parse("[title='x>>y'] >> span").unwrap();
+        assert_eq!(
+            sel.parts(),
+            &[
+                SelectorPart::Css("[title='x>>y']".into()),
+                SelectorPart::Css("span".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_part_rejected() {
+        assert!(matches!(
+            Selector::parse("css=.card >> "),
+            Err(Error::InvalidSelector(_))
+        ));
+        assert!(Selector::parse("text=").is_err());
+    }
+}
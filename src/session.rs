@@ -5,6 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::cdp::Cookie;
 use crate::error::Result;
@@ -41,6 +42,55 @@ impl From<Cookie> for SessionCookie {
     }
 }
 
+impl SessionCookie {
+    /// This cookie as a `Set-Cookie` header value, for cookie jars that
+    /// parse those. `expires` becomes a `Max-Age` from now.
+    pub fn to_set_cookie(&self) -> String {
+        let mut header = format!("{}={}", self.name, self.value);
+        // Chrome marks domain cookies with a leading dot; others are host-only
+        if let Some(domain) = self.domain.strip_prefix('.') {
+            header.push_str(&format!("; Domain={}", domain));
+        }
+        if !self.path.is_empty() {
+            header.push_str(&format!("; Path={}", self.path));
+        }
+        if let Some(expires) = self.expires {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs_f64())
+                .unwrap_or(0.0);
+            header.push_str(&format!("; Max-Age={}", (expires - now).max(0.0) as u64));
+        }
+        if self.secure {
+            header.push_str("; Secure");
+        }
+        if self.http_only {
+            header.push_str("; HttpOnly");
+        }
+        if let Some(same_site) = &self.same_site {
+            header.push_str(&format!("; SameSite={}", same_site));
+        }
+        header
+    }
+
+    /// A URL the cookie applies to (`https` when secure), for jars that
+    /// take cookies per response URL
+    pub fn url(&self) -> String {
+        let scheme = if self.secure { "https" } else { "http" };
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        format!(
+            "{}://{}{}",
+            scheme,
+            self.domain.trim_start_matches('.'),
+            path
+        )
+    }
+}
+
 /// Exported browser session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BrowserSession {
@@ -96,6 +146,22 @@ impl BrowserSession {
             .collect::<Vec<_>>()
             .join("; ")
     }
+
+    /// The cookies as a `reqwest` cookie jar, to continue the session with
+    /// `reqwest::Client::builder().cookie_provider(Arc::new(jar))`. Domain,
+    /// path, `Secure` and `HttpOnly` carry over, so the client sends each
+    /// cookie where the browser would.
+    #[cfg(feature = "reqwest-interop")]
+    pub fn to_reqwest_cookies(&self) -> reqwest::cookie::Jar {
+        let jar = reqwest::cookie::Jar::default();
+        for cookie in &self.cookies {
+            match reqwest::Url::parse(&cookie.url()) {
+                Ok(url) => jar.add_cookie_str(&cookie.to_set_cookie(), &url),
+                Err(e) => tracing::warn!("Skipping cookie {}: {}", cookie.name, e),
+            }
+        }
+        jar
+    }
 }
 
 #[cfg(test)]
@@ -170,4 +236,70 @@ mod tests {
         assert_eq!(example_cookies.len(), 1);
         assert_eq!(example_cookies[0].name, "site1");
     }
+
+    fn cookie(name: &str, domain: &str, path: &str, secure: bool) -> SessionCookie {
+        SessionCookie {
+            name: name.to_string(),
+            value: format!("{}-value", name),
+            domain: domain.to_string(),
+            path: path.to_string(),
+            secure,
+            http_only: false,
+            same_site: None,
+            expires: None,
+        }
+    }
+
+    #[test]
+    fn test_to_set_cookie() {
+        let mut domain = cookie("sid", ".example.com", "/app", true);
+        domain.http_only = true;
+        domain.same_site = Some("Lax".to_string());
+        assert_eq!(
+            domain.to_set_cookie(),
+            "sid=sid-value; Domain=example.com; Path=/app; Secure; HttpOnly; SameSite=Lax"
+        );
+        assert_eq!(domain.url(), "https://example.com/app");
+
+        let mut host_only = cookie("theme", "www.example.com", "/", false);
+        host_only.expires = Some(1.0);
+        assert_eq!(
+            host_only.to_set_cookie(),
+            "theme=theme-value; Path=/; Max-Age=0"
+        );
+        assert_eq!(host_only.url(), "http://www.example.com/");
+    }
+
+    #[cfg(feature = "reqwest-interop")]
+    #[test]
+    fn test_to_reqwest_cookies() {
+        use reqwest::cookie::CookieStore;
+
+        let session = BrowserSession {
+            cookies: vec![
+                cookie("sid", ".example.com", "/", true),
+                cookie("cart", "shop.example.com", "/cart", false),
+            ],
+            user_agent: String::new(),
+            url: String::new(),
+            extra_headers: HashMap::new(),
+        };
+        let jar = session.to_reqwest_cookies();
+        let header = |url: &str| {
+            jar.cookies(&url.parse().unwrap())
+                .map(|v| v.to_str().unwrap().to_string())
+                .unwrap_or_default()
+        };
+
+        // Domain cookie reaches subdomains, but only over https
+        assert_eq!(header("https://api.example.com/"), "sid=sid-value");
+        assert_eq!(header("http://api.example.com/"), "");
+        // Host-only cookie stays on its host and path
+        assert_eq!(
+            header("https://shop.example.com/cart/1"),
+            "sid=sid-value; cart=cart-value"
+        );
+        assert_eq!(header("https://shop.example.com/"), "sid=sid-value");
+        assert_eq!(header("https://other.example.com/cart"), "sid=sid-value");
+    }
 }
@@ -3,6 +3,12 @@
 //! These scripts are injected before any page content loads to patch
 //! detectable browser properties.
 
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use super::minify::minify_js;
+use super::Fingerprint;
 use crate::StealthConfig;
 
 /// Core WebDriver evasion - define webdriver=false on prototype (more realistic)
@@ -354,13 +360,12 @@ Object.defineProperty(Navigator.prototype, 'plugins', {
 });
 "#;
 
-/// Navigator properties (combined for efficiency)
+/// Navigator properties (combined for efficiency). Expects `NAV_PLATFORM`
+/// (the fingerprint's `navigator.platform`).
 pub const NAVIGATOR_PROPS_EVASION: &str = r#"
 // Language, platform, hardware - all on Navigator.prototype for consistency
 const navProps = {
-    languages: { get: () => ['en-US', 'en'] },
-    language: { get: () => 'en-US' },
-    platform: { get: () => 'MacIntel' },
+    platform: { get: () => NAV_PLATFORM },
     vendor: { get: () => 'Google Inc.' },
     hardwareConcurrency: { get: () => 8 },
     deviceMemory: { get: () => 8 },
@@ -371,6 +376,61 @@ for (const [prop, desc] of Object.entries(navProps)) {
 }
 "#;
 
+/// `navigator.language`/`navigator.languages` matching `languages`
+/// (first entry is the primary language). A single statement, safe to
+/// evaluate on its own or inside the evasion IIFE.
+pub fn languages_script(languages: &[String]) -> String {
+    let primary = languages.first().map(String::as_str).unwrap_or("en-US");
+    format!(
+        "Object.defineProperties(Navigator.prototype, {{ \
+         language: {{ get: () => {primary}, configurable: true }}, \
+         languages: {{ get: () => Object.freeze({list}), configurable: true }} }});",
+        primary = serde_json::to_string(primary).unwrap_or_default(),
+        list = serde_json::to_string(languages).unwrap_or_else(|_| "[]".into()),
+    )
+}
+
+/// `Accept-Language` value for `languages`, e.g. `de-DE,de;q=0.9,en;q=0.8`
+pub fn accept_language(languages: &[String]) -> String {
+    languages
+        .iter()
+        .enumerate()
+        .map(|(i, lang)| match i {
+            0 => lang.clone(),
+            _ => format!("{};q={:.1}", lang, (10 - i.min(9)) as f64 / 10.0),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Languages for a locale: `de-DE` → `["de-DE", "de"]`, `fr` → `["fr"]`
+pub fn locale_languages(locale: &str) -> Vec<String> {
+    let mut languages = vec![locale.to_string()];
+    if let Some((base, _)) = locale.split_once('-') {
+        languages.push(base.to_string());
+    }
+    languages
+}
+
+/// Screen metrics from the fingerprint. Expects `SCREEN` (`width`, `height`
+/// in device pixels, `colorDepth`).
+///
+/// Reported in CSS pixels at the page's real `devicePixelRatio`, and never
+/// smaller than the browser window, so screen, window and DPR agree.
+pub const SCREEN_EVASION: &str = r#"
+const screenSize = (physical, windowSize) =>
+    Math.max(Math.round(physical / (window.devicePixelRatio || 1)), windowSize);
+const screenProps = {
+    width: () => screenSize(SCREEN.width, window.outerWidth),
+    height: () => screenSize(SCREEN.height, window.outerHeight),
+    colorDepth: () => SCREEN.colorDepth,
+    pixelDepth: () => SCREEN.colorDepth
+};
+for (const [prop, get] of Object.entries(screenProps)) {
+    Object.defineProperty(Screen.prototype, prop, { get, configurable: true });
+}
+"#;
+
 /// Headless detection bypass
 pub const HEADLESS_EVASION: &str = r#"
 // Fix screen properties
@@ -424,6 +484,22 @@ try {
 } catch(e) {}
 "#;
 
+/// Page visibility - headless and background pages report `hidden`; a real
+/// foreground tab is `visible` and focused, doesn't flip on blur, and has an
+/// active user per the Idle Detection API
+pub const VISIBILITY_EVASION: &str = r#"
+Object.defineProperty(Document.prototype, 'hidden', { get: () => false, configurable: true });
+Object.defineProperty(Document.prototype, 'visibilityState', { get: () => 'visible', configurable: true });
+Document.prototype.hasFocus = function hasFocus() { return true; };
+// Window capture runs before any document listener
+window.addEventListener('visibilitychange', e => e.stopImmediatePropagation(), true);
+
+if (typeof IdleDetector !== 'undefined') {
+    Object.defineProperty(IdleDetector.prototype, 'userState', { get: () => 'active', configurable: true });
+    Object.defineProperty(IdleDetector.prototype, 'screenState', { get: () => 'unlocked', configurable: true });
+}
+"#;
+
 /// Battery API fix - defined on prototype to avoid getOwnPropertyNames detection
 pub const BATTERY_EVASION: &str = r#"
 // Define on Navigator.prototype to avoid Object.getOwnPropertyNames(navigator) detection
@@ -509,32 +585,104 @@ if (navigator.connection) {
 }
 "#;
 
-/// Combined fingerprint evasion (WebGL + Canvas + Audio)
+/// Combined fingerprint evasion (WebGL + Canvas + Audio). Expects `FP_SEED`
+/// and `WEBGL` (`vendor`, `renderer`, `precision` per precision type).
 pub const FINGERPRINT_EVASION: &str = r#"
-// WebGL vendor/renderer spoofing
+// Noise bit for pixel index i, derived from FP_SEED: the same drawing
+// always reads back identically for one identity, differently across them
+const noiseBit = (i) => {
+    let h = (FP_SEED ^ Math.imul(i, 0x9E3779B1)) >>> 0;
+    h = Math.imul(h ^ (h >>> 16), 0x85EBCA6B);
+    h = Math.imul(h ^ (h >>> 13), 0xC2B2AE35);
+    return (h ^ (h >>> 16)) & 1;
+};
+
+// WebGL vendor/renderer spoofing, from the fingerprint's GPU
+// Precision values live in a WeakMap read by the prototype getters, so the
+// returned objects get no own properties
+const spoofedPrecision = new WeakMap();
+if (typeof WebGLShaderPrecisionFormat !== 'undefined') {
+    const precisionProto = WebGLShaderPrecisionFormat.prototype;
+    ['rangeMin', 'rangeMax', 'precision'].forEach((key, i) => {
+        const desc = Object.getOwnPropertyDescriptor(precisionProto, key);
+        if (!desc || !desc.get) return;
+        Object.defineProperty(precisionProto, key, {
+            ...desc,
+            get: function() {
+                const values = spoofedPrecision.get(this);
+                return values ? values[i] : desc.get.call(this);
+            }
+        });
+    });
+}
 const spoofWebGL = (proto) => {
     const orig = proto.getParameter;
     proto.getParameter = function(p) {
-        if (p === 37445) return 'Intel Inc.';
-        if (p === 37446) return 'Intel Iris Pro Graphics 6200';
+        if (p === 37445) return WEBGL.vendor;
+        if (p === 37446) return WEBGL.renderer;
         return orig.call(this, p);
     };
+
+    const origPrecision = proto.getShaderPrecisionFormat;
+    proto.getShaderPrecisionFormat = function(shaderType, precisionType) {
+        const format = origPrecision.apply(this, arguments);
+        // LOW_FLOAT..HIGH_INT are 0x8DF0-0x8DF5
+        const values = WEBGL.precision[precisionType - 0x8DF0];
+        if (format && values) spoofedPrecision.set(format, values);
+        return format;
+    };
+
+    // Same per-pixel noise as the canvas, on RGBA/UNSIGNED_BYTE reads
+    const origReadPixels = proto.readPixels;
+    proto.readPixels = function(x, y, width, height, format, type, pixels) {
+        const result = origReadPixels.apply(this, arguments);
+        if (format === this.RGBA && type === this.UNSIGNED_BYTE && pixels && pixels.length) {
+            const bufferWidth = this.drawingBufferWidth;
+            const count = Math.min(width * height, pixels.length >> 2);
+            for (let p = 0; p < count; p++) {
+                pixels[p * 4] ^= noiseBit((y + ((p / width) | 0)) * bufferWidth + x + (p % width));
+            }
+        }
+        return result;
+    };
 };
 spoofWebGL(WebGLRenderingContext.prototype);
 if (typeof WebGL2RenderingContext !== 'undefined') spoofWebGL(WebGL2RenderingContext.prototype);
 
-// Canvas noise
-const origToDataURL = HTMLCanvasElement.prototype.toDataURL;
-HTMLCanvasElement.prototype.toDataURL = function(type) {
-    if (!type || type === 'image/png') {
-        const ctx = this.getContext('2d');
-        if (ctx) {
-            const d = ctx.getImageData(0, 0, this.width, this.height);
-            for (let i = 0; i < d.data.length; i += 4) d.data[i] ^= (Math.random() * 2) | 0;
-            ctx.putImageData(d, 0, 0);
-        }
+// Canvas noise: one bit per pixel keyed by its position, so repeated reads
+// of the same drawing hash identically. The canvas itself is never modified.
+const addNoise = (data, sx, sy, w, canvasWidth) => {
+    for (let i = 0; i < data.length; i += 4) {
+        const p = i >> 2;
+        data[i] ^= noiseBit((sy + ((p / w) | 0)) * canvasWidth + sx + (p % w));
     }
-    return origToDataURL.apply(this, arguments);
+};
+const origGetImageData = CanvasRenderingContext2D.prototype.getImageData;
+CanvasRenderingContext2D.prototype.getImageData = function(sx, sy, sw, sh) {
+    const d = origGetImageData.apply(this, arguments);
+    addNoise(d.data, sx | 0, sy | 0, d.width, this.canvas.width);
+    return d;
+};
+// Export a noised copy instead of the canvas (works for WebGL canvases too)
+const noisyCopy = (canvas) => {
+    if (!canvas.width || !canvas.height) return canvas;
+    const copy = document.createElement('canvas');
+    copy.width = canvas.width;
+    copy.height = canvas.height;
+    const ctx = copy.getContext('2d');
+    ctx.drawImage(canvas, 0, 0);
+    const d = origGetImageData.call(ctx, 0, 0, copy.width, copy.height);
+    addNoise(d.data, 0, 0, copy.width, copy.width);
+    ctx.putImageData(d, 0, 0);
+    return copy;
+};
+const origToDataURL = HTMLCanvasElement.prototype.toDataURL;
+HTMLCanvasElement.prototype.toDataURL = function() {
+    return origToDataURL.apply(noisyCopy(this), arguments);
+};
+const origToBlob = HTMLCanvasElement.prototype.toBlob;
+HTMLCanvasElement.prototype.toBlob = function() {
+    return origToBlob.apply(noisyCopy(this), arguments);
 };
 
 // Audio noise
@@ -546,35 +694,356 @@ AudioBuffer.prototype.getChannelData = function(ch) {
 };
 "#;
 
-/// WebRTC leak protection - prevent real IP from leaking
+/// Font probing normalization. Expects `FONT_LIST` (installed font names).
+///
+/// Detectors measure text in `'<probe>', <generic>` and compare against the
+/// generic alone. Fonts outside `FONT_LIST` are measured as the generic
+/// (absent); listed fonts that the host lacks get a small stable width offset
+/// (present). `document.fonts.check` answers from the list.
+pub const FONTS_EVASION: &str = r#"
+const fontSet = new Set(FONT_LIST.map(f => f.toLowerCase()));
+const genericFamilies = new Set(['serif', 'sans-serif', 'monospace', 'cursive', 'fantasy',
+    'system-ui', 'ui-serif', 'ui-sans-serif', 'ui-monospace', 'ui-rounded', 'emoji', 'math']);
+const splitFamilies = (list) => list.split(',').map(f => f.trim().replace(/^['"]|['"]$/g, '')).filter(Boolean);
+// "italic 72px 'Foo', monospace" -> { prefix: "italic 72px ", families: ['Foo', 'monospace'] }
+const parseFont = (font) => {
+    const m = /^(.*?\d+(?:\.\d+)?(?:px|pt|em|rem|%|vh|vw)(?:\s*\/\s*\S+)?\s+)(.+)$/.exec(font || '');
+    return m ? { prefix: m[1], families: splitFamilies(m[2]) } : null;
+};
+const isKnown = (family) => genericFamilies.has(family.toLowerCase()) || fontSet.has(family.toLowerCase());
+const fallbackOf = (families) => families.filter(f => genericFamilies.has(f.toLowerCase())).join(', ') || 'serif';
+// Stable per-family factor so "installed" fonts differ from the fallback
+const fontOffset = (family) => {
+    let h = 0;
+    for (const c of family.toLowerCase()) h = (Math.imul(h, 31) + c.charCodeAt(0)) | 0;
+    return 1.02 + ((h >>> 0) % 7) / 100;
+};
+
+const origMeasureText = CanvasRenderingContext2D.prototype.measureText;
+CanvasRenderingContext2D.prototype.measureText = function(text) {
+    const parsed = parseFont(this.font);
+    const primary = parsed && parsed.families[0];
+    if (!primary || genericFamilies.has(primary.toLowerCase())) return origMeasureText.apply(this, arguments);
+
+    const font = this.font;
+    this.font = parsed.prefix + fallbackOf(parsed.families);
+    const fallback = origMeasureText.call(this, text);
+    this.font = font;
+    if (!isKnown(primary)) return fallback;
+
+    const metrics = origMeasureText.call(this, text);
+    if (metrics.width !== fallback.width) return metrics;
+    const width = metrics.width * fontOffset(primary);
+    return new Proxy(metrics, {
+        get: (target, prop) => prop === 'width' ? width : Reflect.get(target, prop, target)
+    });
+};
+
+// Element sizes are scaled by how the text measures off-DOM with and without
+// the probed font, so the element itself (and its style) is never touched
+let measureContext = null;
+const textSize = (font, text) => {
+    if (!measureContext) {
+        measureContext = typeof OffscreenCanvas !== 'undefined'
+            ? new OffscreenCanvas(1, 1).getContext('2d')
+            : document.createElement('canvas').getContext('2d');
+    }
+    measureContext.font = font;
+    const m = origMeasureText.call(measureContext, text);
+    return [m.width, m.fontBoundingBoxAscent + m.fontBoundingBoxDescent];
+};
+const quoteFamily = (f) => genericFamilies.has(f.toLowerCase()) ? f : "'" + f.replace(/'/g, '') + "'";
+const spoofSize = (prop, index) => {
+    const desc = Object.getOwnPropertyDescriptor(HTMLElement.prototype, prop);
+    Object.defineProperty(HTMLElement.prototype, prop, {
+        ...desc,
+        get: function() {
+            const value = desc.get.call(this);
+            const families = this.style && this.style.fontFamily ? splitFamilies(this.style.fontFamily) : [];
+            const primary = families[0];
+            if (!value || !primary || genericFamilies.has(primary.toLowerCase())) return value;
+
+            const style = getComputedStyle(this);
+            const prefix = style.fontStyle + ' ' + style.fontWeight + ' ' + style.fontSize + ' ';
+            const text = this.textContent || '';
+            const real = textSize(prefix + families.map(quoteFamily).join(', '), text)[index];
+            const fallback = textSize(prefix + fallbackOf(families), text)[index];
+            if (!real || !fallback) return value;
+            if (!isKnown(primary)) return Math.round(value * fallback / real);
+            return real !== fallback ? value : Math.round(value * fontOffset(primary));
+        }
+    });
+};
+spoofSize('offsetWidth', 0);
+spoofSize('offsetHeight', 1);
+
+if (document.fonts && document.fonts.check) {
+    const origCheck = document.fonts.check.bind(document.fonts);
+    document.fonts.check = function(font, text) {
+        const parsed = parseFont(font);
+        if (!parsed) return origCheck(font, text);
+        return parsed.families.every(isKnown);
+    };
+}
+"#;
+
+/// WebGPU adapter info matching the spoofed WebGL renderer. Expects
+/// `GPU_INFO` (`vendor`, `architecture`).
+///
+/// Only touches `navigator.gpu` where the browser exposes it; where WebGPU is
+/// absent (e.g. insecure contexts) it stays undefined rather than stubbed.
+pub const WEBGPU_EVASION: &str = r#"
+if (navigator.gpu && typeof GPUAdapterInfo !== 'undefined') {
+    // Same values whether read from adapter.info or requestAdapterInfo()
+    const gpuInfo = { vendor: GPU_INFO.vendor, architecture: GPU_INFO.architecture, device: '', description: '' };
+    for (const [prop, value] of Object.entries(gpuInfo)) {
+        Object.defineProperty(GPUAdapterInfo.prototype, prop, {
+            get: () => value,
+            configurable: true,
+            enumerable: true
+        });
+    }
+}
+"#;
+
+/// How the WebRTC evasion keeps the real IP from leaking
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WebRtcPolicy {
+    /// Leave WebRTC untouched
+    Off,
+    /// Keep WebRTC working but hide addresses: host candidates get mDNS
+    /// names, server-reflexive (public IP) candidates are dropped and related
+    /// addresses zeroed, in events and in the local SDP. Relays (TURN) still
+    /// connect.
+    #[default]
+    MaskCandidates,
+    /// Strip every STUN server from the ICE configuration (TURN is kept)
+    DisableStun,
+}
+
+impl WebRtcPolicy {
+    /// Value of `WEBRTC_POLICY` in [`WEBRTC_EVASION`], `None` when off
+    fn script_value(self) -> Option<&'static str> {
+        match self {
+            Self::Off => None,
+            Self::MaskCandidates => Some("mask"),
+            Self::DisableStun => Some("disable_stun"),
+        }
+    }
+}
+
+/// WebRTC leak protection. Expects `WEBRTC_POLICY` (`'mask'` or
+/// `'disable_stun'`, see [`WebRtcPolicy`]).
 pub const WEBRTC_EVASION: &str = r#"
-// Disable WebRTC IP leak by overriding RTCPeerConnection
 if (typeof RTCPeerConnection !== 'undefined') {
-    const origRTCPeerConnection = RTCPeerConnection;
-
-    // Override to prevent IP leak via STUN
-    window.RTCPeerConnection = function(config, constraints) {
-        // Filter out Google STUN servers which are commonly used for IP detection
-        if (config && config.iceServers) {
-            config.iceServers = config.iceServers.filter(server => {
-                const urls = Array.isArray(server.urls) ? server.urls : [server.urls];
-                return !urls.some(url =>
-                    url.includes('stun.l.google.com') ||
-                    url.includes('stun1.l.google.com') ||
-                    url.includes('stun2.l.google.com')
-                );
+    const OrigPeerConnection = RTCPeerConnection;
+    const pcProto = OrigPeerConnection.prototype;
+
+    if (WEBRTC_POLICY === 'disable_stun') {
+        const stripStun = (config) => {
+            if (config && Array.isArray(config.iceServers)) {
+                config.iceServers = config.iceServers
+                    .map(server => {
+                        const urls = [].concat(server.urls || []);
+                        return Object.assign({}, server, { urls: urls.filter(url => !/^stuns?:/i.test(url)) });
+                    })
+                    .filter(server => server.urls.length > 0);
+            }
+            return config;
+        };
+        window.RTCPeerConnection = function RTCPeerConnection(config, constraints) {
+            return new OrigPeerConnection(stripStun(config), constraints);
+        };
+        window.RTCPeerConnection.prototype = pcProto;
+        // Statics such as generateCertificate
+        Object.setPrototypeOf(window.RTCPeerConnection, OrigPeerConnection);
+        const origSetConfiguration = pcProto.setConfiguration;
+        if (origSetConfiguration) {
+            pcProto.setConfiguration = function setConfiguration(config) {
+                return origSetConfiguration.call(this, stripStun(config));
+            };
+        }
+    }
+
+    if (WEBRTC_POLICY === 'mask') {
+        // One stable mDNS name per real address, like Chrome's own obfuscation.
+        // A v4 UUID from getRandomValues: randomUUID needs a secure context
+        const randomUuid = () => {
+            const b = crypto.getRandomValues(new Uint8Array(16));
+            b[6] = (b[6] & 0x0f) | 0x40;
+            b[8] = (b[8] & 0x3f) | 0x80;
+            const h = Array.from(b, x => x.toString(16).padStart(2, '0')).join('');
+            return h.slice(0, 8) + '-' + h.slice(8, 12) + '-' + h.slice(12, 16) + '-' + h.slice(16, 20) + '-' + h.slice(20);
+        };
+        const mdnsNames = new Map();
+        const mdnsName = (ip) => {
+            if (!mdnsNames.has(ip)) mdnsNames.set(ip, randomUuid() + '.local');
+            return mdnsNames.get(ip);
+        };
+
+        // `[a=]candidate:<foundation> <component> <proto> <priority> <address> <port> typ <type> ...`
+        // Returns null for candidates that must be dropped
+        const maskCandidate = (line) => {
+            const parts = line.split(' ');
+            const typ = parts.indexOf('typ');
+            const type = typ === -1 ? '' : parts[typ + 1];
+            if (type === 'srflx' || type === 'prflx') return null;
+            if (type === 'host' && parts.length > 4 && !parts[4].endsWith('.local')) {
+                parts[4] = mdnsName(parts[4]);
+            }
+            const raddr = parts.indexOf('raddr');
+            if (raddr !== -1 && parts.length > raddr + 3) {
+                parts[raddr + 1] = '0.0.0.0';
+                parts[raddr + 3] = '0';
+            }
+            return parts.join(' ');
+        };
+
+        const maskSdp = (sdp) => sdp
+            .split('\r\n')
+            .map(line => {
+                if (line.startsWith('a=candidate:')) return maskCandidate(line);
+                if (line.startsWith('c=IN IP4 ')) return 'c=IN IP4 0.0.0.0';
+                if (line.startsWith('c=IN IP6 ')) return 'c=IN IP6 ::';
+                return line;
+            })
+            .filter(line => line !== null)
+            .join('\r\n');
+
+        // Masked copy of an RTCIceCandidate, or null if it must be dropped
+        const maskIceCandidate = (candidate) => {
+            if (!candidate || !candidate.candidate) return candidate;
+            const masked = maskCandidate(candidate.candidate);
+            if (masked === null) return null;
+            return new RTCIceCandidate({
+                candidate: masked,
+                sdpMid: candidate.sdpMid,
+                sdpMLineIndex: candidate.sdpMLineIndex,
+                usernameFragment: candidate.usernameFragment
             });
+        };
+
+        // Swap the event's candidate in place so target/currentTarget stay intact
+        const maskEvent = (event) => {
+            const candidate = event.candidate;
+            if (!candidate || !candidate.candidate) return event;
+            const masked = maskIceCandidate(candidate);
+            if (masked === null) return null;
+            Object.defineProperty(event, 'candidate', { value: masked, configurable: true });
+            return event;
+        };
+
+        // getStats() candidate entries carry the raw addresses too
+        const maskStat = (stat) => {
+            if (!stat || stat.type !== 'local-candidate') return stat;
+            const copy = Object.assign({}, stat);
+            for (const key of ['address', 'ip']) {
+                if (typeof copy[key] === 'string' && !copy[key].endsWith('.local')) {
+                    copy[key] = copy.candidateType === 'host' ? mdnsName(copy[key]) : '0.0.0.0';
+                }
+            }
+            if ('relatedAddress' in copy) {
+                copy.relatedAddress = '0.0.0.0';
+                copy.relatedPort = 0;
+            }
+            return copy;
+        };
+        const mapMethods = ['get', 'has', 'forEach', 'entries', 'keys', 'values', Symbol.iterator];
+        const maskReport = (report) => {
+            const masked = new Map();
+            report.forEach((stat, id) => masked.set(id, maskStat(stat)));
+            return new Proxy(report, {
+                get(target, prop) {
+                    if (prop === 'size') return masked.size;
+                    if (mapMethods.includes(prop)) return masked[prop].bind(masked);
+                    const value = Reflect.get(target, prop, target);
+                    return typeof value === 'function' ? value.bind(target) : value;
+                }
+            });
+        };
+        for (const proto of [pcProto, window.RTCRtpSender && RTCRtpSender.prototype, window.RTCRtpReceiver && RTCRtpReceiver.prototype]) {
+            if (!proto || !proto.getStats) continue;
+            const origGetStats = proto.getStats;
+            proto.getStats = function getStats(...args) {
+                const result = origGetStats.apply(this, args);
+                return result && typeof result.then === 'function' ? result.then(maskReport) : result;
+            };
         }
-        return new origRTCPeerConnection(config, constraints);
-    };
 
-    // Copy prototype
-    window.RTCPeerConnection.prototype = origRTCPeerConnection.prototype;
+        if (typeof RTCIceTransport !== 'undefined') {
+            const iceProto = RTCIceTransport.prototype;
+            if (iceProto.getLocalCandidates) {
+                const origGetLocal = iceProto.getLocalCandidates;
+                iceProto.getLocalCandidates = function getLocalCandidates() {
+                    return origGetLocal.call(this).map(maskIceCandidate).filter(c => c !== null);
+                };
+            }
+            if (iceProto.getSelectedCandidatePair) {
+                const origGetPair = iceProto.getSelectedCandidatePair;
+                iceProto.getSelectedCandidatePair = function getSelectedCandidatePair() {
+                    const pair = origGetPair.call(this);
+                    if (!pair || !pair.local) return pair;
+                    const local = maskIceCandidate(pair.local);
+                    return local === null ? null : { local, remote: pair.remote };
+                };
+            }
+        }
 
-    // Preserve static methods
-    Object.keys(origRTCPeerConnection).forEach(key => {
-        window.RTCPeerConnection[key] = origRTCPeerConnection[key];
-    });
+        const wrappers = new WeakMap();
+        const wrap = (listener) => {
+            if (!listener || (typeof listener !== 'function' && typeof listener.handleEvent !== 'function')) {
+                return listener;
+            }
+            if (!wrappers.has(listener)) {
+                wrappers.set(listener, function(event) {
+                    const masked = maskEvent(event);
+                    if (masked === null) return;
+                    return typeof listener === 'function'
+                        ? listener.call(this, masked)
+                        : listener.handleEvent(masked);
+                });
+            }
+            return wrappers.get(listener);
+        };
+
+        const origAdd = pcProto.addEventListener;
+        const origRemove = pcProto.removeEventListener;
+        pcProto.addEventListener = function addEventListener(type, listener, options) {
+            return origAdd.call(this, type, type === 'icecandidate' ? wrap(listener) : listener, options);
+        };
+        pcProto.removeEventListener = function removeEventListener(type, listener, options) {
+            const target = type === 'icecandidate' && wrappers.has(listener) ? wrappers.get(listener) : listener;
+            return origRemove.call(this, type, target, options);
+        };
+
+        const handlerDesc = Object.getOwnPropertyDescriptor(pcProto, 'onicecandidate');
+        if (handlerDesc && handlerDesc.set) {
+            const handlers = new WeakMap();
+            Object.defineProperty(pcProto, 'onicecandidate', {
+                get() { return handlers.has(this) ? handlers.get(this) : null; },
+                set(handler) {
+                    handlers.set(this, handler);
+                    handlerDesc.set.call(this, typeof handler === 'function' ? wrap(handler) : handler);
+                },
+                enumerable: handlerDesc.enumerable,
+                configurable: true
+            });
+        }
+
+        for (const prop of ['localDescription', 'currentLocalDescription', 'pendingLocalDescription']) {
+            const desc = Object.getOwnPropertyDescriptor(pcProto, prop);
+            if (!desc || !desc.get) continue;
+            Object.defineProperty(pcProto, prop, {
+                get() {
+                    const description = desc.get.call(this);
+                    if (!description || !description.sdp) return description;
+                    return new RTCSessionDescription({ type: description.type, sdp: maskSdp(description.sdp) });
+                },
+                enumerable: desc.enumerable,
+                configurable: true
+            });
+        }
+    }
 }
 
 // Also handle webkitRTCPeerConnection
@@ -687,33 +1156,145 @@ Date.prototype.getTimezoneOffset = function() {
 };
 "#;
 
+/// Every static evasion block, minified once on first use
+const STATIC_EVASIONS: &[&str] = &[
+    WEBDRIVER_EVASION,
+    CDP_EVASION,
+    CHROME_RUNTIME_EVASION,
+    PERMISSIONS_EVASION,
+    PLUGINS_EVASION,
+    NAVIGATOR_PROPS_EVASION,
+    SCREEN_EVASION,
+    HEADLESS_EVASION,
+    VISIBILITY_EVASION,
+    BATTERY_EVASION,
+    NAVIGATOR_EXTRA_EVASION,
+    FINGERPRINT_EVASION,
+    FONTS_EVASION,
+    WEBGPU_EVASION,
+    WEBRTC_EVASION,
+    SPEECH_EVASION,
+    MEDIA_DEVICES_EVASION,
+    BLUETOOTH_EVASION,
+    TIMEZONE_EVASION,
+];
+
+static MINIFIED: OnceLock<HashMap<&'static str, String>> = OnceLock::new();
+
+/// Minified form of an evasion block (cached for the static ones)
+fn minified(block: &str) -> Cow<'_, str> {
+    let cache = MINIFIED.get_or_init(|| {
+        STATIC_EVASIONS
+            .iter()
+            .map(|block| (*block, minify_js(block)))
+            .collect()
+    });
+    match cache.get(block) {
+        Some(min) => Cow::Borrowed(min),
+        None => Cow::Owned(minify_js(block)),
+    }
+}
+
 /// Build the complete evasion script based on config
+///
+/// Uses `config.fingerprint`, or a random identity if none is set.
 pub fn build_evasion_script(config: &StealthConfig) -> String {
+    match &config.fingerprint {
+        Some(fingerprint) => build_evasion_script_for(config, fingerprint),
+        None => build_evasion_script_for(config, &Fingerprint::random()),
+    }
+}
+
+/// Build the evasion script for a specific identity
+pub fn build_evasion_script_for(config: &StealthConfig, fingerprint: &Fingerprint) -> String {
+    let languages = languages_script(config.effective_languages());
+    let seed = format!(
+        "const FP_SEED = {}; const WEBGL = {};",
+        fingerprint.seed,
+        serde_json::json!({
+            "vendor": fingerprint.webgl_vendor,
+            "renderer": fingerprint.webgl_renderer,
+            "precision": fingerprint.shader_precisions(),
+        })
+    );
+    let screen = format!(
+        "const SCREEN = {{ width: {}, height: {}, colorDepth: {} }};",
+        fingerprint.screen_width, fingerprint.screen_height, fingerprint.color_depth
+    );
+    let webrtc_policy = config
+        .webrtc_policy
+        .script_value()
+        .map(|policy| format!("const WEBRTC_POLICY = '{}';", policy));
+    let (gpu_vendor, gpu_architecture) = fingerprint.webgpu_adapter_info();
+    let gpu_info = format!(
+        "const GPU_INFO = {{ vendor: '{}', architecture: '{}' }};",
+        gpu_vendor, gpu_architecture
+    );
+    let nav_platform = format!(
+        "const NAV_PLATFORM = '{}';",
+        fingerprint.platform.navigator_platform()
+    );
+    let font_list = format!(
+        "const FONT_LIST = {};",
+        serde_json::to_string(fingerprint.platform.fonts()).unwrap_or_else(|_| "[]".into())
+    );
     let mut scripts = vec![
         WEBDRIVER_EVASION,
         CDP_EVASION,
         CHROME_RUNTIME_EVASION,
         PERMISSIONS_EVASION,
         PLUGINS_EVASION,
-        NAVIGATOR_PROPS_EVASION, // Combined: languages, platform, hardware, etc.
+        &nav_platform,
+        NAVIGATOR_PROPS_EVASION, // Combined: platform, hardware, etc.
+        &languages,
+        &screen,
+        SCREEN_EVASION,
         HEADLESS_EVASION,
-        BATTERY_EVASION,
         NAVIGATOR_EXTRA_EVASION,
-        // New evasions for better coverage
-        WEBRTC_EVASION,
-        SPEECH_EVASION,
-        MEDIA_DEVICES_EVASION,
-        BLUETOOTH_EVASION,
-        TIMEZONE_EVASION,
     ];
 
+    // Optional API overrides, each only when enabled
+    let optional = [
+        (config.battery_spoof, BATTERY_EVASION),
+        (config.speech_spoof, SPEECH_EVASION),
+        (config.media_devices_spoof, MEDIA_DEVICES_EVASION),
+        (config.bluetooth_spoof, BLUETOOTH_EVASION),
+        // A CDP override makes the engine report the zone; the JS patch would fight it
+        (
+            config.timezone_spoof && config.timezone_override.is_none(),
+            TIMEZONE_EVASION,
+        ),
+    ];
+    scripts.extend(optional.iter().filter(|(on, _)| *on).map(|(_, s)| *s));
+
+    if let Some(policy) = &webrtc_policy {
+        scripts.push(policy);
+        scripts.push(WEBRTC_EVASION);
+    }
+
     // Add fingerprint evasion if any spoofing enabled
     if config.webgl_spoof || config.canvas_spoof || config.audio_spoof {
+        scripts.push(&seed);
         scripts.push(FINGERPRINT_EVASION);
     }
 
-    // Wrap in IIFE
-    format!("(function(){{{}}})();", scripts.join("\n"))
+    if config.font_spoof {
+        scripts.push(&font_list);
+        scripts.push(FONTS_EVASION);
+    }
+
+    if config.visibility_spoof {
+        scripts.push(VISIBILITY_EVASION);
+    }
+
+    if config.webgpu_spoof {
+        scripts.push(&gpu_info);
+        scripts.push(WEBGPU_EVASION);
+    }
+
+    // Minify, then wrap in IIFE
+    let body: Vec<Cow<str>> = scripts.iter().map(|s| minified(s)).collect();
+    format!("(function(){{{}}})();", body.join("\n"))
 }
 
 /// Get the full evasion script (all options enabled)
@@ -735,6 +1316,215 @@ mod tests {
         assert!(script.contains("AudioBuffer"));
     }
 
+    #[test]
+    fn test_languages_follow_config() {
+        let config = StealthConfig {
+            languages: vec!["de-DE".into(), "de".into()],
+            ..Default::default()
+        };
+        let script = build_evasion_script(&config);
+        assert!(script.contains(r#"["de-DE","de"]"#));
+        assert!(script.contains(r#"get:()=>"de-DE""#));
+    }
+
+    #[test]
+    fn test_languages_follow_fingerprint_by_default() {
+        let mut fingerprint = Fingerprint::random();
+        fingerprint.languages = vec!["ja-JP".into(), "ja".into()];
+        let config = StealthConfig {
+            fingerprint: Some(fingerprint),
+            ..Default::default()
+        };
+        let script = build_evasion_script(&config);
+        assert!(script.contains(r#"["ja-JP","ja"]"#));
+    }
+
+    #[test]
+    fn test_canvas_noise_is_seeded() {
+        let config = StealthConfig::default();
+        let mut fingerprint = Fingerprint::random();
+        fingerprint.seed = 1234;
+
+        let script = build_evasion_script_for(&config, &fingerprint);
+        assert!(script.contains("const FP_SEED=1234;"));
+        assert!(script.contains("toBlob"));
+        assert!(script.contains("getImageData"));
+        assert!(!script.contains("(Math.random()*2)|0"));
+
+        // Same identity, same script; another seed changes it
+        assert_eq!(script, build_evasion_script_for(&config, &fingerprint));
+        fingerprint.seed = 5678;
+        assert_ne!(script, build_evasion_script_for(&config, &fingerprint));
+    }
+
+    #[test]
+    fn test_webgl_reads_are_spoofed() {
+        let script = build_evasion_script(&StealthConfig::default());
+        assert!(script.contains("proto.readPixels=function"));
+        assert!(script.contains("proto.getShaderPrecisionFormat=function"));
+        assert!(script.contains("noiseBit("));
+        // Precision objects get no own properties
+        assert!(!script.contains("Object.defineProperty(format"));
+    }
+
+    #[test]
+    fn test_webgl_follows_fingerprint() {
+        let mut fingerprint = Fingerprint::random();
+        fingerprint.webgl_vendor = "Google Inc. (AMD)".into();
+        fingerprint.webgl_renderer = "ANGLE (AMD, AMD Radeon RX 6800 XT, Direct3D11)".into();
+        let script = build_evasion_script_for(&StealthConfig::default(), &fingerprint);
+        assert!(script.contains("\"renderer\":\"ANGLE (AMD, AMD Radeon RX 6800 XT, Direct3D11)\""));
+        assert!(script.contains("\"vendor\":\"Google Inc. (AMD)\""));
+        assert!(script.contains("[127,127,23]"));
+        assert!(!script.contains("Intel Iris"));
+    }
+
+    #[test]
+    fn test_fonts_only_when_enabled() {
+        let mut fingerprint = Fingerprint::random();
+        fingerprint.platform = crate::stealth::Platform::Windows;
+
+        let on = build_evasion_script_for(&StealthConfig::default(), &fingerprint);
+        assert!(on.contains("const FONT_LIST=["));
+        assert!(on.contains("\"Segoe UI\""));
+        assert!(on.contains("CanvasRenderingContext2D.prototype.measureText="));
+        // navigator.platform agrees with the fonts
+        assert!(on.contains("const NAV_PLATFORM='Win32';"));
+        // Size probes are answered off-DOM, never by restyling the element
+        assert!(!on.contains("this.style.fontFamily="));
+
+        let config = StealthConfig {
+            font_spoof: false,
+            ..Default::default()
+        };
+        let off = build_evasion_script_for(&config, &fingerprint);
+        assert!(!off.contains("FONT_LIST"));
+        assert!(!off.contains("prototype.measureText="));
+    }
+
+    #[test]
+    fn test_optional_evasions_follow_config() {
+        let full = build_evasion_script(&StealthConfig::default());
+        assert!(full.contains("RTCPeerConnection"));
+        assert!(full.contains("getTimezoneOffset"));
+
+        let config = StealthConfig {
+            webrtc_policy: WebRtcPolicy::Off,
+            ..Default::default()
+        };
+        let script = build_evasion_script(&config);
+        assert!(!script.contains("RTCPeerConnection"));
+        assert!(script.contains("getTimezoneOffset"));
+
+        let config = StealthConfig {
+            timezone_override: Some("Europe/Berlin".into()),
+            ..Default::default()
+        };
+        assert!(!build_evasion_script(&config).contains("getTimezoneOffset"));
+    }
+
+    #[test]
+    fn test_visibility_only_when_enabled() {
+        let on = build_evasion_script(&StealthConfig::default());
+        assert!(on.contains("'visibilityState'"));
+        assert!(on.contains("addEventListener('visibilitychange'"));
+        assert!(on.contains("Document.prototype.hasFocus="));
+        assert!(on.contains("IdleDetector.prototype"));
+
+        let config = StealthConfig {
+            visibility_spoof: false,
+            ..Default::default()
+        };
+        assert!(!build_evasion_script(&config).contains("'visibilityState'"));
+    }
+
+    #[test]
+    fn test_webrtc_policy_in_script() {
+        let script_for = |webrtc_policy| {
+            build_evasion_script(&StealthConfig {
+                webrtc_policy,
+                ..Default::default()
+            })
+        };
+
+        let mask = script_for(WebRtcPolicy::MaskCandidates);
+        assert!(mask.contains("const WEBRTC_POLICY='mask';"));
+        assert!(mask.contains("maskCandidate"));
+        // Works on insecure origins, and covers the stats and ICE transport paths
+        assert!(!mask.contains("randomUUID"));
+        assert!(mask.contains("getStats"));
+        assert!(mask.contains("getLocalCandidates"));
+
+        let stun = script_for(WebRtcPolicy::DisableStun);
+        assert!(stun.contains("const WEBRTC_POLICY='disable_stun';"));
+
+        let off = script_for(WebRtcPolicy::Off);
+        assert!(!off.contains("WEBRTC_POLICY"));
+        assert!(!off.contains("RTCPeerConnection"));
+    }
+
+    #[test]
+    fn test_webgpu_only_when_enabled() {
+        let on = build_evasion_script(&StealthConfig::default());
+        assert!(on.contains("navigator.gpu"));
+        assert!(!on.contains("'gen-8', device"));
+
+        let mut fingerprint = Fingerprint::random();
+        fingerprint.webgl_renderer = "ANGLE (NVIDIA, NVIDIA GeForce RTX 3080, Direct3D11)".into();
+        let nvidia = build_evasion_script_for(&StealthConfig::default(), &fingerprint);
+        assert!(nvidia.contains("const GPU_INFO={vendor:'nvidia',architecture:'ampere'};"));
+        assert!(on.contains("GPUAdapterInfo.prototype"));
+
+        let config = StealthConfig {
+            webgpu_spoof: false,
+            ..Default::default()
+        };
+        assert!(!build_evasion_script(&config).contains("navigator.gpu"));
+    }
+
+    #[test]
+    fn test_screen_follows_fingerprint() {
+        let mut fingerprint = Fingerprint::random();
+        fingerprint.screen_width = 1920;
+        fingerprint.screen_height = 1080;
+        fingerprint.color_depth = 24;
+
+        let script = build_evasion_script_for(&StealthConfig::default(), &fingerprint);
+        assert!(script.contains("const SCREEN={width:1920,height:1080,colorDepth:24};"));
+        assert!(script.contains("screenSize(SCREEN.width,window.outerWidth)"));
+    }
+
+    #[test]
+    fn test_script_is_minified() {
+        let script = build_evasion_script(&StealthConfig::default());
+        let raw_len: usize = STATIC_EVASIONS.iter().map(|block| block.len()).sum();
+        assert!(script.len() < raw_len);
+        assert!(!script.contains("// "));
+        for ident in [
+            "webdriver",
+            "cdcPattern",
+            "RTCPeerConnection",
+            "FP_SEED",
+            "noiseBit",
+        ] {
+            assert!(script.contains(ident), "missing {}", ident);
+        }
+        // Regex literals survive intact
+        assert!(script.contains(r"/^cdc_|^\$cdc_|^__webdriver|"));
+    }
+
+    #[test]
+    fn test_accept_language() {
+        let languages = locale_languages("de-DE");
+        assert_eq!(languages, ["de-DE", "de"]);
+        assert_eq!(accept_language(&languages), "de-DE,de;q=0.9");
+        assert_eq!(
+            accept_language(&["en-US".into(), "en".into(), "fr".into()]),
+            "en-US,en;q=0.9,fr;q=0.8"
+        );
+        assert_eq!(locale_languages("fr"), ["fr"]);
+    }
+
     #[test]
     fn test_script_is_wrapped_in_iife() {
         let config = StealthConfig::default();
@@ -5,6 +5,8 @@
 use rand::prelude::IndexedRandom;
 use rand::Rng;
 
+use crate::cdp::types::{UserAgentBrandVersion, UserAgentMetadata};
+
 /// Chrome versions (recent, realistic)
 const CHROME_VERSIONS: &[&str] = &[
     "120.0.0.0",
@@ -66,26 +68,103 @@ const SCREEN_RESOLUTIONS: &[(u32, u32)] = &[
 
 /// Generate a random realistic user agent
 pub fn random_user_agent() -> String {
+    random_user_agent_for(Platform::random())
+}
+
+/// Random realistic user agent for `platform`
+fn random_user_agent_for(platform: Platform) -> String {
     let mut rng = rand::rng();
 
     let chrome_version = CHROME_VERSIONS.choose(&mut rng).unwrap();
 
-    // 70% Mac, 30% Windows
-    if rng.random_bool(0.7) {
-        let macos = MACOS_VERSIONS.choose(&mut rng).unwrap();
-        format!(
-            "Mozilla/5.0 (Macintosh; Intel Mac OS X {}) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/{} Safari/537.36",
-            macos, chrome_version
-        )
+    match platform {
+        Platform::MacOS => {
+            let macos = MACOS_VERSIONS.choose(&mut rng).unwrap();
+            format!(
+                "Mozilla/5.0 (Macintosh; Intel Mac OS X {}) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/{} Safari/537.36",
+                macos, chrome_version
+            )
+        }
+        Platform::Windows => {
+            let windows = WINDOWS_VERSIONS.choose(&mut rng).unwrap();
+            format!(
+                "Mozilla/5.0 (Windows NT {}; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/{} Safari/537.36",
+                windows, chrome_version
+            )
+        }
+    }
+}
+
+/// `navigator.platform` Chrome reports alongside `user_agent`
+pub fn navigator_platform_for(user_agent: &str) -> Option<&'static str> {
+    if user_agent.contains("Macintosh") {
+        Some(Platform::MacOS.navigator_platform())
+    } else if user_agent.contains("Windows NT") {
+        Some(Platform::Windows.navigator_platform())
+    } else if user_agent.contains("Linux") {
+        Some("Linux x86_64")
     } else {
-        let windows = WINDOWS_VERSIONS.choose(&mut rng).unwrap();
-        format!(
-            "Mozilla/5.0 (Windows NT {}; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/{} Safari/537.36",
-            windows, chrome_version
-        )
+        None
     }
 }
 
+/// Client Hints (`navigator.userAgentData`, `Sec-CH-UA-*`) matching a Chrome
+/// `user_agent` string; `None` if it isn't one
+pub fn user_agent_metadata(user_agent: &str) -> Option<UserAgentMetadata> {
+    let full_version = user_agent
+        .split("Chrome/")
+        .nth(1)?
+        .split_whitespace()
+        .next()?;
+    let major = full_version.split('.').next()?;
+
+    let (platform, platform_version) = if let Some(rest) = user_agent.split("Mac OS X ").nth(1) {
+        let version = rest.split([')', ';']).next().unwrap_or_default();
+        ("macOS", version.replace('_', "."))
+    } else if user_agent.contains("Windows NT") {
+        ("Windows", "10.0.0".to_string())
+    } else if user_agent.contains("Linux") {
+        ("Linux", String::new())
+    } else {
+        return None;
+    };
+
+    let brands = |chrome: &str, grease: &str| {
+        [
+            ("Not_A Brand", grease),
+            ("Chromium", chrome),
+            ("Google Chrome", chrome),
+        ]
+        .into_iter()
+        .map(|(brand, version)| UserAgentBrandVersion {
+            brand: brand.to_string(),
+            version: version.to_string(),
+        })
+        .collect()
+    };
+
+    Some(UserAgentMetadata {
+        brands: brands(major, "8"),
+        full_version_list: brands(full_version, "8.0.0.0"),
+        platform: platform.to_string(),
+        platform_version,
+        architecture: "x86".to_string(),
+        model: String::new(),
+        mobile: false,
+        bitness: "64".to_string(),
+    })
+}
+
+/// GPU vendor from an ANGLE renderer string: `ANGLE (NVIDIA, ...)` -> `NVIDIA`.
+/// Empty if `renderer` isn't in that form.
+fn angle_gpu_vendor(renderer: &str) -> &str {
+    renderer
+        .strip_prefix("ANGLE (")
+        .and_then(|rest| rest.split(',').next())
+        .unwrap_or_default()
+        .trim()
+}
+
 /// Browser fingerprint data
 #[derive(Debug, Clone)]
 pub struct Fingerprint {
@@ -100,6 +179,9 @@ pub struct Fingerprint {
     pub languages: Vec<String>,
     pub webgl_vendor: String,
     pub webgl_renderer: String,
+    /// Seed for the canvas/WebGL/audio noise: the same seed always produces
+    /// the same fingerprint hashes, different seeds produce different ones
+    pub seed: u32,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -108,35 +190,123 @@ pub enum Platform {
     Windows,
 }
 
+/// Fonts present on a stock macOS install
+const MACOS_FONTS: &[&str] = &[
+    "American Typewriter",
+    "Andale Mono",
+    "Arial",
+    "Arial Black",
+    "Arial Narrow",
+    "Avenir",
+    "Avenir Next",
+    "Baskerville",
+    "Big Caslon",
+    "Comic Sans MS",
+    "Courier",
+    "Courier New",
+    "Didot",
+    "Futura",
+    "Geneva",
+    "Georgia",
+    "Gill Sans",
+    "Helvetica",
+    "Helvetica Neue",
+    "Impact",
+    "Lucida Grande",
+    "Menlo",
+    "Monaco",
+    "Optima",
+    "Palatino",
+    "Skia",
+    "Tahoma",
+    "Times",
+    "Times New Roman",
+    "Trebuchet MS",
+    "Verdana",
+];
+
+/// Fonts present on a stock Windows 10/11 install
+const WINDOWS_FONTS: &[&str] = &[
+    "Arial",
+    "Arial Black",
+    "Bahnschrift",
+    "Calibri",
+    "Cambria",
+    "Cambria Math",
+    "Candara",
+    "Comic Sans MS",
+    "Consolas",
+    "Constantia",
+    "Corbel",
+    "Courier New",
+    "Ebrima",
+    "Franklin Gothic Medium",
+    "Gabriola",
+    "Georgia",
+    "Impact",
+    "Lucida Console",
+    "Lucida Sans Unicode",
+    "Microsoft Sans Serif",
+    "Palatino Linotype",
+    "Segoe Print",
+    "Segoe Script",
+    "Segoe UI",
+    "Sylfaen",
+    "Tahoma",
+    "Times New Roman",
+    "Trebuchet MS",
+    "Verdana",
+];
+
+impl Platform {
+    /// 70% Mac, 30% Windows
+    fn random() -> Self {
+        if rand::rng().random_bool(0.7) {
+            Platform::MacOS
+        } else {
+            Platform::Windows
+        }
+    }
+
+    /// `navigator.platform` reported by Chrome on this platform
+    pub fn navigator_platform(&self) -> &'static str {
+        match self {
+            Platform::MacOS => "MacIntel",
+            Platform::Windows => "Win32",
+        }
+    }
+
+    /// Standard installed fonts for this platform
+    pub fn fonts(&self) -> &'static [&'static str] {
+        match self {
+            Platform::MacOS => MACOS_FONTS,
+            Platform::Windows => WINDOWS_FONTS,
+        }
+    }
+}
+
 impl Fingerprint {
     /// Generate a random consistent fingerprint
     pub fn random() -> Self {
         let mut rng = rand::rng();
 
-        let platform = if rng.random_bool(0.7) {
-            Platform::MacOS
-        } else {
-            Platform::Windows
-        };
+        let platform = Platform::random();
 
         let (screen_width, screen_height) = *SCREEN_RESOLUTIONS.choose(&mut rng).unwrap();
 
-        let (webgl_vendor, webgl_renderer) = match platform {
-            Platform::MacOS => {
-                let renderer = WEBGL_RENDERERS_MAC.choose(&mut rng).unwrap();
-                ("Google Inc. (Apple)", *renderer)
-            }
-            Platform::Windows => {
-                let renderer = WEBGL_RENDERERS_WINDOWS.choose(&mut rng).unwrap();
-                ("Google Inc. (NVIDIA Corporation)", *renderer)
-            }
+        let renderers = match platform {
+            Platform::MacOS => WEBGL_RENDERERS_MAC,
+            Platform::Windows => WEBGL_RENDERERS_WINDOWS,
         };
+        let webgl_renderer = *renderers.choose(&mut rng).unwrap();
+        // Chrome reports ANGLE's GPU vendor, e.g. "Google Inc. (NVIDIA)"
+        let webgl_vendor = format!("Google Inc. ({})", angle_gpu_vendor(webgl_renderer));
 
         let hardware_concurrency = *[4, 8, 10, 12, 16].choose(&mut rng).unwrap();
         let device_memory = *[8, 16, 32].choose(&mut rng).unwrap();
 
         Self {
-            user_agent: random_user_agent(),
+            user_agent: random_user_agent_for(platform),
             platform,
             screen_width,
             screen_height,
@@ -145,10 +315,98 @@ impl Fingerprint {
             device_memory,
             timezone: "America/Los_Angeles".to_string(),
             languages: vec!["en-US".to_string(), "en".to_string()],
-            webgl_vendor: webgl_vendor.to_string(),
+            webgl_vendor,
             webgl_renderer: webgl_renderer.to_string(),
+            seed: rng.random(),
+        }
+    }
+
+    /// `[rangeMin, rangeMax, precision]` WebGL reports for each shader
+    /// precision type, `LOW_FLOAT` through `HIGH_INT`, on this GPU. Desktop
+    /// GPUs behind ANGLE run every precision at full 32 bits; anything else
+    /// is treated as a mobile-class GPU where only `high` does.
+    pub fn shader_precisions(&self) -> [[i32; 3]; 6] {
+        const FLOAT: [i32; 3] = [127, 127, 23];
+        const INT: [i32; 3] = [31, 30, 0];
+        const HALF_FLOAT: [i32; 3] = [15, 15, 10];
+        const SHORT_INT: [i32; 3] = [15, 14, 0];
+        match angle_gpu_vendor(&self.webgl_renderer) {
+            "Apple" | "Intel" | "NVIDIA" | "AMD" => [FLOAT, FLOAT, FLOAT, INT, INT, INT],
+            _ => [HALF_FLOAT, HALF_FLOAT, FLOAT, SHORT_INT, SHORT_INT, INT],
+        }
+    }
+
+    /// WebGPU `GPUAdapterInfo` `(vendor, architecture)` for the same GPU as
+    /// `webgl_renderer`, so the two APIs agree. Empty strings (as Chrome
+    /// reports for unknown adapters) when the GPU isn't recognized.
+    pub fn webgpu_adapter_info(&self) -> (&'static str, &'static str) {
+        let renderer = self.webgl_renderer.as_str();
+        let architecture = |table: &[(&str, &'static str)]| {
+            table
+                .iter()
+                .find(|(model, _)| renderer.contains(model))
+                .map_or("", |(_, arch)| *arch)
+        };
+        match angle_gpu_vendor(renderer) {
+            "Apple" => ("apple", architecture(&[("Apple M", "metal-3")])),
+            "Intel" => (
+                "intel",
+                architecture(&[
+                    ("Iris Pro Graphics 6200", "gen-8"),
+                    ("UHD Graphics 630", "gen-9"),
+                    ("UHD Graphics 770", "gen-12lp"),
+                ]),
+            ),
+            "NVIDIA" => (
+                "nvidia",
+                architecture(&[
+                    ("RTX 40", "lovelace"),
+                    ("RTX 30", "ampere"),
+                    ("GTX 10", "pascal"),
+                ]),
+            ),
+            "AMD" => (
+                "amd",
+                architecture(&[("RX 6", "rdna-2"), ("RX 7", "rdna-3")]),
+            ),
+            _ => ("", ""),
         }
     }
+
+    /// Device pixels per CSS pixel for this screen: Retina-class Mac screens
+    /// run at 2x, 4K Windows screens at the usual 150%, everything else 1x
+    pub fn device_scale_factor(&self) -> f64 {
+        match self.platform {
+            Platform::MacOS if self.screen_width >= 2560 && self.screen_height >= 1600 => 2.0,
+            Platform::Windows if self.screen_width >= 3840 => 1.5,
+            _ => 1.0,
+        }
+    }
+
+    /// Chrome flags for the parts of the identity that are fixed at launch:
+    /// user agent, a maximized window (the screen in CSS pixels), locale and
+    /// device scale factor. `Browser::launch_with_config` merges these when
+    /// [`crate::StealthConfig::fingerprint`] is set, so the flags agree with
+    /// what the evasion script reports. Explicit config fields win there:
+    /// `user_agent` if set, the viewport for the window size, and
+    /// `languages` for the locale flags unless left at the default.
+    pub fn to_chrome_args(&self) -> Vec<String> {
+        let scale = self.device_scale_factor();
+        let mut args = vec![
+            format!("--user-agent={}", self.user_agent),
+            format!(
+                "--window-size={},{}",
+                (self.screen_width as f64 / scale).round(),
+                (self.screen_height as f64 / scale).round()
+            ),
+            format!("--force-device-scale-factor={}", scale),
+        ];
+        if let Some(primary) = self.languages.first() {
+            args.push(format!("--lang={}", primary));
+            args.push(format!("--accept-lang={}", self.languages.join(",")));
+        }
+        args
+    }
 }
 
 #[cfg(test)]
@@ -174,4 +432,92 @@ mod tests {
         assert!([4, 8, 10, 12, 16].contains(&fp.hardware_concurrency));
         assert!([8, 16, 32].contains(&fp.device_memory));
     }
+
+    #[test]
+    fn test_fingerprint_user_agent_matches_platform() {
+        for _ in 0..20 {
+            let fp = Fingerprint::random();
+            let token = match fp.platform {
+                Platform::MacOS => "Macintosh",
+                Platform::Windows => "Windows NT",
+            };
+            assert!(fp.user_agent.contains(token), "{:?}", fp);
+        }
+    }
+
+    #[test]
+    fn test_webgl_vendor_matches_renderer() {
+        for _ in 0..20 {
+            let fp = Fingerprint::random();
+            let vendor = angle_gpu_vendor(&fp.webgl_renderer);
+            assert_eq!(fp.webgl_vendor, format!("Google Inc. ({})", vendor));
+            assert_eq!(fp.shader_precisions()[2], [127, 127, 23]);
+        }
+
+        let mut fp = Fingerprint::random();
+        fp.webgl_renderer = "ANGLE (ARM, Mali-G78, OpenGL ES 3.2)".into();
+        assert_eq!(fp.shader_precisions()[0], [15, 15, 10]);
+    }
+
+    #[test]
+    fn test_webgpu_matches_webgl() {
+        for _ in 0..20 {
+            let fp = Fingerprint::random();
+            let (vendor, architecture) = fp.webgpu_adapter_info();
+            assert_eq!(
+                vendor,
+                angle_gpu_vendor(&fp.webgl_renderer).to_lowercase(),
+                "{}",
+                fp.webgl_renderer
+            );
+            assert!(!architecture.is_empty(), "{}", fp.webgl_renderer);
+        }
+    }
+
+    #[test]
+    fn test_user_agent_metadata() {
+        let mac = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 \
+                   (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36";
+        let metadata = user_agent_metadata(mac).unwrap();
+        assert_eq!(metadata.platform, "macOS");
+        assert_eq!(metadata.platform_version, "10.15.7");
+        assert!(metadata
+            .brands
+            .iter()
+            .any(|b| b.brand == "Google Chrome" && b.version == "131"));
+        assert!(metadata
+            .full_version_list
+            .iter()
+            .any(|b| b.brand == "Chromium" && b.version == "131.0.0.0"));
+        assert_eq!(navigator_platform_for(mac), Some("MacIntel"));
+
+        let windows = random_user_agent_for(Platform::Windows);
+        assert_eq!(user_agent_metadata(&windows).unwrap().platform, "Windows");
+        assert_eq!(navigator_platform_for(&windows), Some("Win32"));
+
+        assert!(user_agent_metadata("Mozilla/5.0 (X11; Linux x86_64) Firefox/120.0").is_none());
+    }
+
+    #[test]
+    fn test_to_chrome_args() {
+        let mut fp = Fingerprint::random();
+        fp.platform = Platform::MacOS;
+        fp.screen_width = 3024;
+        fp.screen_height = 1964;
+        fp.languages = vec!["de-DE".into(), "de".into()];
+
+        let args = fp.to_chrome_args();
+        assert!(args.contains(&format!("--user-agent={}", fp.user_agent)));
+        assert!(args.contains(&"--window-size=1512,982".to_string()));
+        assert!(args.contains(&"--force-device-scale-factor=2".to_string()));
+        assert!(args.contains(&"--lang=de-DE".to_string()));
+        assert!(args.contains(&"--accept-lang=de-DE,de".to_string()));
+
+        fp.platform = Platform::Windows;
+        fp.screen_width = 1920;
+        fp.screen_height = 1080;
+        assert!(fp
+            .to_chrome_args()
+            .contains(&"--force-device-scale-factor=1".to_string()));
+    }
 }
@@ -63,6 +63,14 @@ fn random_f64_range(min: f64, max: f64) -> f64 {
     RNG.with(|rng| rng.borrow_mut().random_range(min..max))
 }
 
+/// A random on-screen starting point for moves with no known origin
+pub(crate) fn random_start() -> (f64, f64) {
+    (
+        random_f64_range(100.0, 800.0),
+        random_f64_range(100.0, 600.0),
+    )
+}
+
 fn random_bool(probability: f64) -> bool {
     RNG.with(|rng| rng.borrow_mut().random_bool(probability))
 }
@@ -110,6 +118,8 @@ fn bezier_curve(start: Point, end: Point, num_points: usize) -> PointVec {
 pub struct Human<'a> {
     session: &'a Session,
     speed: HumanSpeed,
+    /// Button held down during moves (drags)
+    button: Option<MouseButton>,
 }
 
 impl<'a> Human<'a> {
@@ -118,6 +128,7 @@ impl<'a> Human<'a> {
         Self {
             session,
             speed: HumanSpeed::Normal,
+            button: None,
         }
     }
 
@@ -127,11 +138,21 @@ impl<'a> Human<'a> {
         self
     }
 
+    /// Report `button` as held on every move, for dragging
+    pub fn with_button(mut self, button: MouseButton) -> Self {
+        self.button = Some(button);
+        self
+    }
+
     /// Move mouse to target position with human-like Bezier curve
     pub async fn move_to(&self, target_x: f64, target_y: f64) -> Result<()> {
         // Start from random position
-        let start_x = random_f64_range(100.0, 800.0);
-        let start_y = random_f64_range(100.0, 600.0);
+        self.move_from(random_start(), target_x, target_y).await
+    }
+
+    /// Move mouse along a Bezier curve from a known position to the target
+    pub async fn move_from(&self, start: (f64, f64), target_x: f64, target_y: f64) -> Result<()> {
+        let (start_x, start_y) = start;
 
         let distance = ((target_x - start_x).powi(2) + (target_y - start_y).powi(2)).sqrt();
         let num_points = self.speed.mouse_points(distance);
@@ -142,7 +163,7 @@ impl<'a> Human<'a> {
         // Move through path
         for (x, y) in path {
             self.session
-                .dispatch_mouse_event(MouseEventType::MouseMoved, x, y, None, None)
+                .dispatch_mouse_event(MouseEventType::MouseMoved, x, y, self.button, None)
                 .await?;
             sleep(Duration::from_millis(random_range(min_delay, max_delay))).await;
         }
@@ -152,7 +173,18 @@ impl<'a> Human<'a> {
 
     /// Move mouse to target and click
     pub async fn move_and_click(&self, target_x: f64, target_y: f64) -> Result<()> {
-        self.move_to(target_x, target_y).await?;
+        self.move_from_and_click(random_start(), target_x, target_y)
+            .await
+    }
+
+    /// Move mouse from a known position to the target and click
+    pub async fn move_from_and_click(
+        &self,
+        start: (f64, f64),
+        target_x: f64,
+        target_y: f64,
+    ) -> Result<()> {
+        self.move_from(start, target_x, target_y).await?;
 
         // Small delay before click
         sleep(Duration::from_millis(random_range(50, 150))).await;
@@ -258,6 +290,12 @@ impl<'a> Human<'a> {
         Ok(())
     }
 
+    /// Pause for a human-like gap between separate key presses
+    pub async fn pause_between_keys(&self) {
+        let (min_delay, max_delay) = self.speed.type_delay_ms();
+        sleep(Duration::from_millis(random_range(min_delay, max_delay))).await;
+    }
+
     /// Press a key
     pub async fn press_key(&self, key: &str) -> Result<()> {
         self.session
@@ -273,6 +311,50 @@ impl<'a> Human<'a> {
         Ok(())
     }
 
+    /// Idle browsing before the first real action: a few wandering mouse
+    /// movements and a small wheel scroll down and back up, all inside
+    /// `viewport`. Starts at `start` (a random point if unknown) and returns
+    /// where the mouse ended up.
+    pub async fn warm_up(
+        &self,
+        start: Option<(f64, f64)>,
+        viewport: (f64, f64),
+    ) -> Result<(f64, f64)> {
+        let (width, height) = viewport;
+        let random_point = || {
+            (
+                random_f64_range(width * 0.1, width * 0.9),
+                random_f64_range(height * 0.1, height * 0.9),
+            )
+        };
+
+        let mut position = start.unwrap_or_else(random_point);
+        for _ in 0..random_range(2, 5) {
+            let target = random_point();
+            self.move_from(position, target.0, target.1).await?;
+            position = target;
+            sleep(Duration::from_millis(random_range(150, 600))).await;
+        }
+
+        // A notch or two down, a look, then back to where the page was
+        let mut scrolled = 0.0;
+        for _ in 0..random_range(1, 3) {
+            let delta = 100.0 + random_f64_range(-20.0, 20.0);
+            self.session
+                .dispatch_mouse_wheel(position.0, position.1, 0.0, delta)
+                .await?;
+            scrolled += delta;
+            sleep(Duration::from_millis(random_range(80, 200))).await;
+        }
+        sleep(Duration::from_millis(random_range(400, 900))).await;
+        self.session
+            .dispatch_mouse_wheel(position.0, position.1, 0.0, -scrolled)
+            .await?;
+        sleep(Duration::from_millis(random_range(100, 300))).await;
+
+        Ok(position)
+    }
+
     /// Scroll the page by delta_y pixels (positive = down, negative = up)
     pub async fn scroll(&self, delta_y: f64) -> Result<()> {
         let num_scrolls = random_range(3, 8);
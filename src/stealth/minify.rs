@@ -0,0 +1,189 @@
+//! JavaScript Minifier
+//!
+//! A small, conservative minifier for the evasion scripts: strips comments
+//! and collapses whitespace. Strings, template literals and regex literals
+//! are copied verbatim, and line breaks are kept wherever automatic
+//! semicolon insertion could depend on them.
+
+/// Characters after which a line break never ends a statement
+const JOIN_AFTER: &[char] = &[
+    '{', '(', '[', ',', ';', ':', '=', '!', '?', '&', '|', '<', '>', '*', '%',
+];
+
+/// Characters before which a line break never ends a statement
+const JOIN_BEFORE: &[char] = &[
+    ')', ']', '}', ',', ';', ':', '=', '?', '&', '|', '<', '>', '*', '%', '.',
+];
+
+/// Characters after which a `/` starts a regex literal rather than a division
+const REGEX_AFTER: &[char] = &[
+    '(', ',', '=', ':', '[', '!', '&', '|', '?', '{', '}', ';', '+', '-', '*', '%', '<', '>', '~',
+    '^',
+];
+
+/// Keywords after which a `/` starts a regex literal
+const REGEX_KEYWORDS: &[&str] = &[
+    "return", "typeof", "case", "in", "of", "delete", "void", "throw",
+];
+
+/// Strip comments and collapse whitespace in `source`
+pub(crate) fn minify_js(source: &str) -> String {
+    let chars: Vec<char> = source.chars().collect();
+    let mut out = String::with_capacity(source.len());
+    let mut i = 0;
+    // Pending whitespace: None, or Some(contains_newline)
+    let mut gap: Option<bool> = None;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if c.is_whitespace() {
+            gap = Some(gap.unwrap_or(false) || c == '\n');
+            i += 1;
+            continue;
+        }
+        if c == '/' && next == Some('/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+        if c == '/' && next == Some('*') {
+            let mut newline = false;
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                newline |= chars[i] == '\n';
+                i += 1;
+            }
+            i += 2;
+            gap = Some(gap.unwrap_or(false) || newline);
+            continue;
+        }
+
+        if let Some(newline) = gap.take() {
+            push_gap(&mut out, newline, c);
+        }
+
+        let end = match c {
+            '\'' | '"' | '`' => quoted_end(&chars, i),
+            '/' if starts_regex(&out) => regex_end(&chars, i),
+            _ => i + 1,
+        };
+        out.extend(&chars[i..end.min(chars.len())]);
+        i = end;
+    }
+
+    out
+}
+
+/// Emit the whitespace between the last output char and `next`, if any is needed
+fn push_gap(out: &mut String, newline: bool, next: char) {
+    let Some(prev) = out.chars().last() else {
+        return;
+    };
+    // `a + +b`, `a - -b` and `a / /re/` change meaning when joined
+    let merges = (prev == next && matches!(prev, '+' | '-')) || (prev == '/' && next == '/');
+    let word = |ch: char| ch.is_alphanumeric() || ch == '_' || ch == '$';
+
+    if newline {
+        let safe = (JOIN_AFTER.contains(&prev) || JOIN_BEFORE.contains(&next)) && !merges;
+        if !safe {
+            out.push('\n');
+        }
+    } else if merges || (word(prev) && word(next)) {
+        out.push(' ');
+    }
+}
+
+/// Whether a `/` following `out` begins a regex literal
+fn starts_regex(out: &str) -> bool {
+    let trimmed = out.trim_end();
+    let Some(prev) = trimmed.chars().last() else {
+        return true;
+    };
+    if REGEX_AFTER.contains(&prev) {
+        return true;
+    }
+    let word_start = trimmed
+        .rfind(|ch: char| !(ch.is_alphanumeric() || ch == '_' || ch == '$'))
+        .map_or(0, |pos| pos + 1);
+    REGEX_KEYWORDS.contains(&&trimmed[word_start..])
+}
+
+/// End (exclusive) of the string or template literal starting at `start`
+fn quoted_end(chars: &[char], start: usize) -> usize {
+    let quote = chars[start];
+    let mut i = start + 1;
+    let mut depth = 0usize;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            // `${ ... }` inside a template literal is code; skip nested quotes there
+            '$' if quote == '`' && chars.get(i + 1) == Some(&'{') => {
+                depth += 1;
+                i += 1;
+            }
+            '{' if depth > 0 => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            '\'' | '"' | '`' if depth > 0 => i = quoted_end(chars, i) - 1,
+            ch if ch == quote && depth == 0 => return i + 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    chars.len()
+}
+
+/// End (exclusive) of the regex literal starting at `start`, flags included
+fn regex_end(chars: &[char], start: usize) -> usize {
+    let mut i = start + 1;
+    let mut in_class = false;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '/' if !in_class => break,
+            '\n' => return i,
+            _ => {}
+        }
+        i += 1;
+    }
+    i += 1;
+    while i < chars.len() && chars[i].is_alphabetic() {
+        i += 1;
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strips_comments_and_whitespace() {
+        let src = "// note\nconst a = 1; /* block */\nif (a) {\n    b();\n}\n";
+        assert_eq!(minify_js(src), "const a=1;if(a){b();}");
+    }
+
+    #[test]
+    fn test_keeps_line_breaks_asi_needs() {
+        assert_eq!(minify_js("let a = b\nfoo()"), "let a=b\nfoo()");
+        assert_eq!(minify_js("return\nx"), "return\nx");
+        assert_eq!(minify_js("a++\nb"), "a++\nb");
+        assert_eq!(minify_js("a + +b"), "a+ +b");
+    }
+
+    #[test]
+    fn test_literals_untouched() {
+        assert_eq!(minify_js("x = 'a  // b';"), "x='a  // b';");
+        assert_eq!(minify_js("x = `a  ${ y }  b`;"), "x=`a  ${ y }  b`;");
+        assert_eq!(
+            minify_js("s.replace(/^['\"]|[ /]$/g, '')"),
+            "s.replace(/^['\"]|[ /]$/g,'')"
+        );
+        assert_eq!(minify_js("p = /^\\/\\/ x/;"), "p=/^\\/\\/ x/;");
+        assert_eq!(minify_js("a = b / c / d;"), "a=b/c/d;");
+    }
+}
@@ -3,15 +3,19 @@
 //! All the anti-detection components:
 //! - Binary patcher (Aho-Corasick based)
 //! - JavaScript evasion scripts
+//! - Evasion script minification
 //! - Human-like interaction simulation
 //! - Fingerprint generation
 
 pub mod evasions;
 pub mod fingerprint;
 pub mod human;
+mod minify;
 pub mod patcher;
 
-pub use evasions::{build_evasion_script, full_evasion_script};
+pub use evasions::{
+    build_evasion_script, build_evasion_script_for, full_evasion_script, WebRtcPolicy,
+};
 pub use fingerprint::{random_user_agent, Fingerprint, Platform};
 pub use human::{Human, HumanSpeed};
-pub use patcher::{find_chrome, ChromePatcher};
+pub use patcher::{find_chrome, ChromePatcher, PatchStrategy};
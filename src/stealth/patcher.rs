@@ -4,16 +4,21 @@
 //! Uses Aho-Corasick for O(n) multi-pattern matching.
 
 use aho_corasick::{AhoCorasick, Match};
-use memmap2::MmapMut;
+use memmap2::{Mmap, MmapMut};
 use rand::Rng;
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::fs::{self, File, OpenOptions};
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::OnceLock;
 
 use crate::error::{Error, Result};
 
+/// Counter keeping exec probe names unique across concurrent checks
+static PROBE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
 // Thread-local RNG
 thread_local! {
     static RNG: RefCell<rand::rngs::ThreadRng> = RefCell::new(rand::rng());
@@ -22,74 +27,92 @@ thread_local! {
 /// Patch pattern with replacement strategy
 #[derive(Clone)]
 struct PatchPattern {
-    pattern: &'static [u8],
+    pattern: Cow<'static, [u8]>,
     strategy: PatchStrategy,
 }
 
-#[derive(Clone, Copy, PartialEq)]
-enum PatchStrategy {
+/// How a matched byte pattern is rewritten
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchStrategy {
+    /// Replace every byte with a random `[a-z0-9]` character
     RandomizePrefix,
+    /// Swap adjacent byte pairs (keeps the length and character set)
     Scramble,
+    /// Overwrite with spaces
     Nullify,
+    /// Detect only, don't modify
     Skip,
 }
 
 /// All patterns to find and patch
 static PATCH_PATTERNS: &[PatchPattern] = &[
     PatchPattern {
-        pattern: b"$cdc_",
+        pattern: Cow::Borrowed(b"$cdc_"),
         strategy: PatchStrategy::RandomizePrefix,
     },
     PatchPattern {
-        pattern: b"cdc_",
+        pattern: Cow::Borrowed(b"cdc_"),
         strategy: PatchStrategy::RandomizePrefix,
     },
     PatchPattern {
-        pattern: b"webdriver",
+        pattern: Cow::Borrowed(b"webdriver"),
         strategy: PatchStrategy::Scramble,
     },
     PatchPattern {
-        pattern: b"--enable-automation",
+        pattern: Cow::Borrowed(b"--enable-automation"),
         strategy: PatchStrategy::Nullify,
     },
     PatchPattern {
-        pattern: b"devtoolsw",
+        pattern: Cow::Borrowed(b"devtoolsw"),
         strategy: PatchStrategy::Scramble,
     },
     PatchPattern {
-        pattern: b"debuggerPrivate",
+        pattern: Cow::Borrowed(b"debuggerPrivate"),
         strategy: PatchStrategy::Scramble,
     },
     PatchPattern {
-        pattern: b"HeadlessChrome",
+        pattern: Cow::Borrowed(b"HeadlessChrome"),
         strategy: PatchStrategy::Scramble,
     },
     PatchPattern {
-        pattern: b"$wdc_",
+        pattern: Cow::Borrowed(b"$wdc_"),
         strategy: PatchStrategy::RandomizePrefix,
     },
     PatchPattern {
-        pattern: b"$chromeDriver",
+        pattern: Cow::Borrowed(b"$chromeDriver"),
         strategy: PatchStrategy::RandomizePrefix,
     },
     // Detection only
     PatchPattern {
-        pattern: b"Runtime.enable",
+        pattern: Cow::Borrowed(b"Runtime.enable"),
         strategy: PatchStrategy::Skip,
     },
     PatchPattern {
-        pattern: b"Page.addScriptToEvaluateOnNewDocument",
+        pattern: Cow::Borrowed(b"Page.addScriptToEvaluateOnNewDocument"),
         strategy: PatchStrategy::Skip,
     },
 ];
 
-/// Compiled pattern matcher (lazy initialized)
-static PATTERN_MATCHER: OnceLock<AhoCorasick> = OnceLock::new();
+/// Patterns plus their compiled Aho-Corasick automaton
+struct PatternSet {
+    patterns: Vec<PatchPattern>,
+    matcher: AhoCorasick,
+}
+
+impl PatternSet {
+    fn new(patterns: Vec<PatchPattern>) -> Result<Self> {
+        let matcher = AhoCorasick::new(patterns.iter().map(|p| &*p.pattern))
+            .map_err(|e| Error::patching("patterns", e.to_string()))?;
+        Ok(Self { patterns, matcher })
+    }
+}
+
+/// Compiled built-in patterns (lazy initialized)
+static DEFAULT_PATTERNS: OnceLock<PatternSet> = OnceLock::new();
 
-fn get_pattern_matcher() -> &'static AhoCorasick {
-    PATTERN_MATCHER.get_or_init(|| {
-        let patterns: Vec<&[u8]> = PATCH_PATTERNS.iter().map(|p| p.pattern).collect();
-        AhoCorasick::new(&patterns).expect("Failed to build Aho-Corasick automaton")
+fn default_pattern_set() -> &'static PatternSet {
+    DEFAULT_PATTERNS.get_or_init(|| {
+        PatternSet::new(PATCH_PATTERNS.to_vec()).expect("Failed to build Aho-Corasick automaton")
     })
 }
 
@@ -128,16 +151,44 @@ pub fn find_chrome() -> Result<PathBuf> {
     Err(Error::ChromeNotFound)
 }
 
+/// Default location for patched binaries
+fn default_output_dir() -> PathBuf {
+    std::env::temp_dir().join("eoka-chrome")
+}
+
+/// Move `path` from under `from` to the same relative location under `to`
+fn rebase_path(path: &Path, from: &Path, to: &Path) -> PathBuf {
+    match path.strip_prefix(from) {
+        Ok(rel) => to.join(rel),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
 /// Chrome binary patcher
 pub struct ChromePatcher {
     original_path: PathBuf,
+    /// Directory the patched copy is written to
+    output_dir: PathBuf,
     patched_path: PathBuf,
+    /// Built-in plus user patterns (None = built-in only)
+    patterns: Option<PatternSet>,
     #[cfg(target_os = "macos")]
     original_bundle: Option<PathBuf>,
     #[cfg(target_os = "macos")]
     patched_bundle: Option<PathBuf>,
 }
 
+/// `residual`'s first few offsets, e.g. `[1, 2, 3, 4, 5] and 12 more`: a
+/// bad patch can leave thousands, and the first few locate the problem
+pub(crate) fn describe_offsets(residual: &[usize]) -> String {
+    const SHOWN: usize = 5;
+    let shown = &residual[..residual.len().min(SHOWN)];
+    match residual.len() - shown.len() {
+        0 => format!("{:?}", shown),
+        n => format!("{:?} and {} more", shown, n),
+    }
+}
+
 impl ChromePatcher {
     /// Create a new patcher for the given Chrome binary
     pub fn new(chrome_path: &Path) -> Result<Self> {
@@ -166,7 +217,8 @@ impl ChromePatcher {
                     .file_name()
                     .ok_or_else(|| Error::patching("new", "Invalid bundle path"))?;
 
-                let patched_bundle = std::env::temp_dir().join("eoka-chrome").join(bundle_name);
+                let output_dir = default_output_dir();
+                let patched_bundle = output_dir.join(bundle_name);
 
                 let relative_path = chrome_path
                     .strip_prefix(&bundle)
@@ -175,7 +227,9 @@ impl ChromePatcher {
 
                 return Ok(Self {
                     original_path: chrome_path.to_path_buf(),
+                    output_dir,
                     patched_path,
+                    patterns: None,
                     original_bundle: Some(bundle),
                     patched_bundle: Some(patched_bundle),
                 });
@@ -187,11 +241,14 @@ impl ChromePatcher {
             .file_name()
             .ok_or_else(|| Error::patching("new", "Invalid path"))?;
 
-        let patched_path = std::env::temp_dir().join("eoka-chrome").join(filename);
+        let output_dir = default_output_dir();
+        let patched_path = output_dir.join(filename);
 
         Ok(Self {
             original_path: chrome_path.to_path_buf(),
+            output_dir,
             patched_path,
+            patterns: None,
             #[cfg(target_os = "macos")]
             original_bundle: None,
             #[cfg(target_os = "macos")]
@@ -199,25 +256,137 @@ impl ChromePatcher {
         })
     }
 
+    /// Write the patched copy to `dir` instead of `<temp>/eoka-chrome`
+    ///
+    /// Useful when the temp directory is read-only or mounted `noexec`.
+    pub fn with_output_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        self.patched_path = rebase_path(&self.patched_path, &self.output_dir, &dir);
+        #[cfg(target_os = "macos")]
+        {
+            self.patched_bundle = self
+                .patched_bundle
+                .as_deref()
+                .map(|p| rebase_path(p, &self.output_dir, &dir));
+        }
+        self.output_dir = dir;
+        self
+    }
+
+    /// Add patterns on top of the built-in list
+    ///
+    /// Lets new Chrome markers be patched without waiting for a crate release.
+    pub fn with_extra_patterns(mut self, extra: &[(Vec<u8>, PatchStrategy)]) -> Result<Self> {
+        let mut patterns = self.pattern_set().patterns.clone();
+        patterns.extend(extra.iter().map(|(pattern, strategy)| PatchPattern {
+            pattern: Cow::Owned(pattern.clone()),
+            strategy: *strategy,
+        }));
+        self.patterns = Some(PatternSet::new(patterns)?);
+        Ok(self)
+    }
+
+    fn pattern_set(&self) -> &PatternSet {
+        match &self.patterns {
+            Some(set) => set,
+            None => default_pattern_set(),
+        }
+    }
+
+    /// Make sure the output directory exists and can hold a runnable binary
+    fn check_output_dir(&self) -> Result<()> {
+        fs::create_dir_all(&self.output_dir).map_err(|e| {
+            Error::patching(
+                "output_dir",
+                format!("Cannot create {:?}: {}", self.output_dir, e),
+            )
+        })?;
+
+        // Unique per process and call, so parallel launches sharing the
+        // directory never run or delete each other's probe
+        let probe = self.output_dir.join(format!(
+            ".eoka-exec-probe-{}-{}",
+            std::process::id(),
+            PROBE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&probe, b"#!/bin/sh\nexit 0\n").map_err(|e| {
+            Error::patching(
+                "output_dir",
+                format!("{:?} is not writable: {}", self.output_dir, e),
+            )
+        })?;
+
+        #[cfg(unix)]
+        let result = {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&probe, fs::Permissions::from_mode(0o755))
+                .and_then(|_| std::process::Command::new(&probe).status())
+        };
+        #[cfg(not(unix))]
+        let result: std::io::Result<()> = Ok(());
+
+        let _ = fs::remove_file(&probe);
+        result.map(|_| ()).map_err(|e| {
+            Error::patching(
+                "output_dir",
+                format!(
+                    "{:?} cannot hold an executable (noexec mount?): {}",
+                    self.output_dir, e
+                ),
+            )
+        })
+    }
+
+    /// Get path the patched binary is (or will be) written to
+    pub fn patched_path(&self) -> &Path {
+        &self.patched_path
+    }
+
     /// Check if a patched binary already exists and is valid
+    ///
+    /// The cache is only trusted if the sidecar fingerprint recorded at patch
+    /// time still matches the current original binary.
     pub fn is_patched(&self) -> bool {
         if !self.patched_path.exists() {
             return false;
         }
 
-        let orig_modified = fs::metadata(&self.original_path)
-            .and_then(|m| m.modified())
-            .ok();
-        let patched_modified = fs::metadata(&self.patched_path)
-            .and_then(|m| m.modified())
-            .ok();
-
-        match (orig_modified, patched_modified) {
-            (Some(orig), Some(patched)) if patched > orig => self.verify_patched_sample(),
+        let recorded = match fs::read_to_string(self.fingerprint_path()) {
+            Ok(s) => s,
+            Err(_) => return false,
+        };
+        match self.cache_key() {
+            Ok(current) if recorded.trim() == current => self.verify_patched_sample(),
             _ => false,
         }
     }
 
+    /// Original binary fingerprint plus a hash of the pattern set, so adding
+    /// patterns also invalidates a cached copy
+    fn cache_key(&self) -> Result<String> {
+        let mut pattern_bytes = Vec::new();
+        for p in &self.pattern_set().patterns {
+            pattern_bytes.extend_from_slice(&p.pattern);
+            pattern_bytes.push(p.strategy as u8);
+        }
+        Ok(format!(
+            "{}:{:016x}",
+            binary_fingerprint(&self.original_path)?,
+            fnv1a(&pattern_bytes)
+        ))
+    }
+
+    /// Sidecar file recording the fingerprint of the original binary
+    fn fingerprint_path(&self) -> PathBuf {
+        let mut name = self
+            .patched_path
+            .file_name()
+            .map(|n| n.to_os_string())
+            .unwrap_or_default();
+        name.push(".eoka-hash");
+        self.patched_path.with_file_name(name)
+    }
+
     /// Quick verification by checking first 64KB for unpatched patterns
     fn verify_patched_sample(&self) -> bool {
         const SAMPLE_SIZE: usize = 64 * 1024;
@@ -234,7 +403,44 @@ impl ChromePatcher {
         };
         buffer.truncate(bytes_read);
 
-        !get_pattern_matcher().is_match(&buffer)
+        !self.pattern_set().matcher.is_match(&buffer)
+    }
+
+    /// Full verification: scan the entire patched binary for leftover markers
+    ///
+    /// Unlike the 64KB sample check used for cache validation, this catches
+    /// markers anywhere in the file. Detection-only (`Skip`) patterns are ignored.
+    pub fn verify_full(&self) -> Result<bool> {
+        let residual = self.residual_offsets()?;
+        if !residual.is_empty() {
+            tracing::warn!(
+                "Patched binary still contains {} markers at offsets {}",
+                residual.len(),
+                describe_offsets(&residual)
+            );
+        }
+        Ok(residual.is_empty())
+    }
+
+    /// Offsets of markers that should have been patched but are still present
+    pub fn residual_offsets(&self) -> Result<Vec<usize>> {
+        let file = File::open(&self.patched_path)?;
+        if file.metadata()?.len() == 0 {
+            return Ok(Vec::new());
+        }
+        // SAFETY: read-only mapping of a file we own; nothing else writes to
+        // the patched copy while we scan it.
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(self.find_residual(&mmap))
+    }
+
+    fn find_residual(&self, data: &[u8]) -> Vec<usize> {
+        let set = self.pattern_set();
+        set.matcher
+            .find_iter(data)
+            .filter(|m| set.patterns[m.pattern().as_usize()].strategy != PatchStrategy::Skip)
+            .map(|m| m.start())
+            .collect()
     }
 
     /// Copy the app bundle (macOS) using symlinks for speed
@@ -310,6 +516,8 @@ impl ChromePatcher {
     pub fn patch(&self) -> Result<()> {
         tracing::info!("Patching Chrome binary: {:?}", self.original_path);
 
+        self.check_output_dir()?;
+
         #[cfg(target_os = "macos")]
         {
             self.copy_bundle()?;
@@ -357,6 +565,9 @@ impl ChromePatcher {
             self.codesign()?;
         }
 
+        // Record which original this copy was made from
+        fs::write(self.fingerprint_path(), self.cache_key()?)?;
+
         tracing::info!(
             "Patched {} occurrences, saved to {:?}",
             patch_count,
@@ -421,13 +632,14 @@ impl ChromePatcher {
     }
 
     fn apply_patches(&self, data: &mut [u8]) -> usize {
-        let matches: Vec<Match> = get_pattern_matcher().find_iter(&*data).collect();
+        let set = self.pattern_set();
+        let matches: Vec<Match> = set.matcher.find_iter(&*data).collect();
         let mut patch_count = 0;
         let mut patched_ranges: Vec<(usize, usize)> = Vec::new();
 
         for m in matches {
             let pattern_idx = m.pattern().as_usize();
-            let pattern = &PATCH_PATTERNS[pattern_idx];
+            let pattern = &set.patterns[pattern_idx];
             let start = m.start();
             let end = m.end();
 
@@ -462,7 +674,7 @@ impl ChromePatcher {
                 PatchStrategy::Skip => {
                     tracing::trace!(
                         "Found (not patching): {:?} at offset {}",
-                        String::from_utf8_lossy(pattern.pattern),
+                        String::from_utf8_lossy(&pattern.pattern),
                         start
                     );
                 }
@@ -507,6 +719,35 @@ impl ChromePatcher {
     }
 }
 
+/// Fast fingerprint of a binary: file size plus an FNV-1a hash of the first
+/// and last MB. Cheap enough to run on every launch, and sensitive to the
+/// version bumps that change a Chrome binary.
+fn binary_fingerprint(path: &Path) -> Result<String> {
+    use std::io::{Seek, SeekFrom};
+
+    const CHUNK: u64 = 1024 * 1024;
+
+    let mut file = File::open(path)?;
+    let size = file.metadata()?.len();
+
+    let mut buf = Vec::with_capacity(2 * CHUNK as usize);
+    (&mut file).take(CHUNK).read_to_end(&mut buf)?;
+    if size > CHUNK {
+        file.seek(SeekFrom::Start(size.saturating_sub(CHUNK).max(CHUNK)))?;
+        file.take(CHUNK).read_to_end(&mut buf)?;
+    }
+
+    Ok(format!("{}:{:016x}", size, fnv1a(&buf)))
+}
+
+/// 64-bit FNV-1a hash (stable across Rust versions, unlike `DefaultHasher`)
+fn fnv1a(data: &[u8]) -> u64 {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+    data.iter()
+        .fold(FNV_OFFSET, |h, &b| (h ^ b as u64).wrapping_mul(FNV_PRIME))
+}
+
 fn random_string(len: usize) -> String {
     RNG.with(|rng| {
         let mut rng = rng.borrow_mut();
@@ -542,10 +783,104 @@ mod tests {
         assert!(s.chars().all(|c| c.is_alphanumeric()));
     }
 
+    #[test]
+    fn test_describe_offsets_caps_list() {
+        assert_eq!(describe_offsets(&[1, 2]), "[1, 2]");
+        let many: Vec<usize> = (0..12).collect();
+        assert_eq!(describe_offsets(&many), "[0, 1, 2, 3, 4] and 7 more");
+    }
+
+    #[test]
+    fn test_original_change_invalidates_cache() {
+        let dir = std::env::temp_dir().join(format!("eoka-patcher-cache-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let original = dir.join("fake-chrome-cache");
+        fs::write(&original, b"header $cdc_ marker webdriver footer").unwrap();
+
+        let patcher = ChromePatcher::new(&original).unwrap();
+        assert!(!patcher.is_patched());
+        patcher.patch().unwrap();
+        assert!(patcher.is_patched());
+
+        // Simulate a Chrome update: same path, different contents
+        fs::write(&original, b"header $cdc_ marker webdriver footer v2").unwrap();
+        assert!(!patcher.is_patched());
+
+        let _ = fs::remove_file(&patcher.patched_path);
+        let _ = fs::remove_file(patcher.fingerprint_path());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_custom_output_dir() {
+        let dir = std::env::temp_dir().join(format!("eoka-patcher-outdir-{}", std::process::id()));
+        let out = dir.join("patched");
+        fs::create_dir_all(&dir).unwrap();
+        let original = dir.join("fake-chrome-outdir");
+        fs::write(&original, b"header $cdc_ marker footer").unwrap();
+
+        let patcher = ChromePatcher::new(&original).unwrap().with_output_dir(&out);
+        assert_eq!(patcher.patched_path(), out.join("fake-chrome-outdir"));
+
+        patcher.patch().unwrap();
+        let patched = fs::read(out.join("fake-chrome-outdir")).unwrap();
+        assert!(!default_pattern_set().matcher.is_match(&patched));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_aho_corasick_patterns() {
         let test_data = b"$cdc_test webdriver HeadlessChrome";
-        let matches: Vec<_> = get_pattern_matcher().find_iter(test_data).collect();
+        let matches: Vec<_> = default_pattern_set().matcher.find_iter(test_data).collect();
         assert!(matches.len() >= 3);
     }
+
+    #[test]
+    fn test_extra_patterns() {
+        let dir = std::env::temp_dir().join(format!("eoka-patcher-extra-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let original = dir.join("fake-chrome-extra");
+        fs::write(&original, b"x").unwrap();
+
+        let patcher = ChromePatcher::new(&original)
+            .unwrap()
+            .with_extra_patterns(&[(b"newMarker".to_vec(), PatchStrategy::Nullify)])
+            .unwrap();
+
+        let mut data = b"abc newMarker def $cdc_ ghi".to_vec();
+        let count = patcher.apply_patches(&mut data);
+        assert_eq!(count, 2);
+        assert_eq!(&data[4..13], b"         ");
+        assert!(!data.windows(5).any(|w| w == b"$cdc_"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_verify_full_detects_residual_markers() {
+        let dir = std::env::temp_dir().join(format!("eoka-patcher-verify-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let original = dir.join("fake-chrome-verify");
+        fs::write(&original, b"x").unwrap();
+        let patcher = ChromePatcher::new(&original)
+            .unwrap()
+            .with_output_dir(dir.join("out"));
+
+        // Marker past the 64KB sample window, plus a detection-only pattern
+        let mut data = vec![b'.'; 128 * 1024];
+        data[100_000..100_005].copy_from_slice(b"$cdc_");
+        data[10..24].copy_from_slice(b"Runtime.enable");
+        assert_eq!(patcher.find_residual(&data), vec![100_000]);
+
+        fs::create_dir_all(dir.join("out")).unwrap();
+        fs::write(patcher.patched_path(), &data).unwrap();
+        assert!(!patcher.verify_full().unwrap());
+
+        patcher.apply_patches(&mut data);
+        fs::write(patcher.patched_path(), &data).unwrap();
+        assert!(patcher.verify_full().unwrap());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
@@ -3,7 +3,7 @@
 //! These tests require Chrome to be installed and available.
 //! Run with: cargo test --test integration -- --ignored
 
-use eoka::{Browser, StealthConfig};
+use eoka::{Browser, InterceptAction, StealthConfig};
 
 /// Check if Chrome is available
 fn chrome_available() -> bool {
@@ -63,6 +63,142 @@ async fn test_page_navigation() {
     browser.close().await.expect("Failed to close browser");
 }
 
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_goto_static_page_is_fast() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("about:blank")
+        .await
+        .expect("Failed to create page");
+
+    let start = std::time::Instant::now();
+    page.goto("data:text/html,<h1>Fast</h1>")
+        .await
+        .expect("Failed to navigate");
+    let elapsed = start.elapsed();
+
+    assert!(
+        elapsed < std::time::Duration::from_millis(100),
+        "goto took {:?}",
+        elapsed
+    );
+    let content = page.content().await.expect("Failed to get content");
+    assert!(content.contains("Fast"));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_goto_waits_for_the_new_document() {
+    use std::io::{Read, Write};
+
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    // Headers at once, body later: the old, complete document stays in
+    // place meanwhile and must not count as ready
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("Failed to bind");
+    let port = listener.local_addr().expect("No local addr").port();
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut buf = [0u8; 4096];
+            let _ = stream.read(&mut buf);
+            let body = "<h1>Slow</h1>";
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            std::thread::sleep(std::time::Duration::from_millis(300));
+            let _ = stream.write_all(body.as_bytes());
+        }
+    });
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<h1>Old</h1>")
+        .await
+        .expect("Failed to create page");
+
+    page.goto(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .expect("Failed to navigate");
+    let content = page.content().await.expect("Failed to get content");
+    assert!(
+        content.contains("Slow"),
+        "still on the old document: {}",
+        content
+    );
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_find_after_navigation() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<p id='first'>One</p>")
+        .await
+        .expect("Failed to create page");
+
+    page.find("#first").await.expect("Failed to find #first");
+
+    page.goto("data:text/html,<p id='second'>Two</p>")
+        .await
+        .expect("Failed to navigate");
+    let el = page.find("#second").await.expect("Failed to find #second");
+    assert_eq!(el.text().await.expect("Failed to get text"), "Two");
+    assert!(page.find("#first").await.is_err());
+
+    // Document replaced by the page itself (only seen via events)
+    page.execute("document.open(); document.write(\"<p id='third'>Three</p>\"); document.close()")
+        .await
+        .expect("Failed to rewrite document");
+    page.wait_for("#third", 5000)
+        .await
+        .expect("Failed to find #third after document rewrite");
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_count_matches_list_items() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<ul><li>a</li><li>b</li><li>c</li></ul><ol><li>d</li></ol>")
+        .await
+        .expect("Failed to create page");
+
+    let count = page.count("li").await.expect("Failed to count");
+    let found = page.find_all("li").await.expect("Failed to find all");
+    assert_eq!(count, 4);
+    assert_eq!(count, found.len());
+    assert_eq!(page.count(".missing").await.expect("Failed to count"), 0);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
 #[tokio::test]
 #[ignore = "requires Chrome"]
 async fn test_page_title() {
@@ -749,3 +885,3506 @@ async fn test_multi_tab() {
 
     browser.close().await.expect("Failed to close browser");
 }
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_persistent_user_data_dir() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let dir = std::env::temp_dir().join("eoka-test-persistent-profile");
+    let _ = std::fs::remove_dir_all(&dir);
+    let config = StealthConfig {
+        user_data_dir: Some(dir.clone()),
+        ..Default::default()
+    };
+
+    // Run 1: set a persistent (non-session) cookie
+    let browser = Browser::launch_with_config(config.clone())
+        .await
+        .expect("Failed to launch browser");
+    let page = browser
+        .new_blank_page()
+        .await
+        .expect("Failed to create page");
+    let expires = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs_f64()
+        + 3600.0;
+    page.session()
+        .send::<_, serde_json::Value>(
+            "Network.setCookie",
+            &eoka::cdp::NetworkSetCookie {
+                name: "persist".into(),
+                value: "yes".into(),
+                url: Some("https://example.com".into()),
+                expires: Some(expires),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("Failed to set cookie");
+
+    // A second browser on the same profile must be refused while the first runs
+    assert!(Browser::launch_with_config(config.clone()).await.is_err());
+
+    browser.close().await.expect("Failed to close browser");
+    assert!(dir.exists(), "persistent profile should survive close");
+
+    // Run 2: reuse the profile and read the cookie back
+    let browser = Browser::launch_with_config(config)
+        .await
+        .expect("Failed to relaunch browser");
+    let page = browser
+        .new_blank_page()
+        .await
+        .expect("Failed to create page");
+    let cookies = page
+        .session()
+        .get_cookies(Some(vec!["https://example.com".into()]))
+        .await
+        .expect("Failed to get cookies");
+    assert!(cookies
+        .iter()
+        .any(|c| c.name == "persist" && c.value == "yes"));
+
+    browser.close().await.expect("Failed to close browser");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_wait_for_new_page_from_blank_link() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<a id='open' target='_blank' href='about:blank#popup'>open</a>")
+        .await
+        .expect("Failed to create page");
+
+    page.click("#open").await.expect("Failed to click link");
+    let popup = browser
+        .wait_for_new_page(5000)
+        .await
+        .expect("Failed to get new page");
+    assert_ne!(popup.target_id(), page.target_id());
+
+    popup
+        .goto("data:text/html,<h1 id='title'>Popup</h1>")
+        .await
+        .expect("Failed to navigate popup");
+    let text = popup.text().await.expect("Failed to get text");
+    assert!(text.contains("Popup"));
+
+    let tabs = browser.tabs().await.expect("Failed to list tabs");
+    let tab = tabs
+        .iter()
+        .find(|t| t.id == popup.target_id())
+        .expect("Popup missing from tabs");
+    assert_eq!(tab.opener_id.as_deref(), Some(page.target_id()));
+
+    // Nothing else was opened
+    assert!(browser.wait_for_new_page(200).await.is_err());
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_frame_navigated_event_subscription() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_blank_page()
+        .await
+        .expect("Failed to create page");
+
+    let mut navigated = page.session().on("Page.frameNavigated");
+    page.goto("data:text/html,<h1>Events</h1>")
+        .await
+        .expect("Failed to navigate");
+
+    let params = tokio::time::timeout(std::time::Duration::from_secs(5), navigated.recv())
+        .await
+        .expect("Timed out waiting for Page.frameNavigated")
+        .expect("Event channel closed");
+    assert!(params["frame"]["url"]
+        .as_str()
+        .is_some_and(|u| u.starts_with("data:text/html")));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_back_and_forward_settle() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_blank_page()
+        .await
+        .expect("Failed to create page");
+
+    let url_a = "data:text/html,<p id='a'>A</p>";
+    let url_b = "data:text/html,<p id='b'>B</p>";
+    page.goto(url_a).await.expect("Failed to navigate to A");
+    page.goto(url_b).await.expect("Failed to navigate to B");
+
+    assert!(page.back().await.expect("Failed to go back"));
+    assert_eq!(page.url().await.expect("Failed to get URL"), url_a);
+    page.find("#a").await.expect("Failed to find #a after back");
+
+    assert!(page.forward().await.expect("Failed to go forward"));
+    assert_eq!(page.url().await.expect("Failed to get URL"), url_b);
+    assert!(!page.forward().await.expect("Failed to go forward"));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_set_viewport() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<p>Viewport</p>")
+        .await
+        .expect("Failed to create page");
+
+    page.set_viewport(375, 812, 1.0)
+        .await
+        .expect("Failed to set viewport");
+    let width: u32 = page
+        .evaluate("window.innerWidth")
+        .await
+        .expect("Failed to read innerWidth");
+    assert_eq!(width, 375);
+
+    // Evasion relations must survive the resize
+    let consistent: bool = page
+        .evaluate(
+            "window.outerWidth >= window.innerWidth && window.outerHeight > window.innerHeight && screen.width >= window.outerWidth && screen.availHeight >= window.outerHeight",
+        )
+        .await
+        .expect("Failed to check dimensions");
+    assert!(consistent);
+
+    page.clear_viewport_override()
+        .await
+        .expect("Failed to clear viewport override");
+    let width: u32 = page
+        .evaluate("window.innerWidth")
+        .await
+        .expect("Failed to read innerWidth");
+    assert_ne!(width, 375);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_wait_for_stable_after_animation() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<style>@keyframes slide{from{transform:translateX(-400px)}to{transform:none}}             #modal{animation:slide 600ms ease-out;width:100px}</style>             <button id='modal' onclick=\"this.textContent='clicked'\">open</button>",
+        )
+        .await
+        .expect("Failed to create page");
+
+    let start = std::time::Instant::now();
+    let element = page
+        .wait_for_stable("#modal", 5000)
+        .await
+        .expect("Element never settled");
+    assert!(start.elapsed() >= std::time::Duration::from_millis(200));
+
+    element.click().await.expect("Failed to click");
+    let text = element.text().await.expect("Failed to get text");
+    assert_eq!(text, "clicked");
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_wait_for_enabled_after_fill() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<input id='email' oninput=\"submit.disabled = !this.value\">             <button id='submit' disabled onclick=\"this.textContent='sent'\">Send</button>",
+        )
+        .await
+        .expect("Failed to create page");
+
+    let button = page.find("#submit").await.expect("Failed to find button");
+    let err = button.wait_for_enabled(300).await.unwrap_err();
+    assert!(err.to_string().contains("button#submit"));
+
+    page.fill("#email", "a@b.c").await.expect("Failed to fill");
+    button
+        .wait_for_enabled(5000)
+        .await
+        .expect("Button never enabled");
+    button.click().await.expect("Failed to click");
+    assert_eq!(button.text().await.expect("Failed to get text"), "sent");
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_check_and_uncheck() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<input type='checkbox' id='terms'>\
+             <input type='radio' name='plan' id='basic' checked>",
+        )
+        .await
+        .expect("Failed to create page");
+
+    page.check("#terms").await.expect("Failed to check");
+    page.check("#terms").await.expect("Failed to check again");
+    let terms = page.find("#terms").await.expect("Failed to find checkbox");
+    assert!(terms.is_checked().await.expect("Failed to read state"));
+
+    page.uncheck("#terms").await.expect("Failed to uncheck");
+    page.uncheck("#terms")
+        .await
+        .expect("Failed to uncheck again");
+    assert!(!terms.is_checked().await.expect("Failed to read state"));
+
+    assert!(matches!(
+        page.uncheck("#basic").await,
+        Err(eoka::Error::InvalidState(_))
+    ));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_find_all_by_text_many_matches() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_blank_page()
+        .await
+        .expect("Failed to create page");
+    page.execute(
+        "for (let i = 0; i < 50; i++) { const b = document.createElement('button'); \
+         b.textContent = 'Add to \"Cart\" ' + i; document.body.appendChild(b); }",
+    )
+    .await
+    .expect("Failed to build page");
+    page.execute(
+        "window.mutations = 0; new MutationObserver(r => mutations += r.length)\
+         .observe(document, { attributes: true, subtree: true })",
+    )
+    .await
+    .expect("Failed to observe");
+
+    let found = page
+        .find_all_by_text("add to \"cart\"")
+        .await
+        .expect("Failed to find by text");
+    assert_eq!(found.len(), 50);
+    assert_eq!(
+        found[49].text().await.expect("Failed to get text"),
+        "Add to \"Cart\" 49"
+    );
+
+    // The DOM is never mutated, not even temporarily
+    let mutations: usize = page
+        .evaluate("new Promise(r => setTimeout(() => r(window.mutations), 50))")
+        .await
+        .expect("Failed to count mutations");
+    assert_eq!(mutations, 0);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_locate_unified_selectors() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<div class='card' id='c1'><button>Buy</button></div>\
+             <div class='card' id='c2'><button id='b2'>Buy now</button><span>Sold</span></div>\
+             <p>Checkout</p><a id='checkout'>Checkout</a><button id='next'>next >> page</button>",
+        )
+        .await
+        .expect("Failed to create page");
+
+    let sold = page
+        .locate("css=#c2 >> text=sold")
+        .await
+        .expect("Failed to locate by chained text");
+    assert_eq!(sold.tag_name().await.expect("Failed to get tag"), "span");
+
+    let exact = page
+        .locate("css=#c2 >> text=\"Buy now\"")
+        .await
+        .expect("Failed to locate exact text");
+    assert_eq!(
+        exact.get_attribute("id").await.expect("Failed to get id"),
+        Some("b2".into())
+    );
+
+    let xpath = page
+        .locate("xpath=//div[@id='c1']/button")
+        .await
+        .expect("Failed to locate by xpath");
+    assert_eq!(xpath.text().await.expect("Failed to get text"), "Buy");
+
+    assert!(page.locate(".card >> text=missing").await.is_err());
+
+    // Links and buttons win over static text that comes first
+    let link = page
+        .locate("text=checkout")
+        .await
+        .expect("Failed to locate link");
+    assert_eq!(link.tag_name().await.expect("Failed to get tag"), "a");
+
+    // `>>` inside quotes is text, not a chain separator
+    let next = page
+        .locate("text=\"next >> page\"")
+        .await
+        .expect("Failed to locate quoted separator");
+    assert_eq!(
+        next.get_attribute("id").await.expect("Failed to get id"),
+        Some("next".into())
+    );
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+/// Serve fixed HTML per path on a background thread, returning the port.
+/// Loading the same server as `127.0.0.1` and `localhost` gives two sites.
+fn serve_html(routes: Vec<(&'static str, String)>) -> u16 {
+    use std::io::{Read, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("Failed to bind");
+    let port = listener.local_addr().expect("No local addr").port();
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..n]);
+            let path = request.split_whitespace().nth(1).unwrap_or("/");
+            let body = routes
+                .iter()
+                .find(|(p, _)| *p == path)
+                .map(|(_, b)| b.as_str())
+                .unwrap_or("");
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+        }
+    });
+    port
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_cross_origin_frame_interaction() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    // The server binds 127.0.0.1; the iframe is loaded via `localhost`, a different site
+    let port = serve_html(vec![
+        (
+            "/frame",
+            "<input id='name' value='stale'><button id='go' onclick=\"this.textContent = 'hi ' + name.value\">Go</button>"
+                .to_string(),
+        ),
+        ("/", String::new()),
+    ]);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(&format!(
+            "data:text/html,<h1>Outer</h1><iframe id='widget' src='http://localhost:{}/frame'></iframe>",
+            port
+        ))
+        .await
+        .expect("Failed to create page");
+    page.wait(1000).await;
+
+    let frame = page.frame("#widget").await.expect("Failed to get frame");
+    frame
+        .fill("#name", "eoka")
+        .await
+        .expect("Failed to fill in frame");
+    frame.click("#go").await.expect("Failed to click in frame");
+
+    let text: String = frame
+        .evaluate("document.querySelector('#go').textContent")
+        .await
+        .expect("Failed to evaluate in frame");
+    assert_eq!(text, "hi eoka");
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_same_process_frame_handles_share_world() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<iframe id='inner' srcdoc=\"<input id='q' value='old'>\"></iframe>",
+        )
+        .await
+        .expect("Failed to create page");
+    page.wait(500).await;
+
+    // A second handle to the same document reuses the first one's world
+    let first = page.frame("#inner").await.expect("Failed to get frame");
+    assert!(!first.is_out_of_process());
+    let _: bool = first
+        .evaluate("(window.__eokaMark = true)")
+        .await
+        .expect("Failed to evaluate in frame");
+    let second = page.frame("#inner").await.expect("Failed to get frame");
+    let marked: bool = second
+        .evaluate("window.__eokaMark === true")
+        .await
+        .expect("Failed to evaluate in frame");
+    assert!(marked);
+
+    // fill replaces the existing value instead of appending to it
+    second
+        .fill("#q", "new")
+        .await
+        .expect("Failed to fill in frame");
+    let value: String = second
+        .evaluate("document.querySelector('#q').value")
+        .await
+        .expect("Failed to evaluate in frame");
+    assert_eq!(value, "new");
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_clipboard_roundtrip() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let port = serve_html(vec![("/", "<p>Clipboard</p>".to_string())]);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<p>opaque</p>")
+        .await
+        .expect("Failed to create page");
+
+    // Opaque origins can't be granted clipboard access
+    assert!(matches!(
+        page.set_clipboard("nope").await,
+        Err(eoka::Error::PermissionDenied(_))
+    ));
+
+    page.goto(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .expect("Failed to navigate");
+    page.set_clipboard("eoka clipboard ✓")
+        .await
+        .expect("Failed to write clipboard");
+    let text = page
+        .get_clipboard()
+        .await
+        .expect("Failed to read clipboard");
+    assert_eq!(text, "eoka clipboard ✓");
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_focused_element_after_tab() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<input id='first'><input id='second'>")
+        .await
+        .expect("Failed to create page");
+
+    assert!(page
+        .focused_element()
+        .await
+        .expect("Failed to get focused element")
+        .is_none());
+
+    page.press_key("Tab").await.expect("Failed to press Tab");
+    let focused = page
+        .focused_element()
+        .await
+        .expect("Failed to get focused element")
+        .expect("Nothing focused after Tab");
+    assert_eq!(
+        focused.get_attribute("id").await.expect("Failed to get id"),
+        Some("first".into())
+    );
+
+    let first = page.find("#first").await.expect("Failed to find #first");
+    let second = page.find("#second").await.expect("Failed to find #second");
+    assert!(first.is_focused().await.expect("Failed to check focus"));
+    assert!(!second.is_focused().await.expect("Failed to check focus"));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_tab_order_navigation() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<input id='a'><input id='b'><input id='c'>")
+        .await
+        .expect("Failed to create page");
+
+    let third = page.tab(3).await.expect("Failed to tab");
+    assert_eq!(
+        third.get_attribute("id").await.expect("Failed to get id"),
+        Some("c".into())
+    );
+
+    let second = page.shift_tab(1).await.expect("Failed to shift-tab");
+    assert_eq!(
+        second.get_attribute("id").await.expect("Failed to get id"),
+        Some("b".into())
+    );
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_goto_and_wait_loads_everything() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    // #b only exists once the load event (after the image) has fired
+    let port = serve_html(vec![(
+        "/",
+        "<html><body><p id='a'>static</p><img src='/pixel'>\
+         <script>addEventListener('load', () => \
+         document.body.insertAdjacentHTML('beforeend', '<p id=\"b\">loaded</p>'))</script>\
+         </body></html>"
+            .to_string(),
+    )]);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_blank_page()
+        .await
+        .expect("Failed to create page");
+
+    page.goto_and_wait(&format!("http://127.0.0.1:{}/", port), 10_000)
+        .await
+        .expect("Failed to navigate and wait");
+
+    let state: String = page
+        .evaluate("document.readyState")
+        .await
+        .expect("Failed to read readyState");
+    assert_eq!(state, "complete");
+    assert!(page.exists("#a").await);
+    assert!(page.exists("#b").await);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_wait_for_navigation_times_out_when_idle() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let port = serve_html(vec![("/", "<p>idle</p>".to_string())]);
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .expect("Failed to create page");
+    page.goto_and_wait(&format!("http://127.0.0.1:{}/", port), 10_000)
+        .await
+        .expect("Failed to load");
+
+    // Already loaded and nothing navigates: the old document doesn't count
+    let result = page.wait_for_navigation(500).await;
+    assert!(
+        matches!(result, Err(eoka::Error::Timeout(_))),
+        "expected a timeout, got {:?}",
+        result
+    );
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_wait_for_navigation_event_vs_polling() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let port = serve_html(vec![
+        (
+            "/",
+            "<html><body><a id='next' href='/next'>next</a></body></html>".to_string(),
+        ),
+        (
+            "/next",
+            "<html><body><p id='done'>done</p></body></html>".to_string(),
+        ),
+    ]);
+    let base = format!("http://127.0.0.1:{}", port);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(&format!("{}/", base))
+        .await
+        .expect("Failed to create page");
+
+    // Event-driven wait
+    let start = std::time::Instant::now();
+    page.execute("document.getElementById('next').click()")
+        .await
+        .expect("Failed to click");
+    page.wait_for_navigation(10_000)
+        .await
+        .expect("Failed to wait for navigation");
+    let event_elapsed = start.elapsed();
+    assert!(page.exists("#done").await);
+
+    // The old approach: poll readyState every 50ms until the new page is complete
+    page.goto_and_wait(&format!("{}/", base), 10_000)
+        .await
+        .expect("Failed to navigate back");
+    let start = std::time::Instant::now();
+    page.execute("document.getElementById('next').click()")
+        .await
+        .expect("Failed to click");
+    loop {
+        let done: bool = page
+            .evaluate("document.readyState === 'complete' && !!document.getElementById('done')")
+            .await
+            .unwrap_or(false);
+        if done {
+            break;
+        }
+        assert!(start.elapsed().as_secs() < 10, "polling never saw the load");
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    let poll_elapsed = start.elapsed();
+
+    eprintln!("event: {:?}, polling: {:?}", event_elapsed, poll_elapsed);
+    assert!(event_elapsed <= poll_elapsed + std::time::Duration::from_millis(100));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_intercept_rewrites_request_url() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let port = serve_html(vec![
+        (
+            "/",
+            "<html><body><script src='/a.js'></script></body></html>".to_string(),
+        ),
+        ("/a.js", "window.loaded = 'a';".to_string()),
+        ("/b.js", "window.loaded = 'b';".to_string()),
+    ]);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_blank_page()
+        .await
+        .expect("Failed to create page");
+
+    page.intercept(|req| {
+        if req.url.ends_with("/a.js") {
+            InterceptAction::Continue {
+                url: Some(req.url.replace("/a.js", "/b.js")),
+                headers: None,
+                post_data: None,
+            }
+        } else {
+            InterceptAction::default()
+        }
+    })
+    .await
+    .expect("Failed to install interceptor");
+
+    page.goto_and_wait(&format!("http://127.0.0.1:{}/", port), 10_000)
+        .await
+        .expect("Failed to navigate");
+    let loaded: String = page
+        .evaluate("window.loaded")
+        .await
+        .expect("Failed to read marker");
+    assert_eq!(loaded, "b");
+
+    page.stop_intercept()
+        .await
+        .expect("Failed to stop interceptor");
+    page.goto_and_wait(&format!("http://127.0.0.1:{}/", port), 10_000)
+        .await
+        .expect("Failed to navigate");
+    let loaded: String = page
+        .evaluate("window.loaded")
+        .await
+        .expect("Failed to read marker");
+    assert_eq!(loaded, "a");
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_hover_shows_css_tooltip() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<style>.tip{display:none}#t:hover .tip{display:block}</style><div id='t' style='width:200px;height:50px'>hover me<span class='tip'>tooltip</span></div>")
+        .await
+        .expect("Failed to create page");
+
+    let tip = page.find(".tip").await.expect("Failed to find tooltip");
+    assert!(!tip.is_visible().await.expect("Failed to check visibility"));
+
+    page.find("#t")
+        .await
+        .expect("Failed to find target")
+        .hover()
+        .await
+        .expect("Failed to hover");
+    assert!(tip.is_visible().await.expect("Failed to check visibility"));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_press_key_sends_event_code() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<input id='i'><script>window.codes=[];document.getElementById('i').addEventListener('keydown',e=>window.codes.push(e.code+':'+e.keyCode))</script>")
+        .await
+        .expect("Failed to create page");
+
+    let input = page.find("#i").await.expect("Failed to find input");
+    for key in ["Enter", "Escape", "ArrowLeft", "F2"] {
+        input.press_key(key).await.expect("Failed to press key");
+    }
+
+    let codes: Vec<String> = page
+        .evaluate("window.codes")
+        .await
+        .expect("Failed to read codes");
+    assert_eq!(codes, ["Enter:13", "Escape:27", "ArrowLeft:37", "F2:113"]);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_upload_multiple_files() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<input type='file' id='upload' multiple>")
+        .await
+        .expect("Failed to create page");
+
+    let temp_dir = std::env::temp_dir();
+    let first = temp_dir.join("eoka_test_upload_a.txt");
+    let second = temp_dir.join("eoka_test_upload_b.txt");
+    std::fs::write(&first, "a").expect("Failed to write temp file");
+    std::fs::write(&second, "b").expect("Failed to write temp file");
+
+    page.upload_files(
+        "#upload",
+        &[first.to_str().unwrap(), second.to_str().unwrap()],
+    )
+    .await
+    .expect("Failed to upload files");
+
+    let names: Vec<String> = page
+        .evaluate("[...document.getElementById('upload').files].map(f => f.name)")
+        .await
+        .expect("Failed to evaluate");
+    assert_eq!(names, ["eoka_test_upload_a.txt", "eoka_test_upload_b.txt"]);
+
+    // Missing files are rejected before reaching Chrome
+    let missing = page
+        .upload_file("#upload", "/nonexistent/eoka_missing.txt")
+        .await;
+    assert!(matches!(missing, Err(eoka::Error::FileNotReadable { .. })));
+
+    std::fs::remove_file(&first).ok();
+    std::fs::remove_file(&second).ok();
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_set_locale_matches_header() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    // Records the Accept-Language header of each request
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("Failed to bind");
+    let port = listener.local_addr().expect("No local addr").port();
+    let (header_tx, header_rx) = std::sync::mpsc::channel::<String>();
+    std::thread::spawn(move || {
+        use std::io::{Read, Write};
+        for mut stream in listener.incoming().flatten() {
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            if let Some(line) = request
+                .lines()
+                .find(|l| l.to_ascii_lowercase().starts_with("accept-language:"))
+            {
+                let _ = header_tx.send(line.split_once(':').unwrap().1.trim().to_string());
+            }
+            let body = "<html><body>locale</body></html>";
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+        }
+    });
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_blank_page()
+        .await
+        .expect("Failed to create page");
+
+    page.set_locale("de-DE")
+        .await
+        .expect("Failed to set locale");
+    page.goto(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .expect("Failed to navigate");
+
+    let language: String = page
+        .evaluate("navigator.language")
+        .await
+        .expect("Failed to read navigator.language");
+    let languages: Vec<String> = page
+        .evaluate("navigator.languages")
+        .await
+        .expect("Failed to read navigator.languages");
+    assert_eq!(language, "de-DE");
+    assert_eq!(languages, ["de-DE", "de"]);
+
+    let header = header_rx
+        .recv_timeout(std::time::Duration::from_secs(5))
+        .expect("No request seen");
+    assert!(
+        header.starts_with("de-DE"),
+        "Accept-Language was {}",
+        header
+    );
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_set_locale_twice_keeps_latest() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let port = serve_html(vec![("/", "<p>locale</p>".to_string())]);
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_blank_page()
+        .await
+        .expect("Failed to create page");
+    page.goto(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .expect("Failed to navigate");
+
+    page.set_locale("de-DE")
+        .await
+        .expect("Failed to set locale");
+    page.set_locale("fr-FR")
+        .await
+        .expect("Failed to switch locale");
+    page.reload_and_wait(10_000)
+        .await
+        .expect("Failed to reload");
+
+    let language: String = page
+        .evaluate("navigator.language")
+        .await
+        .expect("Failed to read navigator.language");
+    assert_eq!(language, "fr-FR");
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_evasions_apply_to_first_document() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    // Record navigator.webdriver while the first document is still parsing
+    let port = serve_html(vec![(
+        "/",
+        "<html><head><script>window.__wd = navigator.webdriver;</script></head><body></body></html>"
+            .to_string(),
+    )]);
+    let url = format!("http://127.0.0.1:{}/", port);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    for _ in 0..5 {
+        let page = browser.new_page(&url).await.expect("Failed to create page");
+        let webdriver: bool = page
+            .evaluate("window.__wd === false")
+            .await
+            .expect("Failed to read recorded webdriver");
+        assert!(webdriver, "evasions missed the first document");
+        browser
+            .close_tab(page.target_id())
+            .await
+            .expect("Failed to close tab");
+    }
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_capture_element_by_selector() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<h1>Shop</h1><div style='height:2000px'></div>\
+             <div id='card' style='width:200px;height:100px;background:red;\
+             padding:10px;border:5px solid blue'>Card</div><div id='empty'></div>",
+        )
+        .await
+        .expect("Failed to create page");
+
+    // IHDR width, big-endian at bytes 16..20
+    let png_width = |png: &[u8]| u32::from_be_bytes([png[16], png[17], png[18], png[19]]);
+
+    // The card is below the fold; the clip still captures it
+    let card = page
+        .screenshot_element("#card")
+        .await
+        .expect("Failed to screenshot element");
+    let full = page.screenshot().await.expect("Failed to take screenshot");
+    assert_eq!(&card[0..4], &[0x89, 0x50, 0x4E, 0x47]);
+    assert!(png_width(&card) < png_width(&full));
+    // Padding and border are part of the capture: 200 + 2 * (10 + 5)
+    let dpr: f64 = page.evaluate("devicePixelRatio").await.unwrap();
+    assert_eq!(png_width(&card), (230.0 * dpr).round() as u32);
+
+    let pdf = page
+        .pdf_element("#card")
+        .await
+        .expect("Failed to print element");
+    assert!(pdf.starts_with(b"%PDF"));
+
+    // The temporary print stylesheet is gone again
+    let leftover: bool = page
+        .evaluate("!!document.querySelector('#eoka-pdf-style, [data-eoka-pdf]')")
+        .await
+        .expect("Failed to evaluate");
+    assert!(!leftover);
+
+    // A zero-size element fails, and still leaves nothing behind
+    assert!(matches!(
+        page.pdf_element("#empty").await,
+        Err(eoka::Error::NodeNotVisible)
+    ));
+    let leftover: bool = page
+        .evaluate("!!document.querySelector('#eoka-pdf-style, [data-eoka-pdf]')")
+        .await
+        .expect("Failed to evaluate");
+    assert!(!leftover);
+
+    assert!(matches!(
+        page.screenshot_element("#missing").await,
+        Err(eoka::Error::ElementNotFound(_))
+    ));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_wait_until_combined_conditions() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<script>\
+             setTimeout(() => { location.hash = 'done'; }, 200);\
+             setTimeout(() => { document.body.innerHTML = '<p id=ok>ok</p>'; }, 400);\
+             </script>",
+        )
+        .await
+        .expect("Failed to create page");
+
+    // #ok doesn't exist at first: find() errors until it appears
+    page.wait_until(50, 5_000, || async {
+        Ok(page.url().await?.ends_with("#done") && page.find("#ok").await?.is_visible().await?)
+    })
+    .await
+    .expect("Conditions never held together");
+
+    let err = page
+        .wait_until(50, 200, || async {
+            page.find("#never").await.map(|_| true)
+        })
+        .await
+        .expect_err("Missing element should time out");
+    assert!(matches!(err, eoka::Error::Timeout(ref msg) if msg.contains("last error")));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_set_cookie_full_flags() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let port = serve_html(vec![("/", "<p>Cookies</p>".to_string())]);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    // localhost counts as a secure context, so Secure cookies stick over http
+    let page = browser
+        .new_page(&format!("http://localhost:{}/", port))
+        .await
+        .expect("Failed to create page");
+
+    page.set_cookie_full(eoka::cdp::Cookie {
+        name: "token".into(),
+        value: "abc".into(),
+        secure: true,
+        http_only: true,
+        same_site: Some("Strict".into()),
+        ..Default::default()
+    })
+    .await
+    .expect("Failed to set cookie");
+
+    let cookie = page
+        .cookie("token")
+        .await
+        .expect("Failed to read cookies")
+        .expect("Cookie missing");
+    assert_eq!(cookie.value, "abc");
+    assert!(cookie.secure);
+    assert!(cookie.http_only);
+    assert!(cookie.session);
+    assert_eq!(cookie.same_site.as_deref(), Some("Strict"));
+
+    // httpOnly hides it from scripts
+    let visible: String = page
+        .evaluate("document.cookie")
+        .await
+        .expect("Failed to read document.cookie");
+    assert!(!visible.contains("token"));
+
+    assert!(page
+        .cookie("missing")
+        .await
+        .expect("Failed to read cookies")
+        .is_none());
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_clear_cookies_and_browsing_data() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let port = serve_html(vec![("/", "<p>Clear</p>".to_string())]);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .expect("Failed to create page");
+
+    page.set_cookie("a", "1", None, None)
+        .await
+        .expect("Failed to set cookie");
+    page.set_cookie("b", "2", None, None)
+        .await
+        .expect("Failed to set cookie");
+    assert_eq!(
+        page.cookies().await.expect("Failed to get cookies").len(),
+        2
+    );
+
+    page.clear_cookies().await.expect("Failed to clear cookies");
+    assert!(page
+        .cookies()
+        .await
+        .expect("Failed to get cookies")
+        .is_empty());
+
+    page.set_cookie("c", "3", None, None)
+        .await
+        .expect("Failed to set cookie");
+    page.execute("localStorage.setItem('k', 'v')")
+        .await
+        .expect("Failed to write localStorage");
+
+    browser
+        .clear_browsing_data()
+        .await
+        .expect("Failed to clear browsing data");
+    assert!(page
+        .cookies()
+        .await
+        .expect("Failed to get cookies")
+        .is_empty());
+    let cleared: bool = page
+        .evaluate("localStorage.getItem('k') === null")
+        .await
+        .expect("Failed to read localStorage");
+    assert!(cleared);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_bring_to_front_visible() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let first = browser
+        .new_page("data:text/html,<p>first</p>")
+        .await
+        .expect("Failed to create page");
+    let _second = browser
+        .new_page("data:text/html,<p>second</p>")
+        .await
+        .expect("Failed to create page");
+
+    first
+        .bring_to_front()
+        .await
+        .expect("Failed to bring page to front");
+    let state: String = first
+        .evaluate("document.visibilityState")
+        .await
+        .expect("Failed to read visibilityState");
+    let hidden: bool = first
+        .evaluate("document.hidden")
+        .await
+        .expect("Failed to read document.hidden");
+    assert_eq!(state, "visible");
+    assert!(!hidden);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_background_page_not_hidden() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<script>window.__changes = 0;\
+             document.addEventListener('visibilitychange', () => window.__changes++);</script>",
+        )
+        .await
+        .expect("Failed to create page");
+    // Opening another tab puts the first one in the background
+    let _other = browser
+        .new_page("data:text/html,<p>other</p>")
+        .await
+        .expect("Failed to create page");
+
+    let hidden: bool = page
+        .evaluate("document.hidden")
+        .await
+        .expect("Failed to read document.hidden");
+    let changes: u32 = page
+        .evaluate("window.__changes")
+        .await
+        .expect("Failed to read change count");
+    assert!(!hidden);
+    assert_eq!(changes, 0);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_has_focus_in_background() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<input id='q'>")
+        .await
+        .expect("Failed to create page");
+    let _other = browser
+        .new_page("data:text/html,<p>other</p>")
+        .await
+        .expect("Failed to create page");
+
+    page.set_window_focus(true)
+        .await
+        .expect("Failed to emulate focus");
+    let focused: bool = page
+        .evaluate("document.hasFocus()")
+        .await
+        .expect("Failed to call hasFocus");
+    assert!(focused);
+
+    page.set_window_focus(false)
+        .await
+        .expect("Failed to stop focus emulation");
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_run_detection_suite() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let tabs_before = browser.tabs().await.expect("Failed to list tabs").len();
+
+    let report = browser
+        .run_detection_suite()
+        .await
+        .expect("Failed to run detection suite");
+    for name in [
+        "webdriver",
+        "automation_markers",
+        "plugins",
+        "chrome_runtime",
+        "headless_user_agent",
+        "platform",
+        "webgl",
+        "webgl_platform",
+        "languages",
+    ] {
+        assert!(report.check(name).is_some(), "missing check {}", name);
+    }
+    assert!(report.passed(), "detected:\n{}", report);
+
+    // The self-test tab is gone again
+    let tabs_after = browser.tabs().await.expect("Failed to list tabs").len();
+    assert_eq!(tabs_before, tabs_after);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_inner_text_vs_text_content() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<div id='card'>Price <span style='display:none'>secret</span><b>42</b></div>",
+        )
+        .await
+        .expect("Failed to create page");
+
+    let card = page.find("#card").await.expect("Failed to find card");
+    let inner = card.inner_text().await.expect("Failed to get innerText");
+    let content = card
+        .text_content()
+        .await
+        .expect("Failed to get textContent");
+
+    assert!(!inner.contains("secret"));
+    assert!(inner.contains("42"));
+    assert!(content.contains("secret"));
+    assert_eq!(card.text().await.expect("Failed to get text"), content);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_clear_prefilled_input() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<input id='name' value='prefilled'>")
+        .await
+        .expect("Failed to create page");
+
+    let input = page.find("#name").await.expect("Failed to find input");
+    input.clear().await.expect("Failed to clear input");
+
+    assert_eq!(input.value().await.expect("Failed to get value"), "");
+    assert!(input.is_focused().await.expect("Failed to check focus"));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_key_type_fires_key_events() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<input id='q'><script>window.keys = 0; document.getElementById('q').addEventListener('keydown', () => window.keys++);</script>",
+        )
+        .await
+        .expect("Failed to create page");
+
+    page.key_type("#q", "Ab-1")
+        .await
+        .expect("Failed to key_type");
+
+    let value: String = page
+        .evaluate("document.getElementById('q').value")
+        .await
+        .expect("Failed to read value");
+    let keys: u32 = page
+        .evaluate("window.keys")
+        .await
+        .expect("Failed to read count");
+    assert_eq!(value, "Ab-1");
+    assert_eq!(keys, 4);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_text_occurrences() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<div><button>Add to cart</button><button>Add to Cart</button><button>Add to cart</button><p>Checkout</p></div>",
+        )
+        .await
+        .expect("Failed to create page");
+
+    let count = page
+        .text_occurrences("add to cart")
+        .await
+        .expect("Failed to count text");
+    assert_eq!(count, 3);
+    assert_eq!(
+        count,
+        page.find_all_by_text("add to cart")
+            .await
+            .expect("Failed to find by text")
+            .len()
+    );
+    assert_eq!(
+        page.count_by_text("Checkout")
+            .await
+            .expect("Failed to count text"),
+        1
+    );
+    assert_eq!(
+        page.text_occurrences("missing")
+            .await
+            .expect("Failed to count text"),
+        0
+    );
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_content_to_writer_large_page() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("about:blank")
+        .await
+        .expect("Failed to create page");
+    // ~3M characters with a surrogate pair every row, so chunk edges land mid-document
+    page.execute(
+        "document.body.innerHTML = Array.from({length: 60000}, (_, i) => `<p>row ${i} 😀 padding padding</p>`).join('')",
+    )
+    .await
+    .expect("Failed to build page");
+
+    let mut html = Vec::new();
+    let written = page
+        .content_to_writer(&mut html)
+        .await
+        .expect("Failed to stream content");
+    let html = String::from_utf8(html).expect("Content is not UTF-8");
+
+    assert_eq!(written, html.len());
+    assert_eq!(html, page.content().await.expect("Failed to get content"));
+    assert!(html.contains("row 59999"));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_set_headers_on_navigation() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    // Records the X-Test header of each request ("" when absent)
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("Failed to bind");
+    let port = listener.local_addr().expect("No local addr").port();
+    let (header_tx, header_rx) = std::sync::mpsc::channel::<String>();
+    std::thread::spawn(move || {
+        use std::io::{Read, Write};
+        for mut stream in listener.incoming().flatten() {
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            if request.starts_with("GET /favicon") {
+                continue;
+            }
+            let header = request
+                .lines()
+                .find(|l| l.to_ascii_lowercase().starts_with("x-test:"))
+                .map(|l| l.split_once(':').unwrap().1.trim().to_string())
+                .unwrap_or_default();
+            let _ = header_tx.send(header);
+            let body = "<html><body>headers</body></html>";
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+        }
+    });
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_blank_page()
+        .await
+        .expect("Failed to create page");
+    let url = format!("http://127.0.0.1:{}/", port);
+    let next_header = || {
+        header_rx
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .expect("No request seen")
+    };
+
+    page.set_headers(std::collections::HashMap::from([(
+        "X-Test".to_string(),
+        "eoka".to_string(),
+    )]))
+    .await
+    .expect("Failed to set headers");
+    page.goto(&url).await.expect("Failed to navigate");
+    assert_eq!(next_header(), "eoka");
+
+    page.reload().await.expect("Failed to reload");
+    assert_eq!(next_header(), "eoka");
+
+    page.clear_headers().await.expect("Failed to clear headers");
+    page.goto(&url).await.expect("Failed to navigate");
+    assert_eq!(next_header(), "");
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_pool_caps_concurrent_pages() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let pool = eoka::BrowserPool::launch(1, 2, StealthConfig::default())
+        .await
+        .expect("Failed to launch pool");
+    let wait = std::time::Duration::from_millis(500);
+
+    let first = pool.acquire().await.expect("Failed to acquire page");
+    let second = pool.acquire().await.expect("Failed to acquire page");
+    assert_eq!(pool.available(), 0);
+    assert!(
+        tokio::time::timeout(wait, pool.acquire()).await.is_err(),
+        "acquire past the cap should block"
+    );
+    let tabs_at_cap = pool.browsers()[0]
+        .tabs()
+        .await
+        .expect("Failed to list tabs")
+        .len();
+
+    drop(first);
+    let third = tokio::time::timeout(wait, pool.acquire())
+        .await
+        .expect("acquire should succeed once a page is released")
+        .expect("Failed to acquire page");
+    // The slot only freed once the first tab was really closed
+    assert_eq!(
+        pool.browsers()[0]
+            .tabs()
+            .await
+            .expect("Failed to list tabs")
+            .len(),
+        tabs_at_cap
+    );
+    third
+        .goto("about:blank")
+        .await
+        .expect("Failed to navigate pooled page");
+
+    drop(third);
+    drop(second);
+    pool.close().await.expect("Failed to close pool");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_download_and_read() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<a id='dl' href='data:text/plain;base64,aGVsbG8gZW9rYQ==' download='hello.txt'>Download</a>",
+        )
+        .await
+        .expect("Failed to create page");
+
+    let dir = std::env::temp_dir().join(format!("eoka-test-downloads-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    page.set_download_behavior("allow", Some(&dir.to_string_lossy()))
+        .await
+        .expect("Failed to set download behavior");
+
+    let (name, bytes) = page
+        .download_and_read(page.click("#dl"), 10_000)
+        .await
+        .expect("Failed to download");
+
+    assert_eq!(name, "hello.txt");
+    assert_eq!(bytes, b"hello eoka");
+
+    // The behavior set before is back: plain downloads land in `dir` again
+    page.click("#dl").await.expect("Failed to click");
+    let saved = dir.join("hello.txt");
+    for _ in 0..50 {
+        if saved.exists() {
+            break;
+        }
+        page.wait(100).await;
+    }
+    assert!(saved.exists(), "download not restored to {:?}", dir);
+    std::fs::remove_dir_all(&dir).ok();
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_client_rect_after_scroll() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<body style='margin:0'><div style='height:3000px'></div><div id='box' style='width:100px;height:50px'></div><div style='height:3000px'></div></body>",
+        )
+        .await
+        .expect("Failed to create page");
+    let elem = page.find("#box").await.expect("Failed to find box");
+
+    let before = elem.client_rect().await.expect("Failed to get client rect");
+    assert_eq!(before.y, 3000.0);
+    assert_eq!((before.width, before.height), (100.0, 50.0));
+
+    page.execute("window.scrollTo(0, 2800)")
+        .await
+        .expect("Failed to scroll");
+    let after = elem.client_rect().await.expect("Failed to get client rect");
+    let bbox = elem.bounding_box().await.expect("Box should be visible");
+
+    // Both are viewport-relative; no padding/border, so they agree
+    assert_eq!(after.y, 200.0);
+    assert!((bbox.y - after.y).abs() < 1.0, "{:?} vs {:?}", bbox, after);
+    assert!((bbox.x - after.x).abs() < 1.0, "{:?} vs {:?}", bbox, after);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_scroll_to_bottom_lazy_load() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    // Appends a viewport-sized block near the bottom, up to 5 times
+    let page = browser
+        .new_page(
+            "data:text/html,<body style='margin:0'><div class='item' style='height:2000px'></div><script>
+                window.addEventListener('scroll', () => {
+                    const items = document.querySelectorAll('.item').length;
+                    if (items < 6 && scrollY + innerHeight >= document.body.scrollHeight - 10) {
+                        setTimeout(() => {
+                            const div = document.createElement('div');
+                            div.className = 'item';
+                            div.style.height = '2000px';
+                            document.body.appendChild(div);
+                        }, 50);
+                    }
+                });
+            </script></body>",
+        )
+        .await
+        .expect("Failed to create page");
+
+    let scrolls = page
+        .scroll_to_bottom(100, 800)
+        .await
+        .expect("Failed to scroll");
+    let items: u32 = page
+        .evaluate("document.querySelectorAll('.item').length")
+        .await
+        .expect("Failed to count items");
+    let at_bottom: bool = page
+        .evaluate("scrollY + innerHeight >= document.documentElement.scrollHeight - 1")
+        .await
+        .expect("Failed to read scroll position");
+
+    assert_eq!(items, 6);
+    assert!(at_bottom);
+    assert!(scrolls > 5 && scrolls < 100, "scrolled {} times", scrolls);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_add_init_script_survives_reload() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<p>init</p>")
+        .await
+        .expect("Failed to create page");
+    let has_marker = || page.evaluate::<bool>("window.eokaMarker === 42");
+
+    let id = page
+        .add_init_script("window.eokaMarker = 42;")
+        .await
+        .expect("Failed to add init script");
+    assert!(!has_marker().await.expect("Failed to check marker"));
+
+    page.reload_and_wait(10_000)
+        .await
+        .expect("Failed to reload");
+    assert!(has_marker().await.expect("Failed to check marker"));
+
+    page.remove_init_script(&id)
+        .await
+        .expect("Failed to remove init script");
+    page.reload_and_wait(10_000)
+        .await
+        .expect("Failed to reload");
+    assert!(!has_marker().await.expect("Failed to check marker"));
+
+    page.inject_script("window.eokaMarker = 42;")
+        .await
+        .expect("Failed to inject script");
+    assert!(has_marker().await.expect("Failed to check marker"));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_disable_evasions_per_page() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<p>evasions</p>")
+        .await
+        .expect("Failed to create page");
+    let other = browser
+        .new_page("data:text/html,<p>other</p>")
+        .await
+        .expect("Failed to create page");
+    // window.chrome.runtime only exists on plain pages because of the evasions
+    let has_runtime = "!!(window.chrome && window.chrome.runtime)";
+
+    assert!(page.evasions_enabled());
+    assert!(page
+        .evaluate::<bool>(has_runtime)
+        .await
+        .expect("Failed to evaluate"));
+
+    page.disable_evasions()
+        .await
+        .expect("Failed to disable evasions");
+    assert!(!page.evasions_enabled());
+    page.reload_and_wait(10_000)
+        .await
+        .expect("Failed to reload");
+    assert!(!page
+        .evaluate::<bool>(has_runtime)
+        .await
+        .expect("Failed to evaluate"));
+
+    // Other pages keep theirs
+    other
+        .reload_and_wait(10_000)
+        .await
+        .expect("Failed to reload");
+    assert!(other
+        .evaluate::<bool>(has_runtime)
+        .await
+        .expect("Failed to evaluate"));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_set_timezone_override() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let config = StealthConfig {
+        timezone_override: Some("Europe/Berlin".into()),
+        ..Default::default()
+    };
+    let browser = Browser::launch_with_config(config)
+        .await
+        .expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<p>tz</p>")
+        .await
+        .expect("Failed to create page");
+    let zone = "Intl.DateTimeFormat().resolvedOptions().timeZone";
+
+    let configured: String = page.evaluate(zone).await.expect("Failed to read zone");
+    assert_eq!(configured, "Europe/Berlin");
+
+    page.set_timezone("Asia/Tokyo")
+        .await
+        .expect("Failed to set timezone");
+    let overridden: String = page.evaluate(zone).await.expect("Failed to read zone");
+    let offset: i32 = page
+        .evaluate("new Date().getTimezoneOffset()")
+        .await
+        .expect("Failed to read offset");
+    assert_eq!(overridden, "Asia/Tokyo");
+    assert_eq!(offset, -540);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_read_selection() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<p id='intro'>Hello <b>eoka</b> world</p><p id='other'>Other</p><input id='name' value='Ada Lovelace'>")
+        .await
+        .expect("Failed to create page");
+
+    // Document selection spanning both paragraphs
+    page.execute(
+        "const r = document.createRange(); r.setStart(document.querySelector('#intro b').firstChild, 0); r.setEnd(document.querySelector('#other').firstChild, 3); getSelection().removeAllRanges(); getSelection().addRange(r);",
+    )
+    .await
+    .expect("Failed to select text");
+    let intro = page.find("#intro").await.expect("Failed to find intro");
+    assert_eq!(
+        intro.selection().await.expect("Failed to read selection"),
+        "eoka world"
+    );
+    assert!(page
+        .selection()
+        .await
+        .expect("Failed to read selection")
+        .starts_with("eoka world"));
+
+    // Field selection
+    let input = page.find("#name").await.expect("Failed to find input");
+    input.select_text().await.expect("Failed to select input");
+    assert_eq!(
+        page.selection().await.expect("Failed to read selection"),
+        "Ada Lovelace"
+    );
+    assert_eq!(
+        input.selection().await.expect("Failed to read selection"),
+        "Ada Lovelace"
+    );
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_evaluate_json_dynamic() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<title>json</title>")
+        .await
+        .expect("Failed to create page");
+
+    let value = page
+        .evaluate_json(
+            "({ title: document.title, n: 1.5, flags: [true, null], nested: { ok: 'yes' } })",
+        )
+        .await
+        .expect("Failed to evaluate");
+    assert_eq!(
+        value,
+        serde_json::json!({
+            "title": "json",
+            "n": 1.5,
+            "flags": [true, null],
+            "nested": { "ok": "yes" }
+        })
+    );
+
+    assert_eq!(
+        page.evaluate_json("undefined")
+            .await
+            .expect("Failed to evaluate"),
+        serde_json::Value::Null
+    );
+    assert!(matches!(
+        page.evaluate_json("missingFunction()").await,
+        Err(eoka::Error::JavaScriptException { .. })
+    ));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_evaluate_async_awaits_fetch() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let port = serve_html(vec![
+        ("/", "<p>home</p>".to_string()),
+        ("/api", "{}".to_string()),
+    ]);
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .expect("Failed to create page");
+
+    let status: u16 = page
+        .evaluate_async("await fetch('/api').then(r => r.status)")
+        .await
+        .expect("Failed to await fetch");
+    assert_eq!(status, 200);
+
+    let sum: i32 = page
+        .evaluate_async("(await Promise.resolve(40)) + 2")
+        .await
+        .expect("Failed to await promise");
+    assert_eq!(sum, 42);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_hard_reload_refetches_cached_resource() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    // Serves a page with a long-cacheable script and counts script fetches
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("Failed to bind");
+    let port = listener.local_addr().expect("No local addr").port();
+    let script_hits = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let hits = std::sync::Arc::clone(&script_hits);
+    std::thread::spawn(move || {
+        use std::io::{Read, Write};
+        for mut stream in listener.incoming().flatten() {
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            let (content_type, body) = if request.starts_with("GET /app.js") {
+                hits.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                ("application/javascript", "window.loaded = true;")
+            } else {
+                ("text/html", "<script src='/app.js'></script>")
+            };
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nCache-Control: max-age=3600\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                content_type,
+                body.len(),
+                body
+            );
+        }
+    });
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_blank_page()
+        .await
+        .expect("Failed to create page");
+    page.goto_and_wait(&format!("http://127.0.0.1:{}/", port), 10_000)
+        .await
+        .expect("Failed to navigate");
+    assert_eq!(script_hits.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+    page.hard_reload().await.expect("Failed to hard reload");
+    page.wait_until(100, 10_000, || async {
+        Ok(script_hits.load(std::sync::atomic::Ordering::SeqCst) == 2)
+    })
+    .await
+    .expect("Hard reload should fetch the script again");
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_click_with_fallback_under_overlay() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<button id='buy' onclick='window.clicks = (window.clicks || 0) + 1'>Buy</button><button id='free' onclick='window.free = true' style='margin-left:400px'>Free</button><div style='position:fixed;top:0;left:0;width:300px;height:300px;background:transparent'></div><button id='far' onclick='window.farTrusted = event.isTrusted' style='margin:3000px 0 0 400px'>Far</button>",
+        )
+        .await
+        .expect("Failed to create page");
+    let clicks = || page.evaluate::<u32>("window.clicks || 0");
+
+    // The overlay swallows the real mouse click
+    page.click("#buy").await.expect("Failed to click");
+    assert_eq!(clicks().await.expect("Failed to read clicks"), 0);
+
+    page.click_with_fallback("#buy")
+        .await
+        .expect("Failed to click with fallback");
+    assert_eq!(clicks().await.expect("Failed to read clicks"), 1);
+
+    // Uncovered elements still get the real click
+    page.click_with_fallback("#free")
+        .await
+        .expect("Failed to click with fallback");
+    assert!(page
+        .evaluate::<bool>("window.free === true")
+        .await
+        .expect("Failed to read flag"));
+
+    // Below the fold: scrolled into view first, then really clicked
+    page.click_with_fallback("#far")
+        .await
+        .expect("Failed to click with fallback");
+    assert!(page
+        .evaluate::<bool>("window.farTrusted === true")
+        .await
+        .expect("Failed to read flag"));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_set_zoom_scales_visual_viewport() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<button id='btn' style='margin:300px 0 0 300px' onclick='window.clicked = true'>Zoomed</button>",
+        )
+        .await
+        .expect("Failed to create page");
+
+    page.set_zoom(2.0).await.expect("Failed to zoom");
+    let scale: f64 = page
+        .evaluate("window.visualViewport.scale")
+        .await
+        .expect("Failed to read scale");
+    assert_eq!(scale, 2.0);
+
+    // Clicking still lands on the element with the zoom applied
+    page.click("#btn").await.expect("Failed to click");
+    assert!(page
+        .evaluate::<bool>("window.clicked === true")
+        .await
+        .expect("Failed to read flag"));
+
+    page.set_zoom(1.0).await.expect("Failed to reset zoom");
+    let scale: f64 = page
+        .evaluate("window.visualViewport.scale")
+        .await
+        .expect("Failed to read scale");
+    assert_eq!(scale, 1.0);
+    assert!(page.set_zoom(0.0).await.is_err());
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_wait_for_request_on_click() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let html = r#"<button id="load" onclick="fetch('/api/items?page=2', { method: 'POST', body: 'x' })">Load</button>"#;
+    let port = serve_html(vec![("/", html.to_string())]);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .expect("Failed to create page");
+    page.enable_request_capture()
+        .await
+        .expect("Failed to enable capture");
+
+    let (request, clicked) = tokio::join!(
+        page.wait_for_request("/api/items", 10_000),
+        page.click("#load"),
+    );
+    clicked.expect("Failed to click");
+    let request = request.expect("Request not seen");
+    assert!(request.url.ends_with("/api/items?page=2"));
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.status, Some(200));
+    assert_eq!(request.mime_type.as_deref(), Some("text/html"));
+
+    // Nothing else matches, so this times out
+    assert!(page.wait_for_request("/never", 500).await.is_err());
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_stream_response_body() {
+    use std::io::{Read, Write};
+
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    const CHUNK: usize = 256 * 1024;
+    const CHUNKS: usize = 8;
+
+    // Serves the page, and a large body in slow chunks so it's still in flight
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("Failed to bind");
+    let port = listener.local_addr().expect("No local addr").port();
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap_or(0);
+            let request = String::from_utf8_lossy(&buf[..n]);
+            if request.starts_with("GET /big") {
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/octet-stream\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    CHUNK * CHUNKS
+                );
+                for i in 0..CHUNKS {
+                    std::thread::sleep(std::time::Duration::from_millis(150));
+                    let _ = stream.write_all(&vec![b'a' + i as u8; CHUNK]);
+                }
+            } else {
+                let body = "<p>stream</p>";
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+            }
+        }
+    });
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .expect("Failed to create page");
+    page.enable_request_capture()
+        .await
+        .expect("Failed to enable capture");
+
+    let (request, started) = tokio::join!(
+        page.wait_for_request("/big", 10_000),
+        page.execute("fetch('/big').then(r => r.arrayBuffer())"),
+    );
+    started.expect("Failed to start fetch");
+    let request = request.expect("Request not seen");
+
+    let mut body = Vec::new();
+    let written = page
+        .stream_response_body(&request.request_id, &mut body, 10_000)
+        .await
+        .expect("Failed to stream body");
+    assert_eq!(written, CHUNK * CHUNKS);
+    assert_eq!(body.len(), CHUNK * CHUNKS);
+    assert!(body[..CHUNK].iter().all(|&b| b == b'a'));
+    assert!(body[CHUNK * (CHUNKS - 1)..].iter().all(|&b| b == b'h'));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_new_page_with_config_user_agent() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let default_page = browser
+        .new_page("data:text/html,<p>default</p>")
+        .await
+        .expect("Failed to create page");
+
+    let custom_ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36 Identity/2";
+    let config = StealthConfig {
+        user_agent: Some(custom_ua.into()),
+        viewport_width: 1024,
+        viewport_height: 700,
+        ..browser.config().clone()
+    };
+    let custom_page = browser
+        .new_page_with_config("data:text/html,<p>custom</p>", config)
+        .await
+        .expect("Failed to create page");
+
+    let default_ua: String = default_page
+        .evaluate("navigator.userAgent")
+        .await
+        .expect("Failed to read UA");
+    let page_ua: String = custom_page
+        .evaluate("navigator.userAgent")
+        .await
+        .expect("Failed to read UA");
+    assert_eq!(page_ua, custom_ua);
+    assert_ne!(default_ua, page_ua);
+
+    let width: u32 = custom_page
+        .evaluate("window.innerWidth")
+        .await
+        .expect("Failed to read width");
+    assert_eq!(width, 1024);
+    // The evasions still run on the custom page
+    assert!(!custom_page
+        .evaluate::<bool>("navigator.webdriver === true")
+        .await
+        .expect("Failed to read webdriver"));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_new_page_with_config_languages_and_client_hints() {
+    use std::io::{Read, Write};
+
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    // Echo each request's headers back as the body
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("Failed to bind");
+    let port = listener.local_addr().expect("No local addr").port();
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut buf = [0u8; 4096];
+            let n = stream.read(&mut buf).unwrap_or(0);
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                n
+            );
+            let _ = stream.write_all(&buf[..n]);
+        }
+    });
+    let url = format!("http://127.0.0.1:{}/", port);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+
+    // Only the languages change: the header must follow navigator.languages
+    let config = StealthConfig {
+        languages: vec!["de-DE".into(), "de".into()],
+        ..browser.config().clone()
+    };
+    let page = browser
+        .new_page_with_config(&url, config)
+        .await
+        .expect("Failed to create page");
+    let headers = page.text().await.expect("Failed to read body");
+    assert!(
+        headers.contains("Accept-Language: de-DE,de;q=0.9"),
+        "{}",
+        headers
+    );
+
+    // A different UA brings matching Client Hints
+    let custom_ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36";
+    let config = StealthConfig {
+        user_agent: Some(custom_ua.into()),
+        ..browser.config().clone()
+    };
+    let page = browser
+        .new_page_with_config(&url, config)
+        .await
+        .expect("Failed to create page");
+    let hints: serde_json::Value = page
+        .evaluate(
+            "navigator.userAgentData.getHighEntropyValues(['platform', 'fullVersionList'])\
+             .then(d => ({ platform: d.platform, brands: d.fullVersionList.map(b => b.brand + '/' + b.version) }))",
+        )
+        .await
+        .expect("Failed to read Client Hints");
+    assert_eq!(hints["platform"], "Windows");
+    assert!(hints["brands"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!("Google Chrome/131.0.0.0")));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_forms_login() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let html = r#"
+        <form id="login" action="/session" method="post">
+            <label for="email">Email address</label>
+            <input id="email" name="email" type="email" required>
+            <input name="password" type="password" placeholder="Password" required>
+            <select name="region" aria-label="Region">
+                <option value="us">United States</option>
+                <option value="eu" selected>Europe</option>
+            </select>
+            <label><input type="checkbox" name="remember" checked> Remember me</label>
+            <button type="submit">Sign in</button>
+        </form>"#;
+    let port = serve_html(vec![("/", html.to_string())]);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .expect("Failed to create page");
+
+    let forms = page.forms().await.expect("Failed to read forms");
+    assert_eq!(forms.len(), 1);
+    let form = &forms[0];
+    assert_eq!(form.method, "POST");
+    assert_eq!(form.action, format!("http://127.0.0.1:{}/session", port));
+    // The submit button isn't a field
+    assert_eq!(form.fields.len(), 4);
+
+    let email = form.field("email").expect("No email field");
+    assert_eq!(email.field_type, "email");
+    assert_eq!(email.label.as_deref(), Some("Email address"));
+    assert!(email.required);
+
+    let password = form.field("password").expect("No password field");
+    assert_eq!(password.field_type, "password");
+    assert_eq!(password.label.as_deref(), Some("Password"));
+
+    let region = form.field("region").expect("No region field");
+    assert_eq!(region.field_type, "select-one");
+    assert_eq!(region.label.as_deref(), Some("Region"));
+    assert_eq!(region.value, "eu");
+    assert_eq!(region.options.len(), 2);
+
+    let remember = form.field("remember").expect("No remember field");
+    assert!(remember.checked);
+    assert_eq!(remember.label.as_deref(), Some("Remember me"));
+
+    // The selectors work with the fill API
+    page.fill(&password.selector, "hunter2")
+        .await
+        .expect("Failed to fill via selector");
+    let forms = page.forms().await.expect("Failed to read forms");
+    assert_eq!(forms[0].field("password").unwrap().value, "hunter2");
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_submit_form_validates_and_fires_handler() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    // No submit button at all
+    let html = r#"
+        <form id="search" onsubmit="event.preventDefault(); window.submitted = (window.submitted || 0) + 1">
+            <input id="q" name="q" required oninvalid="window.invalid = true">
+        </form>"#;
+    let port = serve_html(vec![("/", html.to_string())]);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .expect("Failed to create page");
+
+    // Empty required field: validation fires, the handler doesn't
+    page.submit("#search").await.expect("Failed to submit");
+    assert!(page
+        .evaluate::<bool>("window.invalid === true && window.submitted === undefined")
+        .await
+        .expect("Failed to read flags"));
+
+    // Filled in: submitting via a field inside the form runs the handler
+    page.fill("#q", "eoka").await.expect("Failed to fill");
+    page.submit("#q").await.expect("Failed to submit");
+    let submitted: u32 = page
+        .evaluate("window.submitted")
+        .await
+        .expect("Failed to read count");
+    assert_eq!(submitted, 1);
+
+    assert!(page.submit("body").await.is_err());
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_element_wait_for_scoped_child() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<div id='a' class='card'></div><div id='b' class='card'><span class='price'>1</span></div>",
+        )
+        .await
+        .expect("Failed to create page");
+
+    let card = page.find("#a").await.expect("Failed to find card");
+    // A match elsewhere on the page doesn't count
+    assert!(card.wait_for(".price", 300).await.is_err());
+
+    page.execute(
+        "setTimeout(() => { const s = document.createElement('span'); s.className = 'price'; s.textContent = '42'; document.getElementById('a').appendChild(s); }, 300)",
+    )
+    .await
+    .expect("Failed to schedule insert");
+
+    let price = card
+        .wait_for(".price", 5000)
+        .await
+        .expect("Child never appeared");
+    assert_eq!(price.text().await.expect("Failed to read text"), "42");
+    assert_eq!(
+        card.find_all("span").await.expect("Failed to find").len(),
+        1
+    );
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_readable_text_article() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let html = r#"
+        <header><a href="/">SiteLogo</a></header>
+        <nav><a href="/home">HomeLink</a> <a href="/about">AboutLink</a></nav>
+        <article>
+            <h1>Release notes</h1>
+            <p>The first paragraph of the <b>article</b>.</p>
+            <div class="ad" style="display:none">HiddenAd</div>
+            <ul><li>Faster startup</li><li><p>Smaller binary</p></li></ul>
+            <svg><text>SvgLabel</text></svg>
+            <p>The closing paragraph.</p>
+            <script>var trackingCode = 1;</script>
+        </article>
+        <footer>CopyrightNotice</footer>"#;
+    let port = serve_html(vec![("/", html.to_string())]);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .expect("Failed to create page");
+
+    let text = page.readable_text().await.expect("Failed to extract text");
+    assert!(text.starts_with("# Release notes"), "{}", text);
+    assert!(text.contains("The first paragraph of the article."));
+    assert!(
+        text.contains("- Faster startup\n\n- Smaller binary"),
+        "{}",
+        text
+    );
+    assert!(text.contains("The closing paragraph."));
+    for excluded in [
+        "SiteLogo",
+        "HomeLink",
+        "AboutLink",
+        "HiddenAd",
+        "SvgLabel",
+        "trackingCode",
+        "CopyrightNotice",
+    ] {
+        assert!(
+            !text.contains(excluded),
+            "{} leaked into {}",
+            excluded,
+            text
+        );
+    }
+
+    // A WebForms-style page: one form around everything, plus a real form
+    let html = r#"
+        <form id="aspnetForm">
+            <h1>Quarterly report</h1>
+            <p>Revenue grew across every region this quarter, led by strong demand.</p>
+            <p>Costs stayed flat while the team shipped two major releases.</p>
+        </form>
+        <form><input name="q"><label>SearchBox</label></form>"#;
+    let port = serve_html(vec![("/", html.to_string())]);
+    page.goto(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .expect("Failed to navigate");
+    let text = page.readable_text().await.expect("Failed to extract text");
+    assert!(text.starts_with("# Quarterly report"), "{}", text);
+    assert!(text.contains("Revenue grew"), "{}", text);
+    assert!(!text.contains("SearchBox"), "{}", text);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_click_with_device_scale_factor() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<button id='tiny' style='position:absolute;left:600px;top:500px;width:8px;height:8px;padding:0;border:0' onclick='window.clicked = true'></button>",
+        )
+        .await
+        .expect("Failed to create page");
+    page.set_viewport(800, 600, 2.0)
+        .await
+        .expect("Failed to set viewport");
+    assert_eq!(
+        page.evaluate::<f64>("window.devicePixelRatio")
+            .await
+            .expect("Failed to read DPR"),
+        2.0
+    );
+
+    // Box model and mouse events share CSS pixels; a device-pixel mix-up
+    // would land far from an 8px button
+    let (x, y) = page
+        .find("#tiny")
+        .await
+        .expect("Failed to find button")
+        .center()
+        .await
+        .expect("Failed to get center");
+    assert_eq!((x, y), (604.0, 504.0));
+    page.click("#tiny").await.expect("Failed to click");
+    assert!(page
+        .evaluate::<bool>("window.clicked === true")
+        .await
+        .expect("Failed to read flag"));
+
+    // Screenshots come back in device pixels
+    let png_width = |png: &[u8]| u32::from_be_bytes([png[16], png[17], png[18], png[19]]);
+    let shot = page
+        .screenshot_element("#tiny")
+        .await
+        .expect("Failed to screenshot element");
+    assert_eq!(png_width(&shot), 16);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_page_attribute_and_css_shortcuts() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<style>.next { color: rgb(0, 128, 0) }</style><a class='next' href='/page/2'>Next</a>",
+        )
+        .await
+        .expect("Failed to create page");
+
+    assert_eq!(
+        page.get_attribute("a.next", "href")
+            .await
+            .expect("Failed to read attribute")
+            .as_deref(),
+        Some("/page/2")
+    );
+    assert_eq!(
+        page.get_attribute("a.next", "target")
+            .await
+            .expect("Failed to read attribute"),
+        None
+    );
+    assert_eq!(
+        page.css("a.next", "color")
+            .await
+            .expect("Failed to read css"),
+        "rgb(0, 128, 0)"
+    );
+    assert!(matches!(
+        page.css("#missing", "color").await,
+        Err(eoka::Error::ElementNotFound(_))
+    ));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_element_inspection_args_not_spliced() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<input id='field' value='typed'><button id='other'>x</button>")
+        .await
+        .expect("Failed to create page");
+    page.execute(
+        "document.getElementById('field').setAttribute('data-document.activeElement', 'kept')",
+    )
+    .await
+    .expect("Failed to set attribute");
+    page.find("#other")
+        .await
+        .expect("Failed to find")
+        .focus()
+        .await
+        .expect("Failed to focus");
+
+    let field = page.find("#field").await.expect("Failed to find field");
+    // Read from the field itself, not whatever has focus
+    assert_eq!(
+        field
+            .get_attribute("data-document.activeElement")
+            .await
+            .expect("Failed to read attribute")
+            .as_deref(),
+        Some("kept")
+    );
+    // Names are passed as data, so quotes can't break out of the script
+    assert_eq!(
+        field
+            .get_attribute("x') || 'injected")
+            .await
+            .expect("Failed to read attribute"),
+        None
+    );
+    assert_eq!(field.value().await.expect("Failed to read value"), "typed");
+    assert_eq!(field.tag_name().await.expect("Failed to read tag"), "input");
+    assert_eq!(
+        field.css("display").await.expect("Failed to read css"),
+        "inline-block"
+    );
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_cookie_header_for_url() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let port = serve_html(vec![("/", "<p>cookies</p>".to_string())]);
+    let origin = format!("http://127.0.0.1:{}", port);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(&format!("{}/", origin))
+        .await
+        .expect("Failed to create page");
+    page.execute("document.cookie = 'sid=abc123; path=/'; document.cookie = 'cart=2; path=/shop'")
+        .await
+        .expect("Failed to set cookies");
+
+    let root = page
+        .cookie_header(&format!("{}/", origin))
+        .await
+        .expect("Failed to build header");
+    assert_eq!(root, "sid=abc123");
+
+    // The /shop cookie only applies under its path, and comes first there
+    let shop = page
+        .cookie_header(&format!("{}/shop/item", origin))
+        .await
+        .expect("Failed to build header");
+    assert_eq!(shop, "cart=2; sid=abc123");
+
+    let other = page
+        .cookie_header("http://example.invalid/")
+        .await
+        .expect("Failed to build header");
+    assert_eq!(other, "");
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_dom_node_count_grows() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<div id='root'></div>")
+        .await
+        .expect("Failed to create page");
+
+    let before = page.dom_node_count().await.expect("Failed to read metrics");
+    page.execute(
+        "for (let i = 0; i < 500; i++) document.getElementById('root').appendChild(document.createElement('span'))",
+    )
+    .await
+    .expect("Failed to inject elements");
+    let after = page.dom_node_count().await.expect("Failed to read metrics");
+    assert!(after >= before + 500, "{} -> {}", before, after);
+
+    assert!(page.js_heap_used().await.expect("Failed to read metrics") > 0);
+    let state = page.debug_state().await.expect("Failed to read state");
+    assert!(state.dom_node_count >= after);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_debug_state_single_evaluate() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let log = std::env::temp_dir().join(format!("eoka-debug-state-{}.jsonl", std::process::id()));
+    let config = StealthConfig {
+        cdp_log: Some(log.clone()),
+        ..Default::default()
+    };
+    let browser = Browser::launch_with_config(config)
+        .await
+        .expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<title>Snap</title><iframe></iframe><img src='data:,'><script>1</script>\
+             <a href='/'>x</a><form><input><input type=checkbox><button>Go</button></form>",
+        )
+        .await
+        .expect("Failed to create page");
+
+    let evaluate_count = || {
+        std::fs::read_to_string(&log)
+            .expect("Failed to read CDP log")
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .filter(|line| line["dir"] == "send" && line["message"]["method"] == "Runtime.evaluate")
+            .count()
+    };
+
+    let before = evaluate_count();
+    let state = page.debug_state().await.expect("Failed to read state");
+    assert_eq!(evaluate_count() - before, 1);
+
+    assert_eq!(state.iframe_count, 1);
+    assert_eq!(state.image_count, 1);
+    assert_eq!(state.script_count, 1);
+    assert_eq!(state.title, "Snap");
+    assert_eq!(state.input_count, 2);
+    assert_eq!(state.button_count, 1);
+    assert_eq!(state.link_count, 1);
+    assert_eq!(state.form_count, 1);
+    assert_eq!(state.ready_state, "complete");
+
+    browser.close().await.expect("Failed to close browser");
+    let _ = std::fs::remove_file(&log);
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_mouse_drag_draws_on_canvas() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let html = r#"
+        <body style="margin:0">
+        <canvas id="c" width="200" height="200"></canvas>
+        <script>
+            const canvas = document.getElementById('c');
+            const ctx = canvas.getContext('2d');
+            canvas.addEventListener('mousemove', e => {
+                if (e.buttons & 1) ctx.fillRect(e.offsetX - 2, e.offsetY - 2, 4, 4);
+            });
+        </script>
+        </body>"#;
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(&format!("data:text/html,{}", html))
+        .await
+        .expect("Failed to create page");
+
+    let painted = r#"
+        Array.from(document.getElementById('c').getContext('2d')
+            .getImageData(0, 0, 200, 200).data).filter((v, i) => i % 4 === 3 && v > 0).length
+    "#;
+
+    // Moving without a button held leaves the canvas blank
+    page.mouse_move(20.0, 20.0).await.expect("Failed to move");
+    page.mouse_move(60.0, 60.0).await.expect("Failed to move");
+    assert_eq!(page.evaluate::<u64>(painted).await.unwrap(), 0);
+
+    page.mouse_down(eoka::MouseButton::Left)
+        .await
+        .expect("Failed to press");
+    page.mouse_move(180.0, 100.0).await.expect("Failed to move");
+    page.mouse_move(100.0, 180.0).await.expect("Failed to move");
+    page.mouse_up(eoka::MouseButton::Left)
+        .await
+        .expect("Failed to release");
+
+    let pixels: u64 = page.evaluate(painted).await.unwrap();
+    assert!(pixels > 0, "canvas is empty");
+
+    // Released: further moves don't paint
+    page.mouse_move(10.0, 190.0).await.expect("Failed to move");
+    assert_eq!(page.evaluate::<u64>(painted).await.unwrap(), pixels);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_set_range_fires_events() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<input id='r' type='range' min='0' max='100'>\
+             <script>window.events=[];for(const t of ['input','change'])r.addEventListener(t,()=>events.push(t))</script>",
+        )
+        .await
+        .expect("Failed to create page");
+
+    page.set_range("#r", 75.0)
+        .await
+        .expect("Failed to set range");
+    let value = page
+        .find("#r")
+        .await
+        .expect("Failed to find range")
+        .value()
+        .await
+        .expect("Failed to read value");
+    assert_eq!(value, "75");
+    let events: Vec<String> = page.evaluate("window.events").await.unwrap();
+    assert_eq!(events, ["input", "change"]);
+
+    page.execute("document.body.insertAdjacentHTML('beforeend', '<input id=t>')")
+        .await
+        .unwrap();
+    assert!(matches!(
+        page.set_range("#t", 1.0).await,
+        Err(eoka::Error::InvalidState(_))
+    ));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_drag_slider() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    // A styled native range and a custom "slide to verify" slider whose
+    // handle follows the mouse while pressed. The handle is padded (its
+    // border box is wider than its content box) and sits below the fold.
+    let html = r#"
+        <body style="margin:40px">
+        <input id="native" type="range" min="0" max="100" value="0"
+            style="width:300px;accent-color:green">
+        <div id="track" style="position:relative;width:300px;height:40px;margin-top:1500px;background:#ddd">
+            <div id="handle" style="position:absolute;left:0;top:0;width:20px;padding:0 10px;height:40px;background:#333"></div>
+        </div>
+        <script>
+            const track = document.getElementById('track');
+            const handle = document.getElementById('handle');
+            let grab = null;
+            window.slid = 0;
+            handle.addEventListener('mousedown', e => { grab = e.clientX - handle.offsetLeft; });
+            document.addEventListener('mousemove', e => {
+                if (grab === null) return;
+                const left = Math.max(0, Math.min(260, e.clientX - grab));
+                handle.style.left = left + 'px';
+                window.slid = left / 260;
+            });
+            document.addEventListener('mouseup', () => { grab = null; });
+        </script>
+        </body>"#;
+    let port = serve_html(vec![("/", html.to_string())]);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .expect("Failed to create page");
+
+    page.drag_slider("#native", 0.5)
+        .await
+        .expect("Failed to drag range");
+    let value: f64 = page
+        .evaluate("parseFloat(document.getElementById('native').value)")
+        .await
+        .unwrap();
+    assert!((45.0..=55.0).contains(&value), "range at {}", value);
+
+    page.drag_slider("#handle", 1.0)
+        .await
+        .expect("Failed to drag handle");
+    let slid: f64 = page.evaluate("window.slid").await.unwrap();
+    assert!(slid > 0.95, "handle at {}", slid);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_wait_for_any_text_reports_index() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let html = r#"
+        <form onsubmit="event.preventDefault();
+            const ok = this.password.value === 'hunter2';
+            setTimeout(() => document.body.insertAdjacentHTML('beforeend',
+                ok ? '<p>Welcome</p>' : '<p>Invalid credentials</p>'), 300)">
+            <input name="password">
+            <button>Log in</button>
+        </form>"#;
+    let port = serve_html(vec![("/", html.to_string())]);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .expect("Failed to create page");
+    let outcomes = ["Welcome", "Invalid credentials"];
+
+    page.fill("input", "wrong").await.expect("Failed to fill");
+    page.click("button").await.expect("Failed to click");
+    let (index, element) = page
+        .wait_for_any_text(&outcomes, 5_000)
+        .await
+        .expect("No outcome appeared");
+    assert_eq!(index, 1);
+    assert_eq!(element.text().await.unwrap(), "Invalid credentials");
+
+    page.execute("document.querySelector('p').remove()")
+        .await
+        .unwrap();
+    page.fill("input", "hunter2").await.expect("Failed to fill");
+    page.click("button").await.expect("Failed to click");
+    let (index, _) = page
+        .wait_for_any_text(&outcomes, 5_000)
+        .await
+        .expect("No outcome appeared");
+    assert_eq!(index, 0);
+
+    assert!(matches!(
+        page.wait_for_any_text(&["Never shown", "Also absent"], 300)
+            .await,
+        Err(eoka::Error::Timeout(_))
+    ));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_expect_navigation_on_link_click() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let port = serve_html(vec![
+        (
+            "/",
+            r##"<a id="hash" href="#top">Top</a> <a id="next" href="/next">Next</a>"##.to_string(),
+        ),
+        ("/next", "<h1>Second page</h1>".to_string()),
+    ]);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .expect("Failed to create page");
+
+    // A same-document navigation isn't a new document
+    assert!(matches!(
+        page.expect_navigation(page.click("#hash"), 1_000).await,
+        Err(eoka::Error::Timeout(_))
+    ));
+
+    page.expect_navigation(page.click("#next"), 10_000)
+        .await
+        .expect("Navigation not detected");
+    assert!(page.url().await.unwrap().ends_with("/next"));
+    assert_eq!(
+        page.find("h1").await.unwrap().text().await.unwrap(),
+        "Second page"
+    );
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_set_inner_and_outer_html() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<main><div id='box'>old</div><span>after</span></main>")
+        .await
+        .expect("Failed to create page");
+
+    let div = page.find("#box").await.expect("Failed to find div");
+    div.set_inner_html("<b class='fresh'>new content</b>")
+        .await
+        .expect("Failed to set inner HTML");
+    let bold = page
+        .find("#box .fresh")
+        .await
+        .expect("Injected child missing");
+    assert_eq!(bold.text().await.unwrap(), "new content");
+
+    let replaced = div
+        .set_outer_html("text first <section id='swapped'>swapped</section>")
+        .await
+        .expect("Failed to set outer HTML")
+        .expect("No element in replacement");
+    assert_eq!(replaced.tag_name().await.unwrap(), "section");
+    assert_eq!(replaced.text().await.unwrap(), "swapped");
+    assert!(!page.exists("#box").await);
+    assert_eq!(
+        page.find("#swapped").await.unwrap().text().await.unwrap(),
+        "swapped"
+    );
+    assert_eq!(
+        page.text()
+            .await
+            .unwrap()
+            .split_whitespace()
+            .collect::<Vec<_>>(),
+        ["text", "first", "swapped", "after"]
+    );
+
+    let none = replaced
+        .set_outer_html("just text")
+        .await
+        .expect("Failed to set outer HTML");
+    assert!(none.is_none());
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_query_returns_option() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<p id='present'>here</p>")
+        .await
+        .expect("Failed to create page");
+
+    let present = page.query("#present").await.expect("Query failed");
+    assert_eq!(present.unwrap().text().await.unwrap(), "here");
+    assert!(page
+        .query("#missing")
+        .await
+        .expect("Query failed")
+        .is_none());
+    // An invalid selector is a real error, not "not found"
+    assert!(page.query("p[").await.is_err());
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_wait_for_count_incremental_list() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page(
+            "data:text/html,<ul id='list'></ul><script>let n=0;const t=setInterval(()=>{\
+             list.insertAdjacentHTML('beforeend','<li>Item '+(++n)+'</li>');if(n===8)clearInterval(t)},150)</script>",
+        )
+        .await
+        .expect("Failed to create page");
+
+    let items = page
+        .wait_for_count("#list li", 5, 5_000)
+        .await
+        .expect("List did not grow to 5");
+    assert!(items.len() >= 5);
+    assert_eq!(items[4].text().await.unwrap(), "Item 5");
+
+    assert!(matches!(
+        page.wait_for_count("#list li", 50, 500).await,
+        Err(eoka::Error::Timeout(_))
+    ));
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_warm_up_moves_and_scrolls() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let html = r#"
+        <body style="height:3000px">
+        <button id="go" style="position:fixed;top:20px;left:20px">Go</button>
+        <script>
+            window.log = { moves: 0, wheels: 0, last: null, wheelsBeforeClick: null };
+            document.addEventListener('mousemove', e => { log.moves++; log.last = [e.clientX, e.clientY]; });
+            document.addEventListener('wheel', () => log.wheels++);
+            go.addEventListener('click', () => log.wheelsBeforeClick = log.wheels);
+        </script>
+        </body>"#;
+    let port = serve_html(vec![("/", html.to_string())]);
+    let url = format!("http://127.0.0.1:{}/", port);
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser.new_page(&url).await.expect("Failed to create page");
+
+    page.mouse_move(50.0, 50.0).await.expect("Failed to move");
+    page.warm_up().await.expect("Failed to warm up");
+    let log: serde_json::Value = page.evaluate("window.log").await.unwrap();
+    assert!(log["moves"].as_u64().unwrap() > 2, "{}", log);
+    assert!(log["wheels"].as_u64().unwrap() >= 2, "{}", log);
+    assert_ne!(log["last"], serde_json::json!([50, 50]));
+    let scroll_y: f64 = page.evaluate("scrollY").await.unwrap();
+    assert_eq!(scroll_y, 0.0);
+
+    // At the bottom the way down is clamped; the page still ends up in place
+    page.execute("scrollTo(0, document.body.scrollHeight)")
+        .await
+        .unwrap();
+    let bottom: f64 = page.evaluate("scrollY").await.unwrap();
+    page.warm_up().await.expect("Failed to warm up");
+    let scroll_y: f64 = page.evaluate("scrollY").await.unwrap();
+    assert_eq!(scroll_y, bottom);
+    browser.close().await.expect("Failed to close browser");
+
+    // With the config flag, the first human click is preceded by a warm-up
+    let config = StealthConfig {
+        warm_up: true,
+        ..Default::default()
+    };
+    let browser = Browser::launch_with_config(config)
+        .await
+        .expect("Failed to launch browser");
+    let page = browser.new_page(&url).await.expect("Failed to create page");
+    page.human_click("#go").await.expect("Failed to click");
+    let before_click: u64 = page.evaluate("window.log.wheelsBeforeClick").await.unwrap();
+    assert!(before_click >= 2);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_font_probes_follow_fingerprint_platform() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let mut fingerprint = eoka::Fingerprint::random();
+    fingerprint.platform = eoka::stealth::Platform::Windows;
+    let config = StealthConfig {
+        fingerprint: Some(fingerprint),
+        ..Default::default()
+    };
+    let browser = Browser::launch_with_config(config)
+        .await
+        .expect("Failed to launch browser");
+    let html =
+        "<span id='probe' style=\"font-family: 'No Such Font', monospace\">mmmmmmmmmmlli</span>\
+                <span id='base' style='font-family: monospace'>mmmmmmmmmmlli</span>";
+    let port = serve_html(vec![("/", html.to_string())]);
+    let page = browser
+        .new_page(&format!("http://127.0.0.1:{}/", port))
+        .await
+        .expect("Failed to create page");
+
+    let platform: String = page.evaluate("navigator.platform").await.unwrap();
+    assert_eq!(platform, "Win32");
+
+    // Reading sizes never restyles the element
+    let result: serde_json::Value = page
+        .evaluate(
+            "new Promise(resolve => {
+                let mutations = 0;
+                new MutationObserver(r => mutations += r.length)
+                    .observe(document, { attributes: true, subtree: true });
+                const widths = [probe.offsetWidth, base.offsetWidth];
+                setTimeout(() => resolve({ mutations, widths }), 50);
+            })",
+        )
+        .await
+        .expect("Failed to probe");
+    assert_eq!(result["mutations"], 0);
+    assert_eq!(result["widths"][0], result["widths"][1]);
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_random_identity_is_stable_across_pages() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let identity_js = "[navigator.userAgent, navigator.platform, screen.width, screen.height]";
+    let mut identities = Vec::new();
+    for _ in 0..2 {
+        let page = browser
+            .new_page("data:text/html,<p>identity</p>")
+            .await
+            .expect("Failed to create page");
+        let identity: serde_json::Value = page.evaluate(identity_js).await.unwrap();
+        identities.push(identity);
+    }
+    assert_eq!(identities[0], identities[1]);
+
+    // The launch UA and the evasion script describe the same platform
+    let fingerprint = browser.fingerprint();
+    assert_eq!(identities[0][0], fingerprint.user_agent.as_str());
+    assert_eq!(identities[0][1], fingerprint.platform.navigator_platform());
+
+    browser.close().await.expect("Failed to close browser");
+}
+
+#[tokio::test]
+#[ignore = "requires Chrome"]
+async fn test_webgl_follows_fingerprint() {
+    if !chrome_available() {
+        eprintln!("Chrome not found, skipping test");
+        return;
+    }
+
+    let browser = Browser::launch().await.expect("Failed to launch browser");
+    let page = browser
+        .new_page("data:text/html,<canvas></canvas>")
+        .await
+        .expect("Failed to create page");
+    let webgl: serde_json::Value = page
+        .evaluate(
+            "(() => {
+                const gl = document.createElement('canvas').getContext('webgl');
+                const info = gl.getExtension('WEBGL_debug_renderer_info');
+                const format = gl.getShaderPrecisionFormat(gl.FRAGMENT_SHADER, gl.HIGH_FLOAT);
+                return {
+                    renderer: gl.getParameter(info.UNMASKED_RENDERER_WEBGL),
+                    ownProps: Object.getOwnPropertyNames(format).length,
+                    precision: format.precision,
+                };
+            })()",
+        )
+        .await
+        .expect("Failed to read WebGL");
+
+    assert_eq!(
+        webgl["renderer"],
+        browser.fingerprint().webgl_renderer.as_str()
+    );
+    assert_eq!(webgl["ownProps"], 0);
+    assert_eq!(webgl["precision"], 23);
+
+    browser.close().await.expect("Failed to close browser");
+}